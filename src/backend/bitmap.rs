@@ -0,0 +1,440 @@
+use super::{string::MAX_STRING_LEN, Backend, Value};
+use crate::{BulkString, CommandError};
+
+/// The largest bit offset `SETBIT` accepts, the last bit of the largest string.
+pub const MAX_BIT_OFFSET: u64 = MAX_STRING_LEN as u64 * 8 - 1;
+
+/// The unit of the range given to `BITCOUNT` and `BITPOS`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BitUnit {
+  #[default]
+  Byte,
+  Bit,
+}
+
+/// The operation of `BITOP`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitwiseOp {
+  And,
+  Or,
+  Xor,
+  Not,
+}
+
+/// An integer type of `BITFIELD`, `i1` to `i64` or `u1` to `u63`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitFieldType {
+  pub signed: bool,
+  pub bits: u32,
+}
+
+/// How `BITFIELD` handles a `SET` or `INCRBY` that does not fit the type.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BitOverflow {
+  #[default]
+  Wrap,
+  Sat,
+  Fail,
+}
+
+/// One subcommand of `BITFIELD`, `offset` being in bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitFieldOp {
+  Get { ty: BitFieldType, offset: u64 },
+  Set { ty: BitFieldType, offset: u64, value: i64, overflow: BitOverflow },
+  IncrBy { ty: BitFieldType, offset: u64, increment: i64, overflow: BitOverflow },
+}
+
+impl BitFieldType {
+  fn mask(self) -> u64 {
+    if self.bits == 64 {
+      u64::MAX
+    } else {
+      (1 << self.bits) - 1
+    }
+  }
+
+  fn min(self) -> i128 {
+    if self.signed {
+      -(1 << (self.bits - 1))
+    } else {
+      0
+    }
+  }
+
+  fn max(self) -> i128 {
+    if self.signed {
+      (1 << (self.bits - 1)) - 1
+    } else {
+      (1 << self.bits) - 1
+    }
+  }
+
+  /// The value of the low `bits` bits of `raw`, sign extended for signed types.
+  fn value_of(self, raw: u64) -> i64 {
+    let raw = raw & self.mask();
+    if self.signed && self.bits < 64 && raw & (1 << (self.bits - 1)) != 0 {
+      (raw | !self.mask()) as i64
+    } else {
+      raw as i64
+    }
+  }
+
+  /// Make `value` fit the type as `overflow` says, `None` if it fails.
+  fn fit(self, value: i128, overflow: BitOverflow) -> Option<i64> {
+    if (self.min()..=self.max()).contains(&value) {
+      return Some(value as i64);
+    }
+    match overflow {
+      // the low 64 bits of an i128 are its two's complement truncation
+      BitOverflow::Wrap => Some(self.value_of(value as u64)),
+      BitOverflow::Sat => Some(value.clamp(self.min(), self.max()) as i64),
+      BitOverflow::Fail => None,
+    }
+  }
+}
+
+impl BitFieldOp {
+  fn is_read(&self) -> bool {
+    matches!(self, BitFieldOp::Get { .. })
+  }
+
+  /// Run the subcommand on `bytes`, returns its reply or `None` if it failed on overflow.
+  fn apply(&self, bytes: &mut Vec<u8>) -> Option<i64> {
+    match *self {
+      BitFieldOp::Get { ty, offset } => Some(ty.value_of(read_bits(bytes, offset, ty.bits))),
+      BitFieldOp::Set { ty, offset, value, overflow } => {
+        let value = ty.fit(value as i128, overflow)?;
+        let old = ty.value_of(read_bits(bytes, offset, ty.bits));
+        write_bits(bytes, offset, ty.bits, value as u64);
+        Some(old)
+      }
+      BitFieldOp::IncrBy { ty, offset, increment, overflow } => {
+        let old = ty.value_of(read_bits(bytes, offset, ty.bits));
+        let value = ty.fit(old as i128 + increment as i128, overflow)?;
+        write_bits(bytes, offset, ty.bits, value as u64);
+        Some(value)
+      }
+    }
+  }
+}
+
+/// The `bits` bits from `offset` on as an unsigned integer, bits past the end being 0.
+fn read_bits(bytes: &[u8], offset: u64, bits: u32) -> u64 {
+  (offset..offset + bits as u64).fold(0, |acc, i| {
+    let byte = bytes.get((i / 8) as usize).copied().unwrap_or_default();
+    acc << 1 | ((byte >> (7 - i % 8)) & 1) as u64
+  })
+}
+
+/// Store the low `bits` bits of `raw` from `offset` on, growing `bytes` as needed.
+fn write_bits(bytes: &mut Vec<u8>, offset: u64, bits: u32, raw: u64) {
+  let end = (offset + bits as u64).div_ceil(8) as usize;
+  if bytes.len() < end {
+    bytes.resize(end, 0);
+  }
+  for (n, i) in (offset..offset + bits as u64).enumerate() {
+    let mask = 0x80 >> (i % 8);
+    if (raw >> (bits as usize - 1 - n)) & 1 == 1 {
+      bytes[(i / 8) as usize] |= mask;
+    } else {
+      bytes[(i / 8) as usize] &= !mask;
+    }
+  }
+}
+
+/// Resolve an inclusive range of `len` units where negative offsets count from the end, the
+/// way redis does for bitmaps: out of range offsets are clamped rather than rejected.
+fn normalize_bit_range(start: i64, end: i64, len: i64) -> Option<(i64, i64)> {
+  let start = if start < 0 { start + len } else { start }.max(0);
+  let end = if end < 0 { end + len } else { end }.max(0).min(len - 1);
+  (start <= end).then_some((start, end))
+}
+
+/// Turn a range in `unit` into an inclusive range of bits.
+fn bit_range(range: Option<(i64, i64, BitUnit)>, len: usize) -> Option<(usize, usize)> {
+  let (start, end, unit) = range.unwrap_or((0, -1, BitUnit::Byte));
+  match unit {
+    BitUnit::Byte => normalize_bit_range(start, end, len as i64)
+      .map(|(start, end)| (start as usize * 8, end as usize * 8 + 7)),
+    BitUnit::Bit => normalize_bit_range(start, end, len as i64 * 8)
+      .map(|(start, end)| (start as usize, end as usize)),
+  }
+}
+
+/// The bytes covering the bits `start..=end`, each with a mask of the bits inside the range.
+/// Bit 0 is the most significant bit of the first byte.
+fn masked_bytes(
+  bytes: &[u8],
+  start: usize,
+  end: usize,
+) -> impl Iterator<Item = (usize, u8, u8)> + '_ {
+  (start / 8..=end / 8).map(move |i| {
+    let mut mask = 0xff;
+    if i == start / 8 {
+      mask &= 0xff >> (start % 8);
+    }
+    if i == end / 8 {
+      mask &= 0xff << (7 - end % 8);
+    }
+    (i, bytes[i], mask)
+  })
+}
+
+impl Backend {
+  /// Set or clear the bit at `offset`, growing the string with zero bytes as needed.
+  /// Returns the previous value of the bit.
+  pub fn setbit(&self, key: &str, offset: u64, bit: bool) -> Result<bool, CommandError> {
+    if offset > MAX_BIT_OFFSET {
+      return Err(CommandError::InvalidBitOffset);
+    }
+    let (byte, mask) = ((offset / 8) as usize, 0x80u8 >> (offset % 8));
+    self.update_string(key, |old| {
+      let mut bytes = old.map(<[u8]>::to_vec).unwrap_or_default();
+      if bytes.len() <= byte {
+        bytes.resize(byte + 1, 0);
+      }
+      let old = bytes[byte] & mask != 0;
+      if bit {
+        bytes[byte] |= mask;
+      } else {
+        bytes[byte] &= !mask;
+      }
+      Ok((bytes, old))
+    })
+  }
+
+  /// The bit at `offset`, bits past the end of the string are 0.
+  pub fn getbit(&self, key: &str, offset: u64) -> Result<bool, CommandError> {
+    let bytes = self.read_string(key)?.unwrap_or_default();
+    let byte = bytes.get((offset / 8) as usize).copied().unwrap_or_default();
+    Ok(byte & (0x80 >> (offset % 8)) != 0)
+  }
+
+  /// The number of set bits, in the whole string or in `range`.
+  pub fn bitcount(
+    &self,
+    key: &str,
+    range: Option<(i64, i64, BitUnit)>,
+  ) -> Result<usize, CommandError> {
+    let bytes = self.read_string(key)?.unwrap_or_default();
+    let Some((start, end)) = bit_range(range, bytes.len()) else {
+      return Ok(0);
+    };
+    Ok(
+      masked_bytes(&bytes, start, end)
+        .map(|(_, byte, mask)| (byte & mask).count_ones() as usize)
+        .sum(),
+    )
+  }
+
+  /// The position of the first bit set to `bit`, in the whole string or from `start` to `end`.
+  /// Without an explicit `end` the string is taken as padded with zeros on the right, so
+  /// looking for a clear bit in an all ones string finds the first bit past its end.
+  pub fn bitpos(
+    &self,
+    key: &str,
+    bit: bool,
+    start: i64,
+    end: Option<i64>,
+    unit: BitUnit,
+  ) -> Result<i64, CommandError> {
+    let Some(bytes) = self.read_string(key)? else {
+      return Ok(if bit { -1 } else { 0 });
+    };
+    let Some((start, end_bit)) = bit_range(Some((start, end.unwrap_or(-1), unit)), bytes.len())
+    else {
+      return Ok(-1);
+    };
+
+    for (i, byte, mask) in masked_bytes(&bytes, start, end_bit) {
+      let found = if bit { byte } else { !byte } & mask;
+      if found != 0 {
+        return Ok((i * 8) as i64 + found.leading_zeros() as i64);
+      }
+    }
+    Ok(if !bit && end.is_none() { end_bit as i64 + 1 } else { -1 })
+  }
+
+  /// Run the `BITFIELD` subcommands in order on the string at `key`. Only subcommands that
+  /// write create the key.
+  pub fn bitfield(&self, key: &str, ops: &[BitFieldOp]) -> Result<Vec<Option<i64>>, CommandError> {
+    let apply = |bytes: &mut Vec<u8>| ops.iter().map(|op| op.apply(bytes)).collect::<Vec<_>>();
+    if ops.iter().all(BitFieldOp::is_read) {
+      let mut bytes = self.read_string(key)?.unwrap_or_default();
+      return Ok(apply(&mut bytes));
+    }
+    self.update_string(key, |old| {
+      let mut bytes = old.map(<[u8]>::to_vec).unwrap_or_default();
+      let ret = apply(&mut bytes);
+      Ok((bytes, ret))
+    })
+  }
+
+  /// Combine the strings at `keys` byte by byte into `dst`, missing keys and shorter strings
+  /// being padded with zeros. Returns the length of the result, an empty one deletes `dst`.
+  pub fn bitop(&self, op: BitwiseOp, dst: &str, keys: &[String]) -> Result<usize, CommandError> {
+    if op == BitwiseOp::Not && keys.len() != 1 {
+      return Err(CommandError::InvalidArgument(
+        "BITOP NOT must be called with a single source key.".to_string(),
+      ));
+    }
+    let sources = keys.iter().map(|key| self.read_string(key)).collect::<Result<Vec<_>, _>>()?;
+    let len = sources.iter().map(|s| s.as_ref().map_or(0, Vec::len)).max().unwrap_or(0);
+
+    let byte_at = |source: &Option<Vec<u8>>, i: usize| {
+      source.as_ref().and_then(|s| s.get(i)).copied().unwrap_or_default()
+    };
+    let result = (0..len)
+      .map(|i| {
+        let mut bytes = sources.iter().map(|source| byte_at(source, i));
+        let first = bytes.next().unwrap_or_default();
+        match op {
+          BitwiseOp::And => bytes.fold(first, |acc, b| acc & b),
+          BitwiseOp::Or => bytes.fold(first, |acc, b| acc | b),
+          BitwiseOp::Xor => bytes.fold(first, |acc, b| acc ^ b),
+          BitwiseOp::Not => !first,
+        }
+      })
+      .collect::<Vec<u8>>();
+
+    if result.is_empty() {
+      self.remove(dst);
+    } else {
+      self.expires.remove(dst);
+      self.keyspace.insert(dst.to_string(), Value::String(BulkString::new(result).into()));
+    }
+    Ok(len)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_setbit_getbit() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    assert!(!backend.setbit("b", 7, true)?);
+    assert!(backend.setbit("b", 7, true)?);
+    assert_eq!(backend.get("b")?, Some(BulkString::new(vec![0x01]).into()));
+    assert!(backend.getbit("b", 7)?);
+    assert!(!backend.getbit("b", 0)?);
+    assert!(!backend.getbit("b", 100)?);
+
+    assert!(!backend.setbit("b", 8, true)?);
+    assert!(backend.setbit("b", 7, false)?);
+    assert_eq!(backend.get("b")?, Some(BulkString::new(vec![0x00, 0x80]).into()));
+    assert_eq!(backend.setbit("b", MAX_BIT_OFFSET + 1, true), Err(CommandError::InvalidBitOffset));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_bitcount() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.set("s".to_string(), BulkString::from("foobar").into());
+    assert_eq!(backend.bitcount("s", None)?, 26);
+    assert_eq!(backend.bitcount("s", Some((0, 0, BitUnit::Byte)))?, 4);
+    assert_eq!(backend.bitcount("s", Some((1, 1, BitUnit::Byte)))?, 6);
+    assert_eq!(backend.bitcount("s", Some((1, 1, BitUnit::Bit)))?, 1);
+    assert_eq!(backend.bitcount("s", Some((5, 30, BitUnit::Bit)))?, 17);
+    assert_eq!(backend.bitcount("s", Some((-1, -2, BitUnit::Byte)))?, 0);
+    assert_eq!(backend.bitcount("missing", None)?, 0);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_bitpos() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.set("s".to_string(), BulkString::new(vec![0xff, 0xf0, 0x00]).into());
+    assert_eq!(backend.bitpos("s", false, 0, None, BitUnit::Byte)?, 12);
+    assert_eq!(backend.bitpos("s", true, 2, None, BitUnit::Byte)?, -1);
+    assert_eq!(backend.bitpos("s", true, 2, Some(-1), BitUnit::Byte)?, -1);
+    assert_eq!(backend.bitpos("s", false, 7, Some(15), BitUnit::Bit)?, 12);
+    assert_eq!(backend.bitpos("s", true, 7, Some(15), BitUnit::Bit)?, 7);
+
+    backend.set("ones".to_string(), BulkString::new(vec![0xff, 0xff]).into());
+    assert_eq!(backend.bitpos("ones", false, 0, None, BitUnit::Byte)?, 16);
+    assert_eq!(backend.bitpos("ones", false, 0, Some(-1), BitUnit::Byte)?, -1);
+
+    assert_eq!(backend.bitpos("missing", false, 0, None, BitUnit::Byte)?, 0);
+    assert_eq!(backend.bitpos("missing", true, 0, None, BitUnit::Byte)?, -1);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_bitop() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.set("a".to_string(), BulkString::from("foobar").into());
+    backend.set("b".to_string(), BulkString::from("abcdef").into());
+    let keys = ["a", "b"].map(String::from);
+
+    assert_eq!(backend.bitop(BitwiseOp::And, "dst", &keys)?, 6);
+    assert_eq!(backend.get("dst")?, Some(BulkString::from("`bc`ab").into()));
+    backend.bitop(BitwiseOp::Or, "dst", &keys)?;
+    assert_eq!(backend.get("dst")?, Some(BulkString::from("goofev").into()));
+    backend.bitop(BitwiseOp::Xor, "dst", &["a".to_string(), "missing".to_string()])?;
+    assert_eq!(backend.get("dst")?, Some(BulkString::from("foobar").into()));
+    backend.bitop(BitwiseOp::Not, "dst", &["a".to_string()])?;
+    assert!(backend.getbit("dst", 0)?);
+    assert!(backend.bitop(BitwiseOp::Not, "dst", &keys).is_err());
+
+    assert_eq!(backend.bitop(BitwiseOp::And, "dst", &["missing".to_string()])?, 0);
+    assert!(!backend.exists("dst"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_bitfield_type() {
+    let i8 = BitFieldType { signed: true, bits: 8 };
+    assert_eq!(i8.value_of(0xff), -1);
+    assert_eq!(i8.fit(128, BitOverflow::Wrap), Some(-128));
+    assert_eq!(i8.fit(-129, BitOverflow::Wrap), Some(127));
+    assert_eq!(i8.fit(300, BitOverflow::Sat), Some(127));
+    assert_eq!(i8.fit(128, BitOverflow::Fail), None);
+
+    let u2 = BitFieldType { signed: false, bits: 2 };
+    assert_eq!(u2.fit(5, BitOverflow::Wrap), Some(1));
+    assert_eq!(u2.fit(-1, BitOverflow::Wrap), Some(3));
+    assert_eq!(u2.fit(-1, BitOverflow::Sat), Some(0));
+
+    let i64 = BitFieldType { signed: true, bits: 64 };
+    assert_eq!(i64.fit(i64::MAX as i128 + 1, BitOverflow::Wrap), Some(i64::MIN));
+    assert_eq!(i64.fit(i64::MAX as i128 + 1, BitOverflow::Sat), Some(i64::MAX));
+  }
+
+  #[test]
+  fn test_bitfield() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    let i5 = BitFieldType { signed: true, bits: 5 };
+    let u4 = BitFieldType { signed: false, bits: 4 };
+
+    let get = [BitFieldOp::Get { ty: u4, offset: 0 }];
+    assert_eq!(backend.bitfield("b", &get)?, [Some(0)]);
+    assert!(!backend.exists("b"));
+
+    let ops = [
+      BitFieldOp::Set { ty: i5, offset: 100, value: 1, overflow: BitOverflow::Wrap },
+      BitFieldOp::Get { ty: u4, offset: 0 },
+      BitFieldOp::IncrBy { ty: i5, offset: 100, increment: 16, overflow: BitOverflow::Wrap },
+      BitFieldOp::IncrBy { ty: i5, offset: 100, increment: -2, overflow: BitOverflow::Fail },
+      BitFieldOp::IncrBy { ty: i5, offset: 100, increment: -100, overflow: BitOverflow::Sat },
+    ];
+    assert_eq!(backend.bitfield("b", &ops)?, [Some(0), Some(0), Some(-15), None, Some(-16)]);
+    assert_eq!(backend.strlen("b")?, 14);
+
+    backend.set("s".to_string(), BulkString::new(vec![0xf0]).into());
+    let ops = [
+      BitFieldOp::Get { ty: i5, offset: 0 },
+      BitFieldOp::Set { ty: u4, offset: 4, value: 0xa, overflow: BitOverflow::Wrap },
+      BitFieldOp::Get { ty: BitFieldType { signed: false, bits: 8 }, offset: 0 },
+    ];
+    assert_eq!(backend.bitfield("s", &ops)?, [Some(-2), Some(0), Some(0xfa)]);
+
+    Ok(())
+  }
+}
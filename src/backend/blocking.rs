@@ -0,0 +1,353 @@
+use super::Backend;
+use crate::RespFrame;
+use std::{
+  cell::RefCell,
+  collections::VecDeque,
+  fmt,
+  sync::{Arc, Mutex, PoisonError},
+  time::Duration,
+};
+use tokio::sync::oneshot;
+
+/// Try to serve a blocked client from `key`, returns the reply if it was served.
+type Serve = Box<dyn Fn(&Backend, &str) -> Option<RespFrame> + Send + Sync>;
+
+/// A client parked on one or more keys until a write makes one of them ready.
+pub struct Waiter {
+  keys: Vec<String>,
+  // whether `serve` touches several keys and so needs `command_lock` to itself
+  exclusive: bool,
+  serve: Serve,
+  // taken by whoever finishes the wait first: a write serving the client or the timeout
+  tx: Mutex<Option<oneshot::Sender<RespFrame>>>,
+}
+
+impl fmt::Debug for Waiter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Waiter")
+      .field("keys", &self.keys)
+      .field("exclusive", &self.exclusive)
+      .finish_non_exhaustive()
+  }
+}
+
+/// Unparks the waiter when the wait ends, including when the client goes away mid-wait.
+struct Parked<'a> {
+  backend: &'a Backend,
+  waiter: Arc<Waiter>,
+}
+
+impl Drop for Parked<'_> {
+  fn drop(&mut self) {
+    self.backend.unpark(&self.waiter);
+  }
+}
+
+/// Serves the keys made ready under `command_lock` once it is released, see `Backend::locked`.
+struct ServeReady<'a>(&'a Backend);
+
+impl Drop for ServeReady<'_> {
+  fn drop(&mut self) {
+    if std::thread::panicking() {
+      READY_KEYS.with_borrow_mut(|ready| *ready = None);
+    } else {
+      self.0.serve_ready();
+    }
+  }
+}
+
+thread_local! {
+  // keys made ready while this thread holds `command_lock` or is already serving waiters, they
+  // are served by the outermost of those once it is done, see `signal_ready`
+  static READY_KEYS: RefCell<Option<VecDeque<String>>> = const { RefCell::new(None) };
+}
+
+impl Backend {
+  /// Serve the client from the first of `keys` that `serve` succeeds on, or park it until a
+  /// write to one of them does. Waits forever if `timeout` is `None`, returns `None` if it
+  /// elapses first.
+  ///
+  /// `serve` always runs under `command_lock`, held exclusively if `exclusive` is set, as the
+  /// non-blocking form of the command would. No lock is held while parked: writers call
+  /// `signal_ready`, which runs `serve` for the waiters of that key in FIFO order once the
+  /// writer released the lock.
+  pub async fn block_on<F>(
+    &self,
+    keys: Vec<String>,
+    timeout: Option<Duration>,
+    exclusive: bool,
+    serve: F,
+  ) -> Option<RespFrame>
+  where
+    F: Fn(&Backend, &str) -> Option<RespFrame> + Send + Sync + 'static,
+  {
+    if let Some(frame) = self.locked(exclusive, || keys.iter().find_map(|key| serve(self, key))) {
+      return Some(frame);
+    }
+
+    let (tx, mut rx) = oneshot::channel();
+    let serve = Box::new(serve);
+    let waiter = Arc::new(Waiter { keys, exclusive, serve, tx: Mutex::new(Some(tx)) });
+    let _parked = self.park(waiter.clone());
+    // a write may have landed between the first attempt and parking
+    for key in &waiter.keys {
+      self.signal_ready(key);
+    }
+
+    let ret = match timeout {
+      Some(timeout) => tokio::time::timeout(timeout, &mut rx).await.ok(),
+      None => Some((&mut rx).await),
+    };
+    match ret {
+      Some(Ok(frame)) => Some(frame),
+      _ => {
+        // stop writers from serving us, then pick up a reply that raced with the timeout
+        waiter.tx.lock().unwrap().take();
+        rx.try_recv().ok()
+      }
+    }
+  }
+
+  /// Run `f` under `command_lock`, held exclusively if `exclusive` is set. Clients blocked on
+  /// the keys it writes are served once the lock is released.
+  pub(crate) fn locked<T>(&self, exclusive: bool, f: impl FnOnce() -> T) -> T {
+    let outermost = READY_KEYS.with_borrow_mut(|ready| match ready {
+      Some(_) => false,
+      None => {
+        *ready = Some(VecDeque::new());
+        true
+      }
+    });
+    // declared first so it runs after the guard is dropped
+    let _serve_ready = outermost.then_some(ServeReady(self));
+    let lock = &self.command_lock;
+    if exclusive {
+      let _guard = lock.write().unwrap_or_else(PoisonError::into_inner);
+      f()
+    } else {
+      let _guard = lock.read().unwrap_or_else(PoisonError::into_inner);
+      f()
+    }
+  }
+
+  /// Serve the clients blocked on `key`, called after a write that may have made it ready.
+  pub(crate) fn signal_ready(&self, key: &str) {
+    // a writer holding `command_lock` would deadlock with `serve` taking it, and serving a
+    // client may write to another key, e.g. BLMOVE; either way queue the key for the outermost
+    // call instead of serving it here, so a thread never holds the lock and a waiter at once
+    let queued = READY_KEYS.with_borrow_mut(|ready| match ready {
+      Some(ready) => {
+        ready.push_back(key.to_string());
+        true
+      }
+      None => {
+        *ready = Some(VecDeque::from([key.to_string()]));
+        false
+      }
+    });
+    if !queued {
+      self.serve_ready();
+    }
+  }
+
+  fn serve_ready(&self) {
+    loop {
+      let next = READY_KEYS.with_borrow_mut(|ready| {
+        let next = ready.as_mut().and_then(|ready| ready.pop_front());
+        if next.is_none() {
+          *ready = None;
+        }
+        next
+      });
+      match next {
+        Some(key) => self.serve_waiters(&key),
+        None => break,
+      }
+    }
+  }
+
+  fn serve_waiters(&self, key: &str) {
+    let waiters = match self.blocked.lock().unwrap().get(key) {
+      Some(queue) => queue.iter().cloned().collect::<Vec<_>>(),
+      None => return,
+    };
+    for waiter in waiters {
+      let mut tx = waiter.tx.lock().unwrap();
+      match tx.take() {
+        Some(sender) if !sender.is_closed() => {
+          match self.locked(waiter.exclusive, || (waiter.serve)(self, key)) {
+            Some(frame) => {
+              let _ = sender.send(frame);
+            }
+            None => {
+              // keeps waiting, but the next one may still be served: stream readers don't
+              // consume what they read and may wait for different IDs
+              *tx = Some(sender);
+              continue;
+            }
+          }
+        }
+        // timed out or disconnected, its owner is about to unpark it
+        _ => {}
+      }
+      drop(tx);
+      self.unpark(&waiter);
+    }
+  }
+
+  fn park(&self, waiter: Arc<Waiter>) -> Parked<'_> {
+    let mut blocked = self.blocked.lock().unwrap();
+    for key in &waiter.keys {
+      blocked.entry(key.clone()).or_default().push_back(waiter.clone());
+    }
+    Parked { backend: self, waiter }
+  }
+
+  fn unpark(&self, waiter: &Arc<Waiter>) {
+    let mut blocked = self.blocked.lock().unwrap();
+    for key in &waiter.keys {
+      if let Some(queue) = blocked.get_mut(key) {
+        queue.retain(|w| !Arc::ptr_eq(w, waiter));
+        if queue.is_empty() {
+          blocked.remove(key);
+        }
+      }
+    }
+  }
+
+  #[cfg(test)]
+  fn blocked_on(&self, key: &str) -> usize {
+    self.blocked.lock().unwrap().get(key).map_or(0, |queue| queue.len())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{ListEnd, StreamId, StreamIdSpec};
+  use std::time::Instant;
+
+  fn pop(backend: &Backend, key: &str) -> Option<RespFrame> {
+    backend.pop(key, ListEnd::Left, 1).ok().flatten().and_then(|mut values| values.pop())
+  }
+
+  async fn wait_parked(backend: &Backend, key: &str, n: usize) {
+    while backend.blocked_on(key) < n {
+      tokio::task::yield_now().await;
+    }
+  }
+
+  #[tokio::test]
+  async fn test_block_on_times_out() {
+    let backend = Backend::new();
+    let start = Instant::now();
+    let ret =
+      backend.block_on(vec!["list".to_string()], Some(Duration::from_millis(50)), false, pop).await;
+
+    assert_eq!(ret, None);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert_eq!(backend.blocked_on("list"), 0);
+  }
+
+  #[tokio::test]
+  async fn test_block_on_serves_in_fifo_order() -> anyhow::Result<()> {
+    let backend = Backend::new();
+    let mut handles = vec![];
+    for i in 0..2 {
+      let cloned = backend.clone();
+      handles.push(tokio::spawn(async move {
+        cloned.block_on(vec!["a".to_string(), "list".to_string()], None, false, pop).await
+      }));
+      wait_parked(&backend, "list", i + 1).await;
+    }
+
+    backend.push("list".to_string(), vec![RespFrame::Integer(1)], ListEnd::Right, false)?;
+    backend.push("list".to_string(), vec![RespFrame::Integer(2)], ListEnd::Right, false)?;
+    for (i, handle) in handles.into_iter().enumerate() {
+      assert_eq!(handle.await?, Some(RespFrame::Integer(i as i64 + 1)));
+    }
+    // served clients leave every queue they were parked on
+    assert_eq!(backend.blocked_on("a"), 0);
+    assert_eq!(backend.llen("list")?, 0);
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_block_on_serves_every_stream_reader() -> anyhow::Result<()> {
+    let backend = Backend::new();
+    let mut handles = vec![];
+    // the first reader waits for entries past the one about to be added
+    for (i, after) in [StreamId::new(10, 0), StreamId::MIN, StreamId::MIN].into_iter().enumerate() {
+      let cloned = backend.clone();
+      let serve = move |backend: &Backend, key: &str| {
+        let entries = backend.xread(key, after, None).ok()?;
+        (!entries.is_empty()).then_some(RespFrame::Integer(entries.len() as i64))
+      };
+      handles.push(tokio::spawn(async move {
+        cloned.block_on(vec!["stream".to_string()], None, false, serve).await
+      }));
+      wait_parked(&backend, "stream", i + 1).await;
+    }
+
+    let id = StreamIdSpec::Explicit(StreamId::new(5, 0));
+    backend.xadd("stream", id, vec![], false, None)?;
+    let first = handles.remove(0);
+    for handle in handles {
+      assert_eq!(handle.await?, Some(RespFrame::Integer(1)));
+    }
+    assert_eq!(backend.blocked_on("stream"), 1);
+    first.abort();
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_block_on_served_after_writer_releases_lock() -> anyhow::Result<()> {
+    let backend = Backend::new();
+    let mut handles = vec![];
+    // an exclusive waiter would deadlock if it were served while the writer holds the lock
+    for (i, exclusive) in [true, false].into_iter().enumerate() {
+      let cloned = backend.clone();
+      handles.push(tokio::spawn(async move {
+        cloned.block_on(vec!["list".to_string()], None, exclusive, pop).await
+      }));
+      wait_parked(&backend, "list", i + 1).await;
+    }
+
+    for (i, exclusive) in [true, false].into_iter().enumerate() {
+      backend.locked(exclusive, || {
+        backend.push("list".to_string(), vec![RespFrame::Integer(1)], ListEnd::Right, false)?;
+        // nobody is served until the lock is released
+        assert_eq!(backend.blocked_on("list"), 2 - i);
+        assert_eq!(backend.llen("list")?, 1);
+        anyhow::Ok(())
+      })?;
+    }
+    for handle in handles {
+      assert_eq!(handle.await?, Some(RespFrame::Integer(1)));
+    }
+    assert_eq!(backend.llen("list")?, 0);
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_block_on_cancelled_is_not_served() -> anyhow::Result<()> {
+    let backend = Backend::new();
+    let cloned = backend.clone();
+    let handle =
+      tokio::spawn(
+        async move { cloned.block_on(vec!["list".to_string()], None, false, pop).await },
+      );
+    wait_parked(&backend, "list", 1).await;
+    handle.abort();
+    assert!(handle.await.is_err());
+
+    assert_eq!(backend.blocked_on("list"), 0);
+    backend.push("list".to_string(), vec![RespFrame::Integer(1)], ListEnd::Right, false)?;
+    assert_eq!(backend.llen("list")?, 1);
+
+    Ok(())
+  }
+}
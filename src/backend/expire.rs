@@ -0,0 +1,157 @@
+use super::Backend;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+const ACTIVE_EXPIRE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The `NX | XX | GT | LT` options shared by the `EXPIRE` command family.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExpireCondition {
+  pub nx: bool,
+  pub xx: bool,
+  pub gt: bool,
+  pub lt: bool,
+}
+
+/// Current unix time in milliseconds, the unit of every deadline kept in `expires`.
+pub fn now_ms() -> i64 {
+  SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or_default()
+}
+
+impl ExpireCondition {
+  fn allows(&self, current: Option<i64>, at: i64) -> bool {
+    match current {
+      Some(current) => !self.nx && (!self.gt || at > current) && (!self.lt || at < current),
+      // a key without a ttl behaves like one with an infinite ttl
+      None => !self.xx && !self.gt,
+    }
+  }
+}
+
+impl Backend {
+  /// Set the absolute deadline of `key` in unix milliseconds, a deadline in the past deletes
+  /// the key right away. Returns false if the key does not exist or `cond` was not met.
+  pub fn expire_at(&self, key: &str, at: i64, cond: ExpireCondition) -> bool {
+    if !self.exists(key) {
+      return false;
+    }
+    let current = self.expires.get(key).map(|v| *v);
+    if !cond.allows(current, at) {
+      return false;
+    }
+
+    if at <= now_ms() {
+      self.remove(key);
+    } else {
+      self.expires.insert(key.to_string(), at);
+    }
+    true
+  }
+
+  /// Returns `None` if the key does not exist and `Some(None)` if it has no expiry.
+  pub fn expire_time(&self, key: &str) -> Option<Option<i64>> {
+    if !self.exists(key) {
+      return None;
+    }
+    Some(self.expires.get(key).map(|v| *v))
+  }
+
+  pub fn persist(&self, key: &str) -> bool {
+    self.expire_if_needed(key);
+    self.expires.remove(key).is_some()
+  }
+
+  /// Lazily delete `key` if its deadline has passed, returns true if it was deleted.
+  pub(crate) fn expire_if_needed(&self, key: &str) -> bool {
+    let now = now_ms();
+    match self.expires.get(key) {
+      Some(at) if *at <= now => {}
+      _ => return false,
+    }
+    // re-check under the shard lock so a concurrent write of a fresh value survives
+    self.keyspace.remove_if(key, |_, _| self.expires.get(key).is_some_and(|at| *at <= now));
+    self.expires.remove_if(key, |_, at| *at <= now).is_some()
+  }
+
+  /// Spawn the background task that evicts expired keys nobody is reading.
+  pub fn start_active_expire(&self) {
+    let backend = self.clone();
+    tokio::spawn(async move {
+      let mut interval = tokio::time::interval(ACTIVE_EXPIRE_INTERVAL);
+      loop {
+        interval.tick().await;
+        let n = backend.active_expire_cycle();
+        if n > 0 {
+          debug!("Active expire evicted {} keys", n);
+        }
+      }
+    });
+  }
+
+  fn active_expire_cycle(&self) -> usize {
+    let now = now_ms();
+    let keys = self
+      .expires
+      .iter()
+      .filter(|v| *v.value() <= now)
+      .map(|v| v.key().clone())
+      .collect::<Vec<String>>();
+    keys.iter().filter(|key| self.expire_if_needed(key)).count()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespFrame;
+
+  #[test]
+  fn test_expire_condition() {
+    let cond = ExpireCondition::default();
+    assert!(cond.allows(None, 10));
+    assert!(cond.allows(Some(20), 10));
+
+    let cond = ExpireCondition { nx: true, ..Default::default() };
+    assert!(cond.allows(None, 10));
+    assert!(!cond.allows(Some(20), 10));
+
+    let cond = ExpireCondition { xx: true, lt: true, ..Default::default() };
+    assert!(!cond.allows(None, 10));
+    assert!(cond.allows(Some(20), 10));
+    assert!(!cond.allows(Some(5), 10));
+
+    let cond = ExpireCondition { gt: true, ..Default::default() };
+    assert!(!cond.allows(None, 10));
+    assert!(cond.allows(Some(5), 10));
+  }
+
+  #[test]
+  fn test_lazy_and_active_expire() {
+    let backend = Backend::new();
+    backend.set("a".to_string(), RespFrame::Integer(1));
+    backend.set("b".to_string(), RespFrame::Integer(2));
+    backend.expires.insert("a".to_string(), now_ms() - 1);
+    backend.expires.insert("b".to_string(), now_ms() - 1);
+
+    assert_eq!(backend.get("a"), Ok(None));
+    assert!(!backend.expires.contains_key("a"));
+
+    assert_eq!(backend.active_expire_cycle(), 1);
+    assert!(!backend.keyspace.contains_key("b"));
+  }
+
+  #[test]
+  fn test_expire_at_in_the_past_deletes_key() {
+    let backend = Backend::new();
+    backend.sadd("s", "member").unwrap();
+
+    assert!(backend.expire_at("s", now_ms() + 10_000, ExpireCondition::default()));
+    assert!(backend.expire_time("s").unwrap().is_some());
+    assert!(backend.persist("s"));
+    assert_eq!(backend.expire_time("s"), Some(None));
+
+    assert!(backend.expire_at("s", now_ms() - 1, ExpireCondition::default()));
+    assert_eq!(backend.expire_time("s"), None);
+    assert!(!backend.expire_at("s", now_ms() + 10_000, ExpireCondition::default()));
+  }
+}
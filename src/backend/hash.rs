@@ -0,0 +1,235 @@
+use super::{
+  scan::Entry,
+  set::sample,
+  string::{format_float, parse_i64, string_bytes},
+  Backend, Scannable, Value,
+};
+use crate::{BulkString, CommandError, RespFrame};
+use dashmap::DashMap;
+
+impl Backend {
+  /// Run `f` against the hash at `key`, created empty if missing unless `create` is false;
+  /// the key is removed if `f` leaves the hash empty. Returns `None` if there is no hash.
+  fn with_hash<T>(
+    &self,
+    key: &str,
+    create: bool,
+    f: impl FnOnce(&Scannable<DashMap<String, RespFrame>>) -> Result<T, CommandError>,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    let entry = match self.keyspace.entry(key.to_string()) {
+      Entry::Occupied(entry) => entry,
+      Entry::Vacant(_) if !create => return Ok(None),
+      Entry::Vacant(entry) => entry.insert_entry(Value::Hash(Scannable::default())),
+    };
+    let Value::Hash(hmap) = entry.get() else {
+      return Err(CommandError::WrongType);
+    };
+    let ret = f(hmap);
+    if hmap.is_empty() {
+      entry.remove();
+      self.expires.remove(key);
+    }
+    ret.map(Some)
+  }
+
+  fn read_hash<T>(
+    &self,
+    key: &str,
+    f: impl FnOnce(&Scannable<DashMap<String, RespFrame>>) -> T,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Hash(hmap)) => Ok(Some(f(hmap))),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
+  }
+
+  /// Set every `(field, value)` pair, returns the number of fields that were added.
+  pub fn hmset(&self, key: &str, pairs: Vec<(String, RespFrame)>) -> Result<usize, CommandError> {
+    let added = self.with_hash(key, true, |hmap| {
+      Ok(
+        pairs
+          .into_iter()
+          .filter(|(field, value)| hmap.insert(field.clone(), value.clone()).is_none())
+          .count(),
+      )
+    })?;
+    Ok(added.unwrap_or_default())
+  }
+
+  /// Set `field` only if it does not exist yet, returns whether it was set.
+  pub fn hsetnx(&self, key: &str, field: String, value: RespFrame) -> Result<bool, CommandError> {
+    let set = self.with_hash(key, true, |hmap| {
+      Ok(match hmap.entry(field) {
+        Entry::Occupied(_) => false,
+        Entry::Vacant(entry) => {
+          entry.insert(value);
+          true
+        }
+      })
+    })?;
+    Ok(set.unwrap_or_default())
+  }
+
+  /// Remove `fields`, returns the number of fields that existed.
+  pub fn hdel(&self, key: &str, fields: &[String]) -> Result<usize, CommandError> {
+    let removed = self.with_hash(key, false, |hmap| {
+      Ok(fields.iter().filter(|field| hmap.remove(field).is_some()).count())
+    })?;
+    Ok(removed.unwrap_or_default())
+  }
+
+  pub fn hexists(&self, key: &str, field: &str) -> Result<bool, CommandError> {
+    Ok(self.read_hash(key, |hmap| hmap.contains_key(field))?.unwrap_or_default())
+  }
+
+  pub fn hlen(&self, key: &str) -> Result<usize, CommandError> {
+    Ok(self.read_hash(key, |hmap| hmap.len())?.unwrap_or_default())
+  }
+
+  pub fn hkeys(&self, key: &str) -> Result<Vec<String>, CommandError> {
+    let keys = self.read_hash(key, |hmap| hmap.iter().map(|v| v.key().clone()).collect())?;
+    Ok(keys.unwrap_or_default())
+  }
+
+  pub fn hvals(&self, key: &str) -> Result<Vec<RespFrame>, CommandError> {
+    let vals = self.read_hash(key, |hmap| hmap.iter().map(|v| v.value().clone()).collect())?;
+    Ok(vals.unwrap_or_default())
+  }
+
+  /// Up to `count` random fields of the hash at `key` with their values, see [`sample`] for
+  /// negative counts.
+  pub fn hrandfield(
+    &self,
+    key: &str,
+    count: i64,
+  ) -> Result<Vec<(String, RespFrame)>, CommandError> {
+    let pairs = self.read_hash(key, |hmap| {
+      sample(hmap.iter(), hmap.len(), count, |v| (v.key().clone(), v.value().clone()))
+    })?;
+    Ok(pairs.unwrap_or_default())
+  }
+
+  /// The length of the value of `field`, 0 if missing.
+  pub fn hstrlen(&self, key: &str, field: &str) -> Result<usize, CommandError> {
+    let len = self.read_hash(key, |hmap| hmap.get(field).map(|v| string_bytes(v.value()).len()))?;
+    Ok(len.flatten().unwrap_or_default())
+  }
+
+  /// Add `delta` to the integer value of `field`, missing fields counting as 0.
+  pub fn hincrby(&self, key: &str, field: String, delta: i64) -> Result<i64, CommandError> {
+    let n = self.with_hash(key, true, |hmap| {
+      let n = match hmap.get(&field) {
+        Some(value) => parse_i64(&string_bytes(&value)).ok_or_else(|| {
+          CommandError::InvalidArgument("hash value is not an integer".to_string())
+        })?,
+        None => 0,
+      };
+      let n = n.checked_add(delta).ok_or_else(|| {
+        CommandError::InvalidArgument("increment or decrement would overflow".to_string())
+      })?;
+      hmap.insert(field, BulkString::from(n.to_string()).into());
+      Ok(n)
+    })?;
+    Ok(n.unwrap_or_default())
+  }
+
+  /// Add `delta` to the float value of `field`, missing fields counting as 0. Returns the new
+  /// value as it is stored.
+  pub fn hincrbyfloat(&self, key: &str, field: String, delta: f64) -> Result<String, CommandError> {
+    let s = self.with_hash(key, true, |hmap| {
+      let n = match hmap.get(&field) {
+        Some(value) => std::str::from_utf8(&string_bytes(&value))
+          .ok()
+          .and_then(|s| s.parse::<f64>().ok())
+          .filter(|n| n.is_finite())
+          .ok_or_else(|| CommandError::InvalidArgument("hash value is not a float".to_string()))?,
+        None => 0.0,
+      } + delta;
+      if !n.is_finite() {
+        return Err(CommandError::InvalidArgument(
+          "increment would produce NaN or Infinity".to_string(),
+        ));
+      }
+      let s = format_float(n);
+      hmap.insert(field, BulkString::from(s.clone()).into());
+      Ok(s)
+    })?;
+    Ok(s.unwrap_or_default())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, RespFrame)> {
+    pairs.iter().map(|(f, v)| (f.to_string(), BulkString::from(*v).into())).collect()
+  }
+
+  #[test]
+  fn test_hmset_hdel() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    assert_eq!(backend.hmset("h", pairs(&[("a", "1"), ("b", "2")]))?, 2);
+    assert_eq!(backend.hmset("h", pairs(&[("b", "3"), ("c", "4")]))?, 1);
+    assert_eq!(backend.hlen("h")?, 3);
+    assert_eq!(backend.hget("h", "b")?, Some(BulkString::from("3").into()));
+    assert!(backend.hexists("h", "c")?);
+    assert!(!backend.hsetnx("h", "c".to_string(), BulkString::from("5").into())?);
+    assert!(backend.hsetnx("h", "d".to_string(), BulkString::from("5").into())?);
+
+    let mut keys = backend.hkeys("h")?;
+    keys.sort();
+    assert_eq!(keys, ["a", "b", "c", "d"]);
+    assert_eq!(backend.hvals("h")?.len(), 4);
+
+    let fields = ["a", "b", "c", "x"].map(String::from);
+    assert_eq!(backend.hdel("h", &fields)?, 3);
+    assert!(backend.exists("h"));
+    assert_eq!(backend.hdel("h", &["d".to_string()])?, 1);
+    assert!(!backend.exists("h"));
+    assert_eq!(backend.hdel("h", &["d".to_string()])?, 0);
+    assert_eq!(backend.hkeys("h")?, Vec::<String>::new());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hrandfield() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.hmset("h", pairs(&[("a", "1"), ("b", "2")]))?;
+    let fields = backend.hrandfield("h", 5)?;
+    assert_eq!(fields.len(), 2);
+    assert!(fields.contains(&("a".to_string(), BulkString::from("1").into())));
+    assert_eq!(backend.hrandfield("h", -5)?.len(), 5);
+    assert!(backend.hrandfield("missing", 1)?.is_empty());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hincrby() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    assert_eq!(backend.hincrby("h", "n".to_string(), 5)?, 5);
+    assert_eq!(backend.hincrby("h", "n".to_string(), -8)?, -3);
+    assert_eq!(backend.hstrlen("h", "n")?, 2);
+    assert!(backend.hincrby("h", "n".to_string(), i64::MIN).is_err());
+
+    backend.hmset("h", pairs(&[("s", "abc"), ("f", "10.50")]))?;
+    assert!(backend.hincrby("h", "s".to_string(), 1).is_err());
+    assert_eq!(backend.hincrbyfloat("h", "f".to_string(), 0.1)?, "10.6");
+    assert_eq!(backend.hincrbyfloat("h", "g".to_string(), -5.0)?, "-5");
+    assert!(backend.hincrbyfloat("h", "s".to_string(), 1.0).is_err());
+
+    // a failed increment of a new field in a new hash leaves nothing behind
+    assert!(backend.hincrbyfloat("new", "f".to_string(), f64::INFINITY).is_err());
+    assert!(!backend.exists("new"));
+
+    backend.sadd("set", "a")?;
+    assert_eq!(backend.hincrby("set", "n".to_string(), 1), Err(CommandError::WrongType));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,601 @@
+//! HyperLogLogs are plain strings laid out the way redis lays them out, so their bytes can be
+//! moved to and from a real redis with `GET` and `SET`.
+//!
+//! A 16 bytes header, the magic "HYLL", the encoding, 3 unused bytes and the cached
+//! cardinality as 8 little endian bytes whose most significant bit flags it as stale, is
+//! followed by 16384 registers of 6 bits, either:
+//! - dense: all the registers packed, least significant bits first.
+//! - sparse: runs of registers, `00xxxxxx` for up to 64 zeros, `01xxxxxx yyyyyyyy` for up to
+//!   16384 zeros and `1vvvvvxx` for up to 4 registers set to a value of up to 32.
+
+use super::Backend;
+use crate::CommandError;
+
+const HLL_P: u32 = 14;
+const HLL_Q: u32 = 64 - HLL_P;
+const HLL_REGISTERS: usize = 1 << HLL_P;
+const HLL_BITS: usize = 6;
+const HLL_REGISTER_MAX: u8 = (1 << HLL_BITS) - 1;
+const HLL_HDR_SIZE: usize = 16;
+const HLL_DENSE_SIZE: usize = HLL_HDR_SIZE + (HLL_REGISTERS * HLL_BITS).div_ceil(8);
+const HLL_DENSE: u8 = 0;
+const HLL_SPARSE: u8 = 1;
+// 0.5 / ln(2)
+const HLL_ALPHA_INF: f64 = 0.721_347_520_444_481_7;
+
+// a sparse HyperLogLog growing past this is made dense, as redis does by default
+const HLL_SPARSE_MAX_BYTES: usize = 3000;
+const HLL_SPARSE_ZERO_MAX_LEN: usize = 64;
+const HLL_SPARSE_VAL_MAX_VALUE: u8 = 32;
+const HLL_SPARSE_VAL_MAX_LEN: usize = 4;
+
+/// One opcode of the sparse encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SparseOp {
+  Zero(usize),
+  XZero(usize),
+  Val(u8, usize),
+}
+
+impl SparseOp {
+  fn decode(bytes: &[u8]) -> Option<Self> {
+    let byte = *bytes.first()?;
+    Some(match byte & 0xc0 {
+      0x00 => SparseOp::Zero((byte & 0x3f) as usize + 1),
+      0x40 => SparseOp::XZero((((byte & 0x3f) as usize) << 8 | *bytes.get(1)? as usize) + 1),
+      _ => SparseOp::Val(((byte >> 2) & 0x1f) + 1, (byte & 0x03) as usize + 1),
+    })
+  }
+
+  /// A run of `len` zeros, in the shortest opcode that holds it.
+  fn zeros(len: usize) -> Self {
+    if len > HLL_SPARSE_ZERO_MAX_LEN {
+      SparseOp::XZero(len)
+    } else {
+      SparseOp::Zero(len)
+    }
+  }
+
+  fn encode(self, out: &mut Vec<u8>) {
+    match self {
+      SparseOp::Zero(len) => out.push((len - 1) as u8),
+      SparseOp::XZero(len) => out.extend([((len - 1) >> 8) as u8 | 0x40, (len - 1) as u8]),
+      SparseOp::Val(value, len) => out.push(0x80 | (value - 1) << 2 | (len - 1) as u8),
+    }
+  }
+
+  fn size(self) -> usize {
+    match self {
+      SparseOp::XZero(_) => 2,
+      _ => 1,
+    }
+  }
+
+  fn span(self) -> usize {
+    match self {
+      SparseOp::Zero(len) | SparseOp::XZero(len) | SparseOp::Val(_, len) => len,
+    }
+  }
+}
+
+/// The register an element lands in and the value it sets it to: the position of the first
+/// set bit in what is left of its hash.
+fn pattern_len(element: &[u8]) -> (usize, u8) {
+  let hash = murmur_hash64a(element, 0xadc83b19);
+  let index = hash as usize & (HLL_REGISTERS - 1);
+  let hash = hash >> HLL_P | 1 << HLL_Q;
+  (index, hash.trailing_zeros() as u8 + 1)
+}
+
+/// MurmurHash64A, reading words as little endian.
+fn murmur_hash64a(key: &[u8], seed: u32) -> u64 {
+  const M: u64 = 0xc6a4a7935bd1e995;
+  const R: u32 = 47;
+  let mut h = seed as u64 ^ (key.len() as u64).wrapping_mul(M);
+
+  let mut chunks = key.chunks_exact(8);
+  for chunk in &mut chunks {
+    let mut k = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+    k = k.wrapping_mul(M);
+    k ^= k >> R;
+    k = k.wrapping_mul(M);
+    h ^= k;
+    h = h.wrapping_mul(M);
+  }
+  let tail = chunks.remainder();
+  if !tail.is_empty() {
+    for (i, byte) in tail.iter().enumerate() {
+      h ^= (*byte as u64) << (8 * i);
+    }
+    h = h.wrapping_mul(M);
+  }
+
+  h ^= h >> R;
+  h = h.wrapping_mul(M);
+  h ^ h >> R
+}
+
+/// The estimated cardinality of a set of registers, with the estimator of Otmar Ertl that
+/// redis uses rather than the original one and its bias corrections.
+fn estimate(registers: &[u8]) -> u64 {
+  // a dense register holds up to 63, past the Q + 1 a hash can give, registers set by hand
+  // above that are left out of the estimate as redis does
+  let mut histogram = [0usize; HLL_REGISTER_MAX as usize + 1];
+  for &register in registers {
+    histogram[register as usize] += 1;
+  }
+
+  let m = HLL_REGISTERS as f64;
+  let mut z = m * tau((m - histogram[HLL_Q as usize + 1] as f64) / m);
+  for &n in histogram[1..=HLL_Q as usize].iter().rev() {
+    z += n as f64;
+    z *= 0.5;
+  }
+  z += m * sigma(histogram[0] as f64 / m);
+  (HLL_ALPHA_INF * m * m / z).round() as u64
+}
+
+fn sigma(mut x: f64) -> f64 {
+  if x == 1.0 {
+    return f64::INFINITY;
+  }
+  let (mut y, mut z) = (1.0, x);
+  loop {
+    x *= x;
+    let prev = z;
+    z += x * y;
+    y += y;
+    if prev == z {
+      return z;
+    }
+  }
+}
+
+fn tau(mut x: f64) -> f64 {
+  if x == 0.0 || x == 1.0 {
+    return 0.0;
+  }
+  let (mut y, mut z) = (1.0, 1.0 - x);
+  loop {
+    x = x.sqrt();
+    let prev = z;
+    y *= 0.5;
+    z -= (1.0 - x).powi(2) * y;
+    if prev == z {
+      return z / 3.0;
+    }
+  }
+}
+
+/// A HyperLogLog in its string form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog(Vec<u8>);
+
+impl Default for HyperLogLog {
+  /// An empty sparse HyperLogLog, with a cached cardinality of 0.
+  fn default() -> Self {
+    let mut bytes = b"HYLL".to_vec();
+    bytes.extend([HLL_SPARSE, 0, 0, 0]);
+    bytes.extend([0; 8]);
+    SparseOp::XZero(HLL_REGISTERS).encode(&mut bytes);
+    HyperLogLog(bytes)
+  }
+}
+
+impl TryFrom<Vec<u8>> for HyperLogLog {
+  type Error = CommandError;
+  fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+    let valid = bytes.len() >= HLL_HDR_SIZE
+      && bytes.starts_with(b"HYLL")
+      && match bytes[4] {
+        HLL_DENSE => bytes.len() == HLL_DENSE_SIZE,
+        HLL_SPARSE => true,
+        _ => false,
+      };
+    match valid {
+      true => Ok(HyperLogLog(bytes)),
+      false => Err(CommandError::InvalidHll),
+    }
+  }
+}
+
+impl HyperLogLog {
+  pub fn into_bytes(self) -> Vec<u8> {
+    self.0
+  }
+
+  fn is_sparse(&self) -> bool {
+    self.0[4] == HLL_SPARSE
+  }
+
+  fn cached_count(&self) -> Option<u64> {
+    let card = u64::from_le_bytes(self.0[8..16].try_into().unwrap_or_default());
+    (card >> 63 == 0).then_some(card)
+  }
+
+  fn invalidate_cache(&mut self) {
+    self.0[15] |= 0x80;
+  }
+
+  /// Add an element, returns whether a register changed.
+  pub fn add(&mut self, element: &[u8]) -> Result<bool, CommandError> {
+    let (index, count) = pattern_len(element);
+    self.set(index, count)
+  }
+
+  /// Raise the register at `index` to `count`, returns whether it was lower.
+  fn set(&mut self, index: usize, count: u8) -> Result<bool, CommandError> {
+    let changed = match self.is_sparse() {
+      true => self.sparse_set(index, count)?,
+      false => dense_set(&mut self.0[HLL_HDR_SIZE..], index, count),
+    };
+    if changed {
+      self.invalidate_cache();
+    }
+    Ok(changed)
+  }
+
+  /// The cardinality, from the cache if it is up to date, caching it otherwise.
+  pub fn count(&mut self) -> Result<u64, CommandError> {
+    if let Some(card) = self.cached_count() {
+      return Ok(card);
+    }
+    let mut registers = vec![0; HLL_REGISTERS];
+    self.merge_into(&mut registers)?;
+    let card = estimate(&registers);
+    self.0[8..16].copy_from_slice(&card.to_le_bytes());
+    Ok(card)
+  }
+
+  /// Raise every register in `max` to the one of this HyperLogLog where it is greater.
+  pub fn merge_into(&self, max: &mut [u8]) -> Result<(), CommandError> {
+    let registers = &self.0[HLL_HDR_SIZE..];
+    if !self.is_sparse() {
+      for (i, max) in max.iter_mut().enumerate() {
+        *max = (*max).max(dense_get(registers, i));
+      }
+      return Ok(());
+    }
+
+    let (mut index, mut pos) = (0, 0);
+    while let Some(op) = SparseOp::decode(&registers[pos..]) {
+      if index + op.span() > HLL_REGISTERS {
+        return Err(CommandError::CorruptedHll);
+      }
+      if let SparseOp::Val(value, len) = op {
+        for max in &mut max[index..index + len] {
+          *max = (*max).max(value);
+        }
+      }
+      index += op.span();
+      pos += op.size();
+    }
+    match index == HLL_REGISTERS && pos == registers.len() {
+      true => Ok(()),
+      false => Err(CommandError::CorruptedHll),
+    }
+  }
+
+  /// Set the registers to `max` where it is greater, made dense if any source was.
+  pub fn merge_from(&mut self, max: &[u8], dense: bool) -> Result<(), CommandError> {
+    if dense && self.is_sparse() {
+      self.make_dense()?;
+    }
+    for (index, &count) in max.iter().enumerate() {
+      if count > 0 {
+        self.set(index, count)?;
+      }
+    }
+    self.invalidate_cache();
+    Ok(())
+  }
+
+  fn make_dense(&mut self) -> Result<(), CommandError> {
+    let mut registers = vec![0; HLL_REGISTERS];
+    self.merge_into(&mut registers)?;
+    let mut bytes = self.0[..HLL_HDR_SIZE].to_vec();
+    bytes[4] = HLL_DENSE;
+    bytes.resize(HLL_DENSE_SIZE, 0);
+    for (index, count) in registers.into_iter().enumerate() {
+      dense_set(&mut bytes[HLL_HDR_SIZE..], index, count);
+    }
+    self.0 = bytes;
+    Ok(())
+  }
+
+  /// Set a register of the sparse encoding the way redis does, splitting the opcode covering
+  /// it and merging equal neighbours, so the bytes come out the same as they would there.
+  fn sparse_set(&mut self, index: usize, count: u8) -> Result<bool, CommandError> {
+    if count > HLL_SPARSE_VAL_MAX_VALUE {
+      return self.promote(index, count);
+    }
+
+    // find the opcode covering the register, `first` being the first register it covers
+    let sparse = &self.0[HLL_HDR_SIZE..];
+    let (mut first, mut pos, mut prev) = (0, 0, None);
+    let op = loop {
+      let op = SparseOp::decode(&sparse[pos..]).ok_or(CommandError::CorruptedHll)?;
+      if index < first + op.span() {
+        break op;
+      }
+      prev = Some(pos);
+      pos += op.size();
+      first += op.span();
+    };
+    let last = first + op.span() - 1;
+
+    let mut seq = vec![];
+    match op {
+      SparseOp::Val(value, _) if value >= count => return Ok(false),
+      SparseOp::Val(_, 1) | SparseOp::Zero(1) => SparseOp::Val(count, 1).encode(&mut seq),
+      SparseOp::Val(value, _) => {
+        if index != first {
+          SparseOp::Val(value, index - first).encode(&mut seq);
+        }
+        SparseOp::Val(count, 1).encode(&mut seq);
+        if index != last {
+          SparseOp::Val(value, last - index).encode(&mut seq);
+        }
+      }
+      SparseOp::Zero(_) | SparseOp::XZero(_) => {
+        if index != first {
+          SparseOp::zeros(index - first).encode(&mut seq);
+        }
+        SparseOp::Val(count, 1).encode(&mut seq);
+        if index != last {
+          SparseOp::zeros(last - index).encode(&mut seq);
+        }
+      }
+    }
+    if seq.len() > op.size() && self.0.len() + seq.len() - op.size() > HLL_SPARSE_MAX_BYTES {
+      return self.promote(index, count);
+    }
+    let start = HLL_HDR_SIZE + pos;
+    self.0.splice(start..start + op.size(), seq);
+
+    // merge runs of the same value, looking at up to 5 opcodes from the one before
+    let sparse = &mut self.0[HLL_HDR_SIZE..];
+    let (mut pos, mut end, mut scan) = (prev.unwrap_or_default(), sparse.len(), 5);
+    while pos < end && scan > 0 {
+      scan -= 1;
+      let (op, next) =
+        (SparseOp::decode(&sparse[pos..end]), SparseOp::decode(&sparse[pos + 1..end]));
+      if let (Some(SparseOp::Val(v1, len1)), Some(SparseOp::Val(v2, len2))) = (op, next) {
+        if v1 == v2 && len1 + len2 <= HLL_SPARSE_VAL_MAX_LEN {
+          let mut merged = vec![];
+          SparseOp::Val(v1, len1 + len2).encode(&mut merged);
+          sparse[pos + 1] = merged[0];
+          sparse.copy_within(pos + 1..end, pos);
+          end -= 1;
+          continue;
+        }
+      }
+      pos += op.map_or(1, SparseOp::size);
+    }
+    self.0.truncate(HLL_HDR_SIZE + end);
+    Ok(true)
+  }
+
+  /// Turn into the dense encoding to set a register the sparse one cannot hold.
+  fn promote(&mut self, index: usize, count: u8) -> Result<bool, CommandError> {
+    self.make_dense()?;
+    Ok(dense_set(&mut self.0[HLL_HDR_SIZE..], index, count))
+  }
+}
+
+fn dense_get(registers: &[u8], index: usize) -> u8 {
+  let (byte, bit) = (index * HLL_BITS / 8, index * HLL_BITS % 8);
+  let low = registers[byte] >> bit;
+  let high = registers.get(byte + 1).map_or(0, |b| b.checked_shl(8 - bit as u32).unwrap_or(0));
+  (low | high) & HLL_REGISTER_MAX
+}
+
+/// Raise the register at `index` to `count`, returns whether it was lower.
+fn dense_set(registers: &mut [u8], index: usize, count: u8) -> bool {
+  if dense_get(registers, index) >= count {
+    return false;
+  }
+  let (byte, bit) = (index * HLL_BITS / 8, index * HLL_BITS % 8);
+  registers[byte] &= !(HLL_REGISTER_MAX << bit);
+  registers[byte] |= count << bit;
+  if let Some(next) = registers.get_mut(byte + 1) {
+    let shift = 8 - bit as u32;
+    *next &= !HLL_REGISTER_MAX.checked_shr(shift).unwrap_or(0);
+    *next |= count.checked_shr(shift).unwrap_or(0);
+  }
+  true
+}
+
+impl Backend {
+  /// The HyperLogLog at `key`, `None` if missing.
+  fn read_hll(&self, key: &str) -> Result<Option<HyperLogLog>, CommandError> {
+    self.read_string(key)?.map(HyperLogLog::try_from).transpose()
+  }
+
+  /// Add elements to the HyperLogLog at `key`, creating it if missing. Returns whether the
+  /// estimated cardinality may have changed: a register changed or the key was created.
+  pub fn pfadd(&self, key: &str, elements: &[Vec<u8>]) -> Result<bool, CommandError> {
+    self.update_string(key, |old| {
+      let (mut hll, mut changed) = match old {
+        Some(bytes) => (HyperLogLog::try_from(bytes.to_vec())?, false),
+        None => (HyperLogLog::default(), true),
+      };
+      for element in elements {
+        changed |= hll.add(element)?;
+      }
+      Ok((hll.into_bytes(), changed))
+    })
+  }
+
+  /// The estimated cardinality of the union of the HyperLogLogs at `keys`. For a single key
+  /// it is cached in the HyperLogLog, missing keys count as empty.
+  pub fn pfcount(&self, keys: &[String]) -> Result<u64, CommandError> {
+    if let [key] = keys {
+      if self.read_hll(key)?.is_none() {
+        return Ok(0);
+      }
+      return self.update_string(key, |old| {
+        let mut hll = HyperLogLog::try_from(old.unwrap_or_default().to_vec())?;
+        let card = hll.count()?;
+        Ok((hll.into_bytes(), card))
+      });
+    }
+
+    let mut max = vec![0; HLL_REGISTERS];
+    for key in keys {
+      if let Some(hll) = self.read_hll(key)? {
+        hll.merge_into(&mut max)?;
+      }
+    }
+    Ok(estimate(&max))
+  }
+
+  /// Merge the HyperLogLogs at `keys` into the one at `dst`, creating it if missing. It is
+  /// dense if any of them is.
+  pub fn pfmerge(&self, dst: &str, keys: &[String]) -> Result<(), CommandError> {
+    let (mut max, mut dense) = (vec![0; HLL_REGISTERS], false);
+    for key in std::iter::once(dst).chain(keys.iter().map(String::as_str)) {
+      if let Some(hll) = self.read_hll(key)? {
+        dense |= !hll.is_sparse();
+        hll.merge_into(&mut max)?;
+      }
+    }
+    self.update_string(dst, |old| {
+      let mut hll = match old {
+        Some(bytes) => HyperLogLog::try_from(bytes.to_vec())?,
+        None => HyperLogLog::default(),
+      };
+      hll.merge_from(&max, dense)?;
+      Ok((hll.into_bytes(), ()))
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::BulkString;
+
+  #[test]
+  fn test_sparse_encoding() -> Result<(), CommandError> {
+    let mut hll = HyperLogLog::default();
+    assert_eq!(&hll.0[HLL_HDR_SIZE..], [0x7f, 0xff]);
+    assert_eq!(hll.cached_count(), Some(0));
+
+    assert!(hll.set(100, 3)?);
+    assert_eq!(&hll.0[HLL_HDR_SIZE..], [0x40, 0x63, 0x88, 0x7f, 0x9a]);
+    assert_eq!(hll.cached_count(), None);
+    assert!(!hll.set(100, 2)?);
+    // the neighbour is merged into the run of 3s
+    assert!(hll.set(101, 3)?);
+    assert_eq!(&hll.0[HLL_HDR_SIZE..], [0x40, 0x63, 0x89, 0x7f, 0x99]);
+    assert_eq!(hll.count()?, 2);
+    assert_eq!(hll.cached_count(), Some(2));
+
+    // a value past 32 only fits the dense encoding
+    assert!(hll.set(0, 33)?);
+    assert!(!hll.is_sparse());
+    assert_eq!(hll.0.len(), HLL_DENSE_SIZE);
+    let mut registers = vec![0; HLL_REGISTERS];
+    hll.merge_into(&mut registers)?;
+    assert_eq!(&registers[..3], [33, 0, 0]);
+    assert_eq!(&registers[100..103], [3, 3, 0]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_sparse_matches_registers() -> Result<(), CommandError> {
+    let (mut hll, mut expected) = (HyperLogLog::default(), vec![0; HLL_REGISTERS]);
+    let mut sparse = true;
+    for i in 0..5000 {
+      let element = format!("{i}");
+      let (index, count) = pattern_len(element.as_bytes());
+      expected[index] = expected[index].max(count);
+      hll.add(element.as_bytes())?;
+      sparse &= hll.is_sparse();
+      if i % 10 != 0 {
+        continue;
+      }
+      let mut registers = vec![0; HLL_REGISTERS];
+      hll.merge_into(&mut registers)?;
+      assert_eq!(registers, expected, "after {i}");
+    }
+    // it outgrew the sparse encoding on the way
+    assert!(!sparse && !hll.is_sparse());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_dense_registers() {
+    let mut registers = vec![0; HLL_DENSE_SIZE - HLL_HDR_SIZE];
+    for index in [0, 1, 2, 3, HLL_REGISTERS - 1] {
+      assert!(dense_set(&mut registers, index, 63 - index as u8 % 60));
+    }
+    assert!(!dense_set(&mut registers, 1, 1));
+    for index in [0, 1, 2, 3, HLL_REGISTERS - 1] {
+      assert_eq!(dense_get(&registers, index), 63 - index as u8 % 60);
+    }
+    assert_eq!(dense_get(&registers, 4), 0);
+  }
+
+  #[test]
+  fn test_murmur_hash64a() {
+    assert_eq!(murmur_hash64a(b"", 0), 0);
+    assert_eq!(murmur_hash64a(b"hello", 0xadc83b19), 0x0f656f01eecfe400);
+    assert_eq!(murmur_hash64a(b"redis hyperloglog", 0xadc83b19), 0x73712e8bb3a94a27);
+    assert_eq!(pattern_len(b"hello"), (9216, 1));
+  }
+
+  #[test]
+  fn test_pfadd_pfcount() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    let elements = (0..100_000).map(|i| format!("element:{i}").into_bytes()).collect::<Vec<_>>();
+    assert!(backend.pfadd("hll", &elements)?);
+    assert!(!backend.pfadd("hll", &elements[..10])?);
+    let card = backend.pfcount(&["hll".to_string()])?;
+    // well within 3 standard errors of 0.81%
+    assert!((card as f64 - 100_000.0).abs() < 100_000.0 * 0.0243, "{card}");
+
+    assert!(backend.pfadd("empty", &[])?);
+    assert_eq!(backend.pfcount(&["empty".to_string(), "missing".to_string()])?, 0);
+    backend.set("s".to_string(), BulkString::from("foo").into());
+    assert_eq!(backend.pfadd("s", &[]), Err(CommandError::InvalidHll));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_pfcount_crafted_dense() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    // every register at 63 and a stale cache
+    let mut bytes = b"HYLL".to_vec();
+    bytes.extend([HLL_DENSE, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80]);
+    bytes.resize(HLL_DENSE_SIZE, 0xff);
+    backend.set("hll".to_string(), BulkString::new(bytes).into());
+
+    backend.pfcount(&["hll".to_string()])?;
+    backend.pfcount(&["hll".to_string(), "missing".to_string()])?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_pfmerge() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    let elements = (0..1000).map(|i| format!("{i}").into_bytes()).collect::<Vec<_>>();
+    backend.pfadd("a", &elements[..600])?;
+    backend.pfadd("b", &elements[400..])?;
+    let keys = ["a".to_string(), "b".to_string()];
+    let union = backend.pfcount(&keys)?;
+    assert!((980..=1020).contains(&union), "{union}");
+
+    backend.pfmerge("dst", &keys)?;
+    assert_eq!(backend.pfcount(&["dst".to_string()])?, union);
+    backend.pfmerge("dst", &[])?;
+    assert_eq!(backend.pfcount(&["dst".to_string()])?, union);
+
+    Ok(())
+  }
+}
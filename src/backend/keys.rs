@@ -0,0 +1,147 @@
+use super::{Backend, Value};
+use crate::CommandError;
+use tracing::debug;
+
+// values with more elements than this are freed off the connection task by UNLINK
+const LAZYFREE_THRESHOLD: usize = 64;
+
+impl Backend {
+  /// Remove `key` and hand big values to a blocking task to be freed.
+  pub fn unlink(&self, key: &str) -> bool {
+    self.expire_if_needed(key);
+    self.expires.remove(key);
+    let Some((_, value)) = self.keyspace.remove(key) else {
+      return false;
+    };
+    match tokio::runtime::Handle::try_current() {
+      Ok(handle) if value.free_effort() > LAZYFREE_THRESHOLD => {
+        debug!("Freeing {} in the background", key);
+        handle.spawn_blocking(move || drop(value));
+      }
+      _ => drop(value),
+    }
+    true
+  }
+
+  /// Move `src` to `dst` along with its ttl, overwriting `dst` unless `nx` is set.
+  /// Returns false if `nx` is set and `dst` already exists.
+  pub fn rename(&self, src: &str, dst: &str, nx: bool) -> Result<bool, CommandError> {
+    if !self.exists(src) {
+      return Err(CommandError::NoSuchKey);
+    }
+    if src == dst {
+      return Ok(!nx);
+    }
+    if nx && self.exists(dst) {
+      return Ok(false);
+    }
+
+    let Some((_, value)) = self.keyspace.remove(src) else {
+      return Err(CommandError::NoSuchKey);
+    };
+    let at = self.expires.remove(src).map(|(_, at)| at);
+    self.put(dst.to_string(), value, at);
+    Ok(true)
+  }
+
+  /// Copy the value and ttl of `src` to `dst`. Returns false if `src` does not exist, or
+  /// if `dst` does and `replace` is not set.
+  pub fn copy(&self, src: &str, dst: &str, replace: bool) -> Result<bool, CommandError> {
+    if src == dst {
+      return Err(CommandError::InvalidArgument(
+        "source and destination objects are the same".to_string(),
+      ));
+    }
+    self.expire_if_needed(src);
+    let Some(value) = self.keyspace.get(src).map(|v| v.clone()) else {
+      return Ok(false);
+    };
+    if !replace && self.exists(dst) {
+      return Ok(false);
+    }
+
+    let at = self.expires.get(src).map(|v| *v);
+    self.put(dst.to_string(), value, at);
+    Ok(true)
+  }
+
+  /// Store `value` at `key`, replacing any previous value and ttl.
+  fn put(&self, key: String, value: Value, expire_at: Option<i64>) {
+    // blocked clients wait on lists, sorted sets and streams
+    let is_ready = matches!(value, Value::List(_) | Value::ZSet(_) | Value::Stream(_));
+    let entry = self.keyspace.entry(key.clone());
+    match expire_at {
+      Some(at) => {
+        self.expires.insert(entry.key().clone(), at);
+      }
+      None => {
+        self.expires.remove(entry.key());
+      }
+    }
+    entry.insert(value);
+    if is_ready {
+      self.signal_ready(&key);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{now_ms, ExpireCondition, RespFrame};
+
+  #[test]
+  fn test_rename() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    assert_eq!(backend.rename("a", "b", false), Err(CommandError::NoSuchKey));
+
+    backend.set("a".to_string(), RespFrame::Integer(1));
+    backend.expire_at("a", now_ms() + 10_000, ExpireCondition::default());
+    backend.sadd("b", "member")?;
+
+    assert!(backend.rename("a", "b", false)?);
+    assert!(!backend.exists("a"));
+    assert_eq!(backend.get("b")?, Some(RespFrame::Integer(1)));
+    assert!(backend.expire_time("b").unwrap().is_some());
+
+    backend.set("a".to_string(), RespFrame::Integer(2));
+    assert!(!backend.rename("a", "b", true)?);
+    assert!(!backend.rename("a", "a", true)?);
+    assert!(backend.rename("a", "a", false)?);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_copy() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    assert!(!backend.copy("a", "b", false)?);
+
+    backend.hset("a".to_string(), "field".to_string(), RespFrame::Integer(1))?;
+    assert!(backend.copy("a", "b", false)?);
+    backend.hset("a".to_string(), "field".to_string(), RespFrame::Integer(2))?;
+
+    // the copy does not share storage with the source
+    assert_eq!(backend.hget("b", "field")?, Some(RespFrame::Integer(1)));
+    assert!(!backend.copy("a", "b", false)?);
+    assert!(backend.copy("a", "b", true)?);
+    assert_eq!(backend.hget("b", "field")?, Some(RespFrame::Integer(2)));
+    assert!(backend.copy("a", "a", true).is_err());
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_unlink_big_value() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    for i in 0..LAZYFREE_THRESHOLD * 2 {
+      backend.sadd("big", i.to_string())?;
+    }
+
+    assert!(backend.unlink("big"));
+    assert!(!backend.exists("big"));
+    assert!(!backend.unlink("big"));
+
+    Ok(())
+  }
+}
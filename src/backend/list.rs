@@ -0,0 +1,354 @@
+use super::scan::Entry;
+use super::{Backend, Value};
+use crate::{CommandError, RespFrame};
+use std::collections::VecDeque;
+
+/// Which end of a list an element is pushed to or popped from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListEnd {
+  Left,
+  Right,
+}
+
+/// Clamp a redis `start stop` range, negative indexes counting from the tail, to a half-open
+/// range of positions in a sequence of `len` elements. Returns `None` if the range is empty.
+pub(crate) fn normalize_range(start: i64, stop: i64, len: usize) -> Option<(usize, usize)> {
+  let len = len as i64;
+  let start = if start < 0 { (start + len).max(0) } else { start };
+  let stop = if stop < 0 { stop + len } else { stop.min(len - 1) };
+  if start > stop || start >= len {
+    return None;
+  }
+  Some((start as usize, stop as usize + 1))
+}
+
+fn normalize_index(index: i64, len: usize) -> Option<usize> {
+  let index = if index < 0 { index + len as i64 } else { index };
+  (0..len as i64).contains(&index).then_some(index as usize)
+}
+
+/// The `RANK`, `COUNT` and `MAXLEN` options of `LPOS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LPosOptions {
+  pub rank: i64,
+  pub count: usize,
+  pub max_len: usize,
+}
+
+impl Default for LPosOptions {
+  fn default() -> Self {
+    Self { rank: 1, count: 1, max_len: 0 }
+  }
+}
+
+impl Backend {
+  /// Run `f` against the list at `key`, removing the key if `f` leaves the list empty.
+  /// Returns `None` if the key does not exist.
+  fn with_list<T>(
+    &self,
+    key: &str,
+    f: impl FnOnce(&mut VecDeque<RespFrame>) -> T,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    let Entry::Occupied(mut entry) = self.keyspace.entry(key.to_string()) else {
+      return Ok(None);
+    };
+    let Value::List(list) = entry.get_mut() else {
+      return Err(CommandError::WrongType);
+    };
+    let ret = f(list);
+    if list.is_empty() {
+      entry.remove();
+      self.expires.remove(key);
+    }
+    Ok(Some(ret))
+  }
+
+  fn read_list<T>(
+    &self,
+    key: &str,
+    f: impl FnOnce(&VecDeque<RespFrame>) -> T,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::List(list)) => Ok(Some(f(list))),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
+  }
+
+  /// Push `values` one by one to `end` of the list, creating it unless `only_if_exists` is set.
+  /// Returns the length of the list afterwards, before any blocked client is served from it.
+  pub fn push(
+    &self,
+    key: String,
+    values: Vec<RespFrame>,
+    end: ListEnd,
+    only_if_exists: bool,
+  ) -> Result<usize, CommandError> {
+    self.expire_if_needed(&key);
+    let mut entry = match self.keyspace.entry(key.clone()) {
+      Entry::Occupied(entry) => entry.into_ref(),
+      Entry::Vacant(_) if only_if_exists => return Ok(0),
+      Entry::Vacant(entry) => entry.insert(Value::List(VecDeque::new())),
+    };
+    let Value::List(list) = entry.value_mut() else {
+      return Err(CommandError::WrongType);
+    };
+    for value in values {
+      match end {
+        ListEnd::Left => list.push_front(value),
+        ListEnd::Right => list.push_back(value),
+      }
+    }
+    let len = list.len();
+    drop(entry);
+    self.signal_ready(&key);
+    Ok(len)
+  }
+
+  /// Pop up to `count` elements from `end`, returns `None` if the key does not exist.
+  pub fn pop(
+    &self,
+    key: &str,
+    end: ListEnd,
+    count: usize,
+  ) -> Result<Option<Vec<RespFrame>>, CommandError> {
+    self.with_list(key, |list| {
+      let count = count.min(list.len());
+      match end {
+        ListEnd::Left => list.drain(..count).collect(),
+        ListEnd::Right => list.drain(list.len() - count..).rev().collect(),
+      }
+    })
+  }
+
+  pub fn llen(&self, key: &str) -> Result<usize, CommandError> {
+    Ok(self.read_list(key, |list| list.len())?.unwrap_or(0))
+  }
+
+  pub fn lrange(&self, key: &str, start: i64, stop: i64) -> Result<Vec<RespFrame>, CommandError> {
+    let ret = self.read_list(key, |list| match normalize_range(start, stop, list.len()) {
+      Some((start, end)) => list.range(start..end).cloned().collect(),
+      None => vec![],
+    })?;
+    Ok(ret.unwrap_or_default())
+  }
+
+  pub fn lindex(&self, key: &str, index: i64) -> Result<Option<RespFrame>, CommandError> {
+    let ret =
+      self.read_list(key, |list| normalize_index(index, list.len()).map(|i| list[i].clone()));
+    Ok(ret?.flatten())
+  }
+
+  pub fn lset(&self, key: &str, index: i64, value: RespFrame) -> Result<(), CommandError> {
+    let ret = self.with_list(key, |list| match normalize_index(index, list.len()) {
+      Some(i) => {
+        list[i] = value;
+        Ok(())
+      }
+      None => Err(CommandError::IndexOutOfRange),
+    })?;
+    ret.unwrap_or(Err(CommandError::NoSuchKey))
+  }
+
+  /// Remove `count` occurrences of `value` from the head, or from the tail if `count` is
+  /// negative, or all of them if it is 0. Returns the number of removed elements.
+  pub fn lrem(&self, key: &str, count: i64, value: &RespFrame) -> Result<usize, CommandError> {
+    let ret = self.with_list(key, |list| {
+      let limit = if count == 0 { usize::MAX } else { count.unsigned_abs() as usize };
+      let mut positions = list.iter().enumerate().filter(|(_, v)| *v == value).map(|(i, _)| i);
+      let mut positions: Vec<usize> = if count < 0 {
+        positions.rev().take(limit).collect()
+      } else {
+        positions.by_ref().take(limit).collect()
+      };
+      positions.sort_unstable();
+      for i in positions.iter().rev() {
+        list.remove(*i);
+      }
+      positions.len()
+    })?;
+    Ok(ret.unwrap_or(0))
+  }
+
+  pub fn ltrim(&self, key: &str, start: i64, stop: i64) -> Result<(), CommandError> {
+    self.with_list(key, |list| match normalize_range(start, stop, list.len()) {
+      Some((start, end)) => {
+        list.truncate(end);
+        list.drain(..start);
+      }
+      None => list.clear(),
+    })?;
+    Ok(())
+  }
+
+  /// Insert `value` next to the first occurrence of `pivot`. Returns the new length of the
+  /// list, -1 if `pivot` was not found or 0 if the key does not exist.
+  pub fn linsert(
+    &self,
+    key: &str,
+    before: bool,
+    pivot: &RespFrame,
+    value: RespFrame,
+  ) -> Result<i64, CommandError> {
+    let ret = self.with_list(key, |list| match list.iter().position(|v| v == pivot) {
+      Some(i) => {
+        list.insert(if before { i } else { i + 1 }, value);
+        list.len() as i64
+      }
+      None => -1,
+    })?;
+    Ok(ret.unwrap_or(0))
+  }
+
+  /// Positions of the elements equal to `element`, see `LPOS` for the meaning of the options.
+  pub fn lpos(
+    &self,
+    key: &str,
+    element: &RespFrame,
+    opts: LPosOptions,
+  ) -> Result<Vec<usize>, CommandError> {
+    let ret = self.read_list(key, |list| {
+      let max_len = if opts.max_len == 0 { list.len() } else { opts.max_len.min(list.len()) };
+      let count = if opts.count == 0 { usize::MAX } else { opts.count };
+      let skip = opts.rank.unsigned_abs() as usize - 1;
+      let matches = |i: &usize| list[*i] == *element;
+      if opts.rank > 0 {
+        (0..max_len).filter(matches).skip(skip).take(count).collect()
+      } else {
+        (list.len() - max_len..list.len()).rev().filter(matches).skip(skip).take(count).collect()
+      }
+    })?;
+    Ok(ret.unwrap_or_default())
+  }
+
+  /// Atomically pop an element from `from` of `src` and push it to `to` of `dst`.
+  pub fn lmove(
+    &self,
+    src: &str,
+    dst: &str,
+    from: ListEnd,
+    to: ListEnd,
+  ) -> Result<Option<RespFrame>, CommandError> {
+    if src == dst {
+      let ret = self.with_list(src, |list| {
+        let value = match from {
+          ListEnd::Left => list.pop_front(),
+          ListEnd::Right => list.pop_back(),
+        }?;
+        match to {
+          ListEnd::Left => list.push_front(value.clone()),
+          ListEnd::Right => list.push_back(value.clone()),
+        }
+        Some(value)
+      })?;
+      return Ok(ret.flatten());
+    }
+
+    // fail before popping anything if the destination cannot take the element
+    self.read_list(dst, |_| ())?;
+    let Some(mut values) = self.pop(src, from, 1)? else {
+      return Ok(None);
+    };
+    let Some(value) = values.pop() else {
+      return Ok(None);
+    };
+    self.push(dst.to_string(), vec![value.clone()], to, false)?;
+    Ok(Some(value))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn frames(values: &[i64]) -> Vec<RespFrame> {
+    values.iter().map(|v| RespFrame::Integer(*v)).collect()
+  }
+
+  #[test]
+  fn test_normalize_range() {
+    assert_eq!(normalize_range(0, -1, 5), Some((0, 5)));
+    assert_eq!(normalize_range(-100, 100, 5), Some((0, 5)));
+    assert_eq!(normalize_range(-2, -1, 5), Some((3, 5)));
+    assert_eq!(normalize_range(3, 1, 5), None);
+    assert_eq!(normalize_range(5, 10, 5), None);
+    assert_eq!(normalize_range(0, -1, 0), None);
+  }
+
+  #[test]
+  fn test_push_pop() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    assert_eq!(backend.push("list".to_string(), frames(&[1]), ListEnd::Left, true)?, 0);
+    assert_eq!(backend.push("list".to_string(), frames(&[1, 2]), ListEnd::Left, false)?, 2);
+    assert_eq!(backend.push("list".to_string(), frames(&[3]), ListEnd::Right, false)?, 3);
+    assert_eq!(backend.lrange("list", 0, -1)?, frames(&[2, 1, 3]));
+
+    assert_eq!(backend.pop("list", ListEnd::Right, 2)?, Some(frames(&[3, 1])));
+    assert_eq!(backend.pop("list", ListEnd::Left, 5)?, Some(frames(&[2])));
+    // the last pop removed the key
+    assert_eq!(backend.type_of("list"), None);
+    assert_eq!(backend.pop("list", ListEnd::Left, 1)?, None);
+
+    backend.set("str".to_string(), RespFrame::Integer(1));
+    assert_eq!(backend.pop("str", ListEnd::Left, 1), Err(CommandError::WrongType));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lrem_and_ltrim() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.push("list".to_string(), frames(&[1, 2, 1, 3, 1]), ListEnd::Right, false)?;
+
+    assert_eq!(backend.lrem("list", -2, &RespFrame::Integer(1))?, 2);
+    assert_eq!(backend.lrange("list", 0, -1)?, frames(&[1, 2, 3]));
+
+    backend.ltrim("list", 1, -1)?;
+    assert_eq!(backend.lrange("list", 0, -1)?, frames(&[2, 3]));
+    backend.ltrim("list", 5, 10)?;
+    assert!(!backend.exists("list"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lpos() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.push("list".to_string(), frames(&[0, 1, 2, 1, 1]), ListEnd::Right, false)?;
+    let one = RespFrame::Integer(1);
+
+    assert_eq!(backend.lpos("list", &one, LPosOptions::default())?, vec![1]);
+    let opts = LPosOptions { rank: 2, count: 0, max_len: 0 };
+    assert_eq!(backend.lpos("list", &one, opts)?, vec![3, 4]);
+    let opts = LPosOptions { rank: -1, count: 2, max_len: 0 };
+    assert_eq!(backend.lpos("list", &one, opts)?, vec![4, 3]);
+    let opts = LPosOptions { rank: 1, count: 0, max_len: 2 };
+    assert_eq!(backend.lpos("list", &one, opts)?, vec![1]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lmove() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.push("src".to_string(), frames(&[1, 2, 3]), ListEnd::Right, false)?;
+
+    let ret = backend.lmove("src", "dst", ListEnd::Left, ListEnd::Right)?;
+    assert_eq!(ret, Some(RespFrame::Integer(1)));
+    let ret = backend.lmove("src", "src", ListEnd::Left, ListEnd::Right)?;
+    assert_eq!(ret, Some(RespFrame::Integer(2)));
+    assert_eq!(backend.lrange("src", 0, -1)?, frames(&[3, 2]));
+    assert_eq!(backend.lrange("dst", 0, -1)?, frames(&[1]));
+
+    backend.set("str".to_string(), RespFrame::Integer(1));
+    assert_eq!(
+      backend.lmove("src", "str", ListEnd::Left, ListEnd::Left),
+      Err(CommandError::WrongType)
+    );
+    assert_eq!(backend.llen("src")?, 2);
+
+    Ok(())
+  }
+}
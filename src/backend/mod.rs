@@ -1,15 +1,80 @@
-use crate::RespFrame;
+mod bitmap;
+mod blocking;
+mod expire;
+mod hash;
+mod hyperloglog;
+mod keys;
+mod list;
+mod scan;
+mod set;
+mod skiplist;
+mod stream;
+mod stream_group;
+mod string;
+mod value;
+mod zset;
+
+pub use self::{
+  bitmap::{BitFieldOp, BitFieldType, BitOverflow, BitUnit, BitwiseOp, MAX_BIT_OFFSET},
+  expire::{now_ms, ExpireCondition},
+  hyperloglog::HyperLogLog,
+  list::{LPosOptions, ListEnd},
+  scan::Scannable,
+  set::SetOp,
+  stream::{Stream, StreamId, StreamIdSpec, StreamTrim, TrimStrategy},
+  stream_group::{
+    ClaimOptions, ConsumerGroup, ConsumerInfo, GroupInfo, GroupRead, PendingEntry, PendingSummary,
+    StreamInfo,
+  },
+  string::LcsMatch,
+  value::Value,
+  zset::{Aggregate, SortedSet, ZAddOptions, ZRangeBy, ZSetOp},
+};
+use crate::{CommandError, RespFrame};
 use dashmap::{DashMap, DashSet};
-use std::{ops::Deref, sync::Arc};
+use scan::Entry;
+use std::{
+  collections::{HashMap, VecDeque},
+  ops::Deref,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex, RwLock,
+  },
+};
 
 #[derive(Debug, Clone)]
 pub struct Backend(Arc<BackendInner>);
 
 #[derive(Debug)]
 pub struct BackendInner {
-  pub(crate) map: DashMap<String, RespFrame>,
-  pub(crate) hmap: DashMap<String, DashMap<String, RespFrame>>,
-  pub(crate) set: DashMap<String, DashSet<String>>,
+  pub(crate) keyspace: Scannable<DashMap<String, Value>>,
+  // key -> deadline in unix milliseconds, for every key type
+  pub(crate) expires: DashMap<String, i64>,
+  // key -> clients blocked on it, in the order they arrived
+  pub(crate) blocked: Mutex<HashMap<String, VecDeque<Arc<blocking::Waiter>>>>,
+  // shared by single-key commands, held exclusively by multi-key ones
+  pub(crate) command_lock: RwLock<()>,
+  // commands rejected because their name is not in the command table
+  pub(crate) unknown_commands: AtomicU64,
+}
+
+/// The `NX | XX` options of `SET`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SetCondition {
+  #[default]
+  Always,
+  IfNotExists,
+  IfExists,
+}
+
+/// What happens to the ttl of a key overwritten by `SET`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SetTtl {
+  #[default]
+  Discard,
+  Keep,
+  /// A deadline in unix milliseconds.
+  At(i64),
 }
 
 impl Deref for Backend {
@@ -28,7 +93,13 @@ impl Default for Backend {
 
 impl Default for BackendInner {
   fn default() -> Self {
-    Self { map: DashMap::new(), hmap: DashMap::new(), set: DashMap::new() }
+    Self {
+      keyspace: Scannable::default(),
+      expires: DashMap::new(),
+      blocked: Mutex::new(HashMap::new()),
+      command_lock: RwLock::new(()),
+      unknown_commands: AtomicU64::new(0),
+    }
   }
 }
 
@@ -37,35 +108,173 @@ impl Backend {
     Self::default()
   }
 
-  pub fn get(&self, key: &str) -> Option<RespFrame> {
-    self.map.get(key).map(|v| v.value().clone())
+  /// Count a command rejected as unknown, returns the new total.
+  pub fn count_unknown_command(&self) -> u64 {
+    self.unknown_commands.fetch_add(1, Ordering::Relaxed) + 1
+  }
+
+  /// How many unknown commands were received so far.
+  pub fn unknown_commands(&self) -> u64 {
+    self.unknown_commands.load(Ordering::Relaxed)
+  }
+
+  pub fn exists(&self, key: &str) -> bool {
+    self.expire_if_needed(key);
+    self.keyspace.contains_key(key)
+  }
+
+  pub fn remove(&self, key: &str) -> bool {
+    self.expire_if_needed(key);
+    let removed = self.keyspace.remove(key).is_some();
+    self.expires.remove(key);
+    removed
+  }
+
+  /// The type name of the value held by `key`, if any.
+  pub fn type_of(&self, key: &str) -> Option<&'static str> {
+    self.expire_if_needed(key);
+    self.keyspace.get(key).map(|v| v.type_name())
+  }
+
+  pub fn get(&self, key: &str) -> Result<Option<RespFrame>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::String(v)) => Ok(Some(v.clone())),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
   }
 
   pub fn set(&self, key: String, value: RespFrame) {
-    self.map.insert(key, value);
+    // a plain overwrite never looks at the old value, so it cannot fail
+    let _ = self.set_if(key, value, SetCondition::Always, SetTtl::Discard, false);
+  }
+
+  /// Write a string value if `cond` holds, atomically with respect to other writers of `key`.
+  /// Returns whether the value was written, along with the previous value if it was a string.
+  /// With `return_old` set, a previous value of another type is an error and nothing is written.
+  pub fn set_if(
+    &self,
+    key: String,
+    value: RespFrame,
+    cond: SetCondition,
+    ttl: SetTtl,
+    return_old: bool,
+  ) -> Result<(bool, Option<RespFrame>), CommandError> {
+    self.expire_if_needed(&key);
+    // keep the shard locked until the ttl is updated so readers never see a stale deadline
+    let entry = self.keyspace.entry(key);
+    let (exists, old) = match &entry {
+      Entry::Occupied(v) => match v.get() {
+        Value::String(old) => (true, Some(old.clone())),
+        _ if return_old => return Err(CommandError::WrongType),
+        _ => (true, None),
+      },
+      Entry::Vacant(_) => (false, None),
+    };
+    let ok = match cond {
+      SetCondition::Always => true,
+      SetCondition::IfNotExists => !exists,
+      SetCondition::IfExists => exists,
+    };
+    if !ok {
+      return Ok((false, old));
+    }
+
+    match ttl {
+      SetTtl::Discard => {
+        self.expires.remove(entry.key());
+      }
+      SetTtl::Keep => {}
+      SetTtl::At(at) => {
+        self.expires.insert(entry.key().clone(), at);
+      }
+    }
+    entry.insert(Value::String(value));
+    Ok((true, old))
+  }
+
+  pub fn hget(&self, key: &str, field: &str) -> Result<Option<RespFrame>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Hash(hmap)) => Ok(hmap.get(field).map(|v| v.value().clone())),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
   }
 
-  pub fn hget(&self, key: &str, field: &str) -> Option<RespFrame> {
-    self.hmap.get(key).and_then(|v| v.get(field).map(|v| v.value().clone()))
+  /// Set `field` in the hash at `key`, returns whether the field is new.
+  pub fn hset(&self, key: String, field: String, value: RespFrame) -> Result<bool, CommandError> {
+    self.expire_if_needed(&key);
+    let entry = self.keyspace.entry(key).or_insert_with(|| Value::Hash(Scannable::default()));
+    match entry.value() {
+      Value::Hash(hmap) => Ok(hmap.insert(field, value).is_none()),
+      _ => Err(CommandError::WrongType),
+    }
   }
 
-  pub fn hset(&self, key: String, field: String, value: RespFrame) {
-    let hmap = self.hmap.entry(key).or_default();
-    hmap.insert(field, value);
+  pub fn hgetall(&self, key: &str) -> Result<Option<DashMap<String, RespFrame>>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Hash(hmap)) => Ok(Some(hmap.snapshot())),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
   }
 
-  pub fn hgetall(&self, key: &str) -> Option<DashMap<String, RespFrame>> {
-    self.hmap.get(key).map(|v| v.clone())
+  pub fn sadd(
+    &self,
+    key: impl Into<String>,
+    member: impl Into<String>,
+  ) -> Result<bool, CommandError> {
+    let key = key.into();
+    self.expire_if_needed(&key);
+    let entry = self.keyspace.entry(key).or_insert_with(|| Value::Set(Scannable::default()));
+    match entry.value() {
+      Value::Set(set) => Ok(set.insert(member.into())),
+      _ => Err(CommandError::WrongType),
+    }
   }
 
-  pub fn sadd(&self, key: impl Into<String>, member: impl Into<String>) -> bool {
-    self.set.entry(key.into()).or_default().insert(member.into())
+  pub fn smembers(&self, key: &str) -> Result<Option<DashSet<String>>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Set(set)) => Ok(Some(set.snapshot())),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
   }
 
-  pub fn smembers(&self, key: &str) -> Option<DashSet<String>> {
-    self.set.get(key).map(|v| v.clone())
+  pub fn sismember(&self, key: &str, member: &str) -> Result<bool, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Set(set)) => Ok(set.contains(member)),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(false),
+    }
   }
-  pub fn sismember(&self, key: &str, member: &str) -> bool {
-    self.set.get(key).map(|v| v.contains(member)).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_keyspace_is_shared_across_types() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.hset("key".to_string(), "field".to_string(), RespFrame::Integer(1))?;
+
+    assert_eq!(backend.type_of("key"), Some("hash"));
+    assert!(matches!(backend.get("key"), Err(CommandError::WrongType)));
+    assert!(matches!(backend.sadd("key", "member"), Err(CommandError::WrongType)));
+    assert!(matches!(backend.smembers("key"), Err(CommandError::WrongType)));
+
+    // SET replaces a value of any type
+    backend.set("key".to_string(), RespFrame::Integer(2));
+    assert_eq!(backend.type_of("key"), Some("string"));
+    assert!(matches!(backend.hget("key", "field"), Err(CommandError::WrongType)));
+    assert_eq!(backend.type_of("missing"), None);
+
+    Ok(())
   }
 }
@@ -0,0 +1,516 @@
+use super::{Backend, Value};
+use crate::{CommandError, RespFrame};
+use dashmap::{
+  iter,
+  mapref::{
+    entry,
+    one::{Ref, RefMut},
+  },
+  setref::multiple::RefMulti,
+  DashMap, DashSet,
+};
+use std::{
+  collections::{hash_map::DefaultHasher, BTreeSet},
+  hash::{Hash, Hasher},
+  ops::Bound,
+  sync::{OnceLock, RwLock},
+};
+
+// Cursors are positions in a fixed 64-bit hash space: each call returns the elements with the
+// smallest hashes at or after the cursor, and the next cursor is one past the largest of them.
+// Since the position of an element never changes, anything present for the whole iteration is
+// returned exactly once no matter how the underlying maps are resized or mutated in between.
+// Every scannable map keeps its keys ordered by that position, so a page costs O(COUNT).
+// Like the map itself the index is split in shards, each one owning a contiguous range of
+// positions, so writers only contend when their keys fall in the same range.
+
+fn scan_hash(s: &str) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  s.hash(&mut hasher);
+  hasher.finish()
+}
+
+/// The number of index shards, the same power of two dashmap picks for its own maps.
+fn shard_amount() -> usize {
+  static SHARD_AMOUNT: OnceLock<usize> = OnceLock::new();
+  *SHARD_AMOUNT.get_or_init(|| {
+    (std::thread::available_parallelism().map_or(1, usize::from) * 4).next_power_of_two()
+  })
+}
+
+/// Keys of one range of positions, along with their position.
+type Shard = RwLock<BTreeSet<(u64, String)>>;
+
+/// The keys of a map ordered by their position in the scan.
+#[derive(Debug)]
+struct ScanIndex(Box<[Shard]>);
+
+impl Default for ScanIndex {
+  fn default() -> Self {
+    ScanIndex((0..shard_amount()).map(|_| RwLock::default()).collect())
+  }
+}
+
+impl Clone for ScanIndex {
+  fn clone(&self) -> Self {
+    ScanIndex(self.0.iter().map(|shard| RwLock::new(shard.read().unwrap().clone())).collect())
+  }
+}
+
+impl ScanIndex {
+  /// The shard owning `hash`, picked by its high bits so shards follow the scan order.
+  fn shard_of(&self, hash: u64) -> usize {
+    let bits = self.0.len().trailing_zeros();
+    hash.checked_shr(u64::BITS - bits).unwrap_or(0) as usize
+  }
+
+  fn add(&self, key: &str) {
+    let hash = scan_hash(key);
+    self.0[self.shard_of(hash)].write().unwrap().insert((hash, key.to_string()));
+  }
+
+  fn remove(&self, key: &str) {
+    let hash = scan_hash(key);
+    self.0[self.shard_of(hash)].write().unwrap().remove(&(hash, key.to_string()));
+  }
+
+  /// At least `count` keys from `cursor` on, along with every other key sharing the hash of
+  /// the last one, and the next cursor, 0 once the iteration is complete.
+  fn page(&self, cursor: u64, count: usize) -> (u64, Vec<String>) {
+    let (mut keys, mut last) = (vec![], 0);
+    // keys sharing a hash share a shard, so a page never splits them across shards
+    for shard in &self.0[self.shard_of(cursor)..] {
+      let shard = shard.read().unwrap();
+      for (hash, key) in shard.range((Bound::Included((cursor, String::new())), Bound::Unbounded)) {
+        if keys.len() >= count.max(1) && *hash != last {
+          return (last + 1, keys);
+        }
+        keys.push(key.clone());
+        last = *hash;
+      }
+    }
+    (0, keys)
+  }
+}
+
+/// A map or set whose keys can be scanned page by page. The underlying collection is never
+/// handed out, since its `&self` mutators would skip the index: reads and writes go through
+/// the methods below, which keep the scan order up to date.
+#[derive(Debug, Clone, Default)]
+pub struct Scannable<T> {
+  inner: T,
+  index: ScanIndex,
+}
+
+impl<T> Scannable<T> {
+  /// One page of keys from `cursor` on, see `ScanIndex::page`.
+  pub fn scan_page(&self, cursor: u64, count: usize) -> (u64, Vec<String>) {
+    self.index.page(cursor, count)
+  }
+
+  /// A copy of the underlying collection, detached from the index.
+  pub fn snapshot(&self) -> T
+  where
+    T: Clone,
+  {
+    self.inner.clone()
+  }
+}
+
+impl<V> Scannable<DashMap<String, V>> {
+  pub fn get(&self, key: &str) -> Option<Ref<'_, String, V>> {
+    self.inner.get(key)
+  }
+
+  /// Mutable access to the value at `key`, the keys themselves stay unchanged.
+  pub fn get_mut(&self, key: &str) -> Option<RefMut<'_, String, V>> {
+    self.inner.get_mut(key)
+  }
+
+  pub fn contains_key(&self, key: &str) -> bool {
+    self.inner.contains_key(key)
+  }
+
+  pub fn iter(&self) -> iter::Iter<'_, String, V> {
+    self.inner.iter()
+  }
+
+  pub fn len(&self) -> usize {
+    self.inner.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.inner.is_empty()
+  }
+
+  pub fn entry(&self, key: String) -> Entry<'_, V> {
+    match self.inner.entry(key) {
+      entry::Entry::Occupied(entry) => Entry::Occupied(OccupiedEntry { entry, index: &self.index }),
+      entry::Entry::Vacant(entry) => Entry::Vacant(VacantEntry { entry, index: &self.index }),
+    }
+  }
+
+  pub fn insert(&self, key: String, value: V) -> Option<V> {
+    match self.entry(key) {
+      Entry::Occupied(mut entry) => Some(entry.insert(value)),
+      Entry::Vacant(entry) => {
+        entry.insert(value);
+        None
+      }
+    }
+  }
+
+  pub fn remove(&self, key: &str) -> Option<(String, V)> {
+    self.remove_if(key, |_, _| true)
+  }
+
+  pub fn remove_if(&self, key: &str, f: impl FnOnce(&String, &V) -> bool) -> Option<(String, V)> {
+    match self.entry(key.to_string()) {
+      Entry::Occupied(entry) if f(entry.key(), entry.get()) => Some(entry.remove_entry()),
+      _ => None,
+    }
+  }
+}
+
+impl<V> FromIterator<(String, V)> for Scannable<DashMap<String, V>> {
+  fn from_iter<I: IntoIterator<Item = (String, V)>>(iter: I) -> Self {
+    let map = Self::default();
+    for (key, value) in iter {
+      map.insert(key, value);
+    }
+    map
+  }
+}
+
+impl Scannable<DashSet<String>> {
+  pub fn contains(&self, member: &str) -> bool {
+    self.inner.contains(member)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = RefMulti<'_, String>> {
+    self.inner.iter()
+  }
+
+  pub fn len(&self) -> usize {
+    self.inner.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.inner.is_empty()
+  }
+
+  // members are only ever changed with the key of the set locked for writing, so updating
+  // the index right after the set cannot race with another writer
+  pub fn insert(&self, member: String) -> bool {
+    let added = self.inner.insert(member.clone());
+    if added {
+      self.index.add(&member);
+    }
+    added
+  }
+
+  pub fn remove(&self, member: &str) -> Option<String> {
+    let removed = self.inner.remove(member);
+    if removed.is_some() {
+      self.index.remove(member);
+    }
+    removed
+  }
+}
+
+impl FromIterator<String> for Scannable<DashSet<String>> {
+  fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+    let set = Self::default();
+    for member in iter {
+      set.insert(member);
+    }
+    set
+  }
+}
+
+/// An entry of a scannable map, which keeps its index in step while holding the lock of
+/// the key, so concurrent writers to the same key cannot interleave their index updates.
+pub enum Entry<'a, V> {
+  Occupied(OccupiedEntry<'a, V>),
+  Vacant(VacantEntry<'a, V>),
+}
+
+pub struct OccupiedEntry<'a, V> {
+  entry: entry::OccupiedEntry<'a, String, V>,
+  index: &'a ScanIndex,
+}
+
+pub struct VacantEntry<'a, V> {
+  entry: entry::VacantEntry<'a, String, V>,
+  index: &'a ScanIndex,
+}
+
+impl<'a, V> Entry<'a, V> {
+  pub fn key(&self) -> &String {
+    match self {
+      Entry::Occupied(entry) => entry.key(),
+      Entry::Vacant(entry) => entry.key(),
+    }
+  }
+
+  pub fn insert(self, value: V) -> RefMut<'a, String, V> {
+    match self {
+      Entry::Occupied(mut entry) => {
+        entry.insert(value);
+        entry.into_ref()
+      }
+      Entry::Vacant(entry) => entry.insert(value),
+    }
+  }
+
+  pub fn or_insert_with(self, value: impl FnOnce() -> V) -> RefMut<'a, String, V> {
+    match self {
+      Entry::Occupied(entry) => entry.into_ref(),
+      Entry::Vacant(entry) => entry.insert(value()),
+    }
+  }
+}
+
+impl<'a, V> OccupiedEntry<'a, V> {
+  pub fn key(&self) -> &String {
+    self.entry.key()
+  }
+
+  pub fn get(&self) -> &V {
+    self.entry.get()
+  }
+
+  pub fn get_mut(&mut self) -> &mut V {
+    self.entry.get_mut()
+  }
+
+  pub fn insert(&mut self, value: V) -> V {
+    self.entry.insert(value)
+  }
+
+  pub fn into_ref(self) -> RefMut<'a, String, V> {
+    self.entry.into_ref()
+  }
+
+  pub fn remove(self) -> V {
+    self.remove_entry().1
+  }
+
+  pub fn remove_entry(self) -> (String, V) {
+    self.index.remove(self.entry.key());
+    self.entry.remove_entry()
+  }
+}
+
+impl<'a, V> VacantEntry<'a, V> {
+  pub fn key(&self) -> &String {
+    self.entry.key()
+  }
+
+  pub fn insert(self, value: V) -> RefMut<'a, String, V> {
+    let entry = self.entry.insert(value);
+    self.index.add(entry.key());
+    entry
+  }
+
+  pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, V> {
+    let entry = self.entry.insert_entry(value);
+    self.index.add(entry.key());
+    OccupiedEntry { entry, index: self.index }
+  }
+}
+
+impl Backend {
+  /// One page of keys, optionally restricted to values of the type named `type_name`. Like
+  /// in redis the filters apply after the page is taken, so it may come back short or empty.
+  pub fn scan(&self, cursor: u64, count: usize, type_name: Option<&str>) -> (u64, Vec<String>) {
+    let now = super::now_ms();
+    let (cursor, mut keys) = self.keyspace.scan_page(cursor, count);
+    keys.retain(|key| {
+      if self.expires.get(key).is_some_and(|at| *at <= now) {
+        return false;
+      }
+      match (self.keyspace.get(key), type_name) {
+        (Some(value), Some(t)) => value.type_name().eq_ignore_ascii_case(t),
+        (value, _) => value.is_some(),
+      }
+    });
+    (cursor, keys)
+  }
+
+  pub fn hscan(
+    &self,
+    key: &str,
+    cursor: u64,
+    count: usize,
+  ) -> Result<(u64, Vec<(String, RespFrame)>), CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Hash(hmap)) => {
+        let (cursor, fields) = hmap.scan_page(cursor, count);
+        let fields = fields
+          .into_iter()
+          .filter_map(|field| hmap.get(&field).map(|v| (field, v.value().clone())))
+          .collect();
+        Ok((cursor, fields))
+      }
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok((0, vec![])),
+    }
+  }
+
+  pub fn sscan(
+    &self,
+    key: &str,
+    cursor: u64,
+    count: usize,
+  ) -> Result<(u64, Vec<String>), CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Set(set)) => Ok(set.scan_page(cursor, count)),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok((0, vec![])),
+    }
+  }
+
+  /// Every key that has not expired.
+  pub fn keys(&self) -> Vec<String> {
+    let now = super::now_ms();
+    self
+      .keyspace
+      .iter()
+      .filter(|v| self.expires.get(v.key()).is_none_or(|at| *at > now))
+      .map(|v| v.key().clone())
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashSet;
+
+  fn scan_all(backend: &Backend, count: usize) -> Vec<String> {
+    let (mut cursor, mut ret) = (0, vec![]);
+    loop {
+      let (next, keys) = backend.scan(cursor, count, None);
+      ret.extend(keys);
+      if next == 0 {
+        return ret;
+      }
+      cursor = next;
+    }
+  }
+
+  #[test]
+  fn test_scan_page_keeps_ties_together() {
+    let index = ScanIndex::default();
+    for (hash, key) in [(5, "a"), (3, "b"), (5, "c"), (9, "d"), (1, "e")] {
+      index.0[index.shard_of(hash)].write().unwrap().insert((hash, key.to_string()));
+    }
+    assert_eq!(index.page(0, 2), (4, vec!["e".to_string(), "b".to_string()]));
+    assert_eq!(index.page(4, 1), (6, vec!["a".to_string(), "c".to_string()]));
+    assert_eq!(index.page(6, 2), (0, vec!["d".to_string()]));
+  }
+
+  #[test]
+  fn test_scan_page_walks_shards_in_order() {
+    let index = ScanIndex::default();
+    let hashes = [0, 1 << 40, u64::MAX / 2, u64::MAX / 2 + 1, u64::MAX - 1];
+    for (i, hash) in hashes.into_iter().enumerate().rev() {
+      index.0[index.shard_of(hash)].write().unwrap().insert((hash, i.to_string()));
+    }
+    assert_ne!(index.shard_of(0), index.shard_of(u64::MAX - 1));
+    assert_eq!(index.page(0, 2), ((1 << 40) + 1, vec!["0".to_string(), "1".to_string()]));
+    assert_eq!(index.page((1 << 40) + 1, 2), (u64::MAX / 2 + 2, vec!["2".into(), "3".into()]));
+    assert_eq!(index.page(u64::MAX / 2 + 2, 2), (0, vec!["4".to_string()]));
+  }
+
+  #[test]
+  fn test_scannable_keeps_index_in_step() {
+    let map = Scannable::<DashMap<String, i64>>::default();
+    map.insert("a".to_string(), 1);
+    map.entry("b".to_string()).or_insert_with(|| 2);
+    map.insert("a".to_string(), 3);
+    assert_eq!(map.scan_page(0, 10).1.len(), 2);
+    map.remove("a");
+    if let Entry::Occupied(entry) = map.entry("b".to_string()) {
+      entry.remove();
+    }
+    assert_eq!(map.scan_page(0, 10), (0, vec![]));
+
+    let set = ["x", "y"].map(String::from).into_iter().collect::<Scannable<DashSet<String>>>();
+    set.remove("x");
+    assert_eq!(set.scan_page(0, 10), (0, vec!["y".to_string()]));
+  }
+
+  #[test]
+  fn test_scan_returns_every_key_once() {
+    let backend = Backend::new();
+    for i in 0..100 {
+      backend.set(format!("key:{}", i), RespFrame::Integer(i));
+    }
+    backend.sadd("set", "member").unwrap();
+
+    let keys = scan_all(&backend, 7);
+    assert_eq!(keys.len(), 101);
+    assert_eq!(keys.iter().collect::<HashSet<_>>().len(), 101);
+
+    let (_, keys) = backend.scan(0, 1000, Some("set"));
+    assert_eq!(keys, vec!["set".to_string()]);
+  }
+
+  #[test]
+  fn test_scan_survives_concurrent_writes() {
+    let backend = Backend::new();
+    for i in 0..50 {
+      backend.set(format!("old:{}", i), RespFrame::Integer(i));
+    }
+
+    let (mut cursor, mut seen) = (0, HashSet::new());
+    let mut i = 0;
+    loop {
+      let (next, keys) = backend.scan(cursor, 5, None);
+      seen.extend(keys);
+      // grow the keyspace between calls
+      for _ in 0..20 {
+        backend.set(format!("new:{}", i), RespFrame::Integer(i));
+        i += 1;
+      }
+      if next == 0 {
+        break;
+      }
+      cursor = next;
+    }
+    assert!((0..50).all(|i| seen.contains(&format!("old:{}", i))));
+  }
+
+  #[test]
+  fn test_sscan_and_hscan() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    for i in 0..20 {
+      backend.sadd("set", i.to_string())?;
+      backend.hset("hash".to_string(), i.to_string(), RespFrame::Integer(i))?;
+    }
+
+    let (mut cursor, mut members) = (0, vec![]);
+    loop {
+      let (next, page) = backend.sscan("set", cursor, 3)?;
+      members.extend(page);
+      if next == 0 {
+        break;
+      }
+      cursor = next;
+    }
+    assert_eq!(members.len(), 20);
+
+    let (next, fields) = backend.hscan("hash", 0, 100)?;
+    assert_eq!(next, 0);
+    assert_eq!(fields.len(), 20);
+
+    assert_eq!(backend.hscan("set", 0, 10), Err(CommandError::WrongType));
+    assert_eq!(backend.sscan("missing", 0, 10)?, (0, vec![]));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,321 @@
+use super::{scan::Entry, Backend, Scannable, Value};
+use crate::CommandError;
+use dashmap::DashSet;
+use rand::{seq::index, Rng};
+use std::collections::HashSet;
+
+/// How `SUNION`, `SINTER` and `SDIFF` combine their sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOp {
+  Union,
+  Inter,
+  Diff,
+}
+
+/// Pick `count` random items out of the `len` ones of `items` the way `SRANDMEMBER` and
+/// `HRANDFIELD` do: a positive count gives distinct items, at most all of them, a negative one
+/// allows repeats and always gives `-count` items unless there are none.
+///
+/// `items` is walked once and only the picked items are turned into a `T` by `f`.
+pub(super) fn sample<I: Iterator, T: Clone>(
+  items: I,
+  len: usize,
+  count: i64,
+  f: impl Fn(I::Item) -> T,
+) -> Vec<T> {
+  let mut rng = rand::thread_rng();
+  let picks = match count {
+    count if count >= 0 => index::sample(&mut rng, len, len.min(count as usize)).into_vec(),
+    _ if len == 0 => return Vec::new(),
+    count => (0..count.unsigned_abs()).map(|_| rng.gen_range(0..len)).collect(),
+  };
+
+  // the positions in the reply, in the order their items come up
+  let mut order = (0..picks.len()).collect::<Vec<_>>();
+  order.sort_unstable_by_key(|&i| picks[i]);
+  let mut order = order.into_iter().peekable();
+  let mut ret = vec![None; picks.len()];
+  for (i, item) in items.enumerate() {
+    match order.peek() {
+      Some(&next) if picks[next] == i => {}
+      Some(_) => continue,
+      None => break,
+    }
+    let item = f(item);
+    while let Some(next) = order.next_if(|&next| picks[next] == i) {
+      ret[next] = Some(item.clone());
+    }
+  }
+  ret.into_iter().flatten().collect()
+}
+
+impl Backend {
+  /// Run `f` against the set at `key`, created empty if missing unless `create` is false;
+  /// the key is removed if `f` leaves the set empty. Returns `None` if there is no set.
+  pub(super) fn with_set<T>(
+    &self,
+    key: &str,
+    create: bool,
+    f: impl FnOnce(&Scannable<DashSet<String>>) -> T,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    let entry = match self.keyspace.entry(key.to_string()) {
+      Entry::Occupied(entry) => entry,
+      Entry::Vacant(_) if !create => return Ok(None),
+      Entry::Vacant(entry) => entry.insert_entry(Value::Set(Scannable::default())),
+    };
+    let Value::Set(set) = entry.get() else {
+      return Err(CommandError::WrongType);
+    };
+    let ret = f(set);
+    if set.is_empty() {
+      entry.remove();
+      self.expires.remove(key);
+    }
+    Ok(Some(ret))
+  }
+
+  /// Run `f` against the set at `key`, returns `None` if it is missing.
+  pub(super) fn read_set<T>(
+    &self,
+    key: &str,
+    f: impl FnOnce(&Scannable<DashSet<String>>) -> T,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Set(set)) => Ok(Some(f(set))),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
+  }
+
+  /// Remove `members`, returns the number of members that existed.
+  pub fn srem(&self, key: &str, members: &[String]) -> Result<usize, CommandError> {
+    let removed = self
+      .with_set(key, false, |set| members.iter().filter(|m| set.remove(m).is_some()).count())?;
+    Ok(removed.unwrap_or_default())
+  }
+
+  pub fn scard(&self, key: &str) -> Result<usize, CommandError> {
+    Ok(self.read_set(key, |set| set.len())?.unwrap_or_default())
+  }
+
+  pub fn smismember(&self, key: &str, members: &[String]) -> Result<Vec<bool>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Set(set)) => Ok(members.iter().map(|m| set.contains(m)).collect()),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(vec![false; members.len()]),
+    }
+  }
+
+  /// Move `member` from the set at `src` to the one at `dst`, returns false if it was not in
+  /// `src`. Callers hold the command lock exclusively.
+  pub fn smove(&self, src: &str, dst: &str, member: &str) -> Result<bool, CommandError> {
+    self.expire_if_needed(dst);
+    match self.keyspace.get(dst).as_deref() {
+      Some(Value::Set(_)) | None => {}
+      Some(_) => return Err(CommandError::WrongType),
+    }
+    if src == dst {
+      return self.sismember(src, member);
+    }
+    let moved = self.with_set(src, false, |set| set.remove(member).is_some())?.unwrap_or_default();
+    if moved {
+      self.with_set(dst, true, |set| set.insert(member.to_string()))?;
+    }
+    Ok(moved)
+  }
+
+  /// The union, intersection or difference of the sets at `keys`, missing keys being empty
+  /// sets. The difference is the first set minus all the others.
+  pub fn scombine(&self, op: SetOp, keys: &[String]) -> Result<Vec<String>, CommandError> {
+    // read every key first so a wrong type fails the command whatever the other sets hold
+    let sets = keys
+      .iter()
+      .map(|key| {
+        let members = self.read_set(key, |set| set.iter().map(|m| m.key().clone()).collect())?;
+        Ok(members.unwrap_or_default())
+      })
+      .collect::<Result<Vec<HashSet<String>>, CommandError>>()?;
+    let mut sets = sets.into_iter();
+    let first = sets.next().unwrap_or_default();
+    let ret = match op {
+      SetOp::Union => sets.fold(first, |mut acc, set| {
+        acc.extend(set);
+        acc
+      }),
+      SetOp::Inter => sets.fold(first, |acc, set| acc.intersection(&set).cloned().collect()),
+      SetOp::Diff => sets.fold(first, |acc, set| acc.difference(&set).cloned().collect()),
+    };
+    Ok(ret.into_iter().collect())
+  }
+
+  /// Replace `dst` by a set of `members`, deleting it if there are none. Returns the size of
+  /// the stored set.
+  pub fn sstore(&self, dst: &str, members: Vec<String>) -> usize {
+    self.remove(dst);
+    if members.is_empty() {
+      return 0;
+    }
+    let set = members.into_iter().collect::<Scannable<DashSet<String>>>();
+    let len = set.len();
+    self.keyspace.insert(dst.to_string(), Value::Set(set));
+    len
+  }
+
+  /// Up to `count` random members of the set at `key`, see [`sample`] for negative counts.
+  pub fn srandmember(&self, key: &str, count: i64) -> Result<Vec<String>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Set(set)) => Ok(sample(set.iter(), set.len(), count, |m| m.key().clone())),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(Vec::new()),
+    }
+  }
+
+  /// Remove and return up to `count` distinct random members of the set at `key`.
+  pub fn spop(&self, key: &str, count: usize) -> Result<Vec<String>, CommandError> {
+    let popped = self.with_set(key, false, |set| {
+      let count = count.min(set.len()) as i64;
+      let popped = sample(set.iter(), set.len(), count, |m| m.key().clone());
+      for member in &popped {
+        set.remove(member);
+      }
+      popped
+    })?;
+    Ok(popped.unwrap_or_default())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashSet;
+
+  #[test]
+  fn test_sample() {
+    let items = [1, 2, 3];
+    let sample = |count| sample(items.iter(), items.len(), count, |i| *i);
+    let distinct = sample(5);
+    assert_eq!(distinct.iter().collect::<HashSet<_>>().len(), 3);
+    assert_eq!(sample(2).len(), 2);
+    assert!(sample(0).is_empty());
+
+    let repeated = sample(-10);
+    assert_eq!(repeated.len(), 10);
+    assert!(repeated.iter().all(|i| items.contains(i)));
+    assert!(super::sample(std::iter::empty::<i32>(), 0, -3, |i| i).is_empty());
+  }
+
+  #[test]
+  fn test_sample_converts_picked_items_only() {
+    let converted = std::cell::Cell::new(0);
+    let picked = sample(0..1000, 1000, -50, |i| {
+      converted.set(converted.get() + 1);
+      i
+    });
+    assert_eq!(picked.len(), 50);
+    assert!(converted.get() <= 50);
+  }
+
+  #[test]
+  fn test_sample_is_uniform() {
+    let items = [0, 1, 2, 3];
+    let mut hits = [0; 4];
+    for i in sample(items.iter(), items.len(), -40_000, |i| *i) {
+      hits[i] += 1;
+    }
+    // each item is expected 10_000 times, a skewed pick would be far off
+    assert!(hits.iter().all(|n| (9_000..11_000).contains(n)), "{:?}", hits);
+  }
+
+  #[test]
+  fn test_srandmember_spop() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    for member in ["a", "b", "c"] {
+      backend.sadd("s", member)?;
+    }
+    assert_eq!(backend.srandmember("s", 10)?.len(), 3);
+    assert_eq!(backend.srandmember("s", -5)?.len(), 5);
+    assert!(backend.srandmember("missing", -5)?.is_empty());
+
+    let popped = backend.spop("s", 2)?;
+    assert_eq!(popped.len(), 2);
+    assert!(popped.iter().all(|m| !backend.sismember("s", m).unwrap()));
+    assert_eq!(backend.spop("s", 5)?.len(), 1);
+    assert!(!backend.exists("s"));
+    assert!(backend.spop("s", 1)?.is_empty());
+
+    backend.set("str".to_string(), crate::RespFrame::Integer(1));
+    assert_eq!(backend.spop("str", 1), Err(CommandError::WrongType));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_srem_scard_smismember() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    for member in ["a", "b", "c"] {
+      backend.sadd("s", member)?;
+    }
+    assert_eq!(backend.scard("s")?, 3);
+    let members = ["a", "x"].map(String::from);
+    assert_eq!(backend.smismember("s", &members)?, [true, false]);
+    assert_eq!(backend.smismember("missing", &members)?, [false, false]);
+    assert_eq!(backend.srem("s", &members)?, 1);
+    assert_eq!(backend.srem("s", &["b".to_string(), "c".to_string()])?, 2);
+    assert!(!backend.exists("s"));
+    assert_eq!(backend.scard("s")?, 0);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_smove() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.sadd("src", "a")?;
+    assert!(backend.smove("src", "dst", "a")?);
+    assert!(!backend.exists("src"));
+    assert!(backend.sismember("dst", "a")?);
+    assert!(!backend.smove("src", "dst", "a")?);
+    assert!(backend.smove("dst", "dst", "a")?);
+
+    backend.set("str".to_string(), crate::RespFrame::Integer(1));
+    assert_eq!(backend.smove("dst", "str", "a"), Err(CommandError::WrongType));
+    assert!(backend.sismember("dst", "a")?);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_scombine_sstore() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    for (key, members) in [("k1", ["a", "b", "c"]), ("k2", ["c", "d", "e"])] {
+      for member in members {
+        backend.sadd(key, member)?;
+      }
+    }
+    let sorted = |mut members: Vec<String>| {
+      members.sort();
+      members
+    };
+    let keys = ["k1", "k2"].map(String::from);
+    assert_eq!(sorted(backend.scombine(SetOp::Union, &keys)?), ["a", "b", "c", "d", "e"]);
+    assert_eq!(backend.scombine(SetOp::Inter, &keys)?, ["c"]);
+    assert_eq!(sorted(backend.scombine(SetOp::Diff, &keys)?), ["a", "b"]);
+    let keys = ["k1", "missing"].map(String::from);
+    assert!(backend.scombine(SetOp::Inter, &keys)?.is_empty());
+
+    assert_eq!(backend.sstore("dst", backend.scombine(SetOp::Union, &keys)?), 3);
+    assert_eq!(backend.scard("dst")?, 3);
+    assert_eq!(backend.sstore("dst", Vec::new()), 0);
+    assert!(!backend.exists("dst"));
+
+    backend.set("str".to_string(), crate::RespFrame::Integer(1));
+    let keys = ["missing", "str"].map(String::from);
+    assert_eq!(backend.scombine(SetOp::Inter, &keys), Err(CommandError::WrongType));
+
+    Ok(())
+  }
+}
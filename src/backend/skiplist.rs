@@ -0,0 +1,325 @@
+use rand::Rng;
+use std::iter::FusedIterator;
+
+/// Levels a node may have, enough for 2^64 elements at p = 1/4.
+const MAX_LEVEL: usize = 32;
+
+/// An ordered set of distinct keys that finds the position of a key, and the key at a
+/// position, in O(log N): the skiplist of redis sorted sets, where each link records how many
+/// elements it skips.
+#[derive(Debug, Clone)]
+pub(super) struct SkipList<K> {
+  // the links of the header, one per level in use
+  head: Vec<Link>,
+  // nodes by index, free slots are reused before the vector grows
+  nodes: Vec<Option<Node<K>>>,
+  free: Vec<usize>,
+  tail: Option<usize>,
+  len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node<K> {
+  key: K,
+  backward: Option<usize>,
+  links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Link {
+  forward: Option<usize>,
+  // elements passed by following `forward`, the target included
+  span: usize,
+}
+
+impl<K> Default for SkipList<K> {
+  fn default() -> Self {
+    Self { head: vec![Link::default()], nodes: vec![], free: vec![], tail: None, len: 0 }
+  }
+}
+
+impl<K: Ord> SkipList<K> {
+  pub fn first(&self) -> Option<&K> {
+    self.head[0].forward.map(|i| self.key(i))
+  }
+
+  pub fn last(&self) -> Option<&K> {
+    self.tail.map(|i| self.key(i))
+  }
+
+  /// Add `key`, which must not be in the list yet.
+  pub fn insert(&mut self, key: K) {
+    // the last node before `key` on each level, and its position
+    let mut update = [None; MAX_LEVEL];
+    let mut rank = [0; MAX_LEVEL];
+    let mut x = None;
+    for i in (0..self.head.len()).rev() {
+      rank[i] = rank.get(i + 1).copied().unwrap_or_default();
+      while let Some(next) = self.link(x, i).forward.filter(|&next| *self.key(next) < key) {
+        rank[i] += self.link(x, i).span;
+        x = Some(next);
+      }
+      update[i] = x;
+    }
+
+    let level = random_level();
+    while self.head.len() < level {
+      // new levels start at the header, whose link spans the whole list
+      self.head.push(Link { forward: None, span: self.len });
+    }
+    let node = Node { key, backward: update[0], links: vec![Link::default(); level] };
+    let index = match self.free.pop() {
+      Some(index) => {
+        self.nodes[index] = Some(node);
+        index
+      }
+      None => {
+        self.nodes.push(Some(node));
+        self.nodes.len() - 1
+      }
+    };
+
+    for i in 0..level {
+      let prev = *self.link(update[i], i);
+      let skipped = rank[0] - rank[i];
+      *self.link_mut(Some(index), i) = Link { forward: prev.forward, span: prev.span - skipped };
+      *self.link_mut(update[i], i) = Link { forward: Some(index), span: skipped + 1 };
+    }
+    // the links passing over the new node
+    for (i, &prev) in update.iter().enumerate().take(self.head.len()).skip(level) {
+      self.link_mut(prev, i).span += 1;
+    }
+    match self.link(Some(index), 0).forward {
+      Some(next) => self.node_mut(next).backward = Some(index),
+      None => self.tail = Some(index),
+    }
+    self.len += 1;
+  }
+
+  /// Remove `key`, returns whether it was in the list.
+  pub fn remove(&mut self, key: &K) -> bool {
+    let mut update = [None; MAX_LEVEL];
+    let mut x = None;
+    for i in (0..self.head.len()).rev() {
+      while let Some(next) = self.link(x, i).forward.filter(|&next| self.key(next) < key) {
+        x = Some(next);
+      }
+      update[i] = x;
+    }
+    let Some(index) = self.link(x, 0).forward.filter(|&next| self.key(next) == key) else {
+      return false;
+    };
+
+    for (i, &prev) in update.iter().enumerate().take(self.head.len()) {
+      if self.link(prev, i).forward == Some(index) {
+        let removed = *self.link(Some(index), i);
+        *self.link_mut(prev, i) =
+          Link { forward: removed.forward, span: self.link(prev, i).span + removed.span - 1 };
+      } else {
+        self.link_mut(prev, i).span -= 1;
+      }
+    }
+    let backward = self.node(index).backward;
+    match self.link(Some(index), 0).forward {
+      Some(next) => self.node_mut(next).backward = backward,
+      None => self.tail = backward,
+    }
+    while self.head.len() > 1 && self.head[self.head.len() - 1].forward.is_none() {
+      self.head.pop();
+    }
+    self.nodes[index] = None;
+    self.free.push(index);
+    self.len -= 1;
+    true
+  }
+
+  /// How many keys come before the first one for which `before` is false, `before` being
+  /// true for a prefix of the keys.
+  pub fn position(&self, before: impl Fn(&K) -> bool) -> usize {
+    let mut rank = 0;
+    let mut x = None;
+    for i in (0..self.head.len()).rev() {
+      while let Some(next) = self.link(x, i).forward.filter(|&next| before(self.key(next))) {
+        rank += self.link(x, i).span;
+        x = Some(next);
+      }
+    }
+    rank
+  }
+
+  /// The keys at positions `start..end` in ascending order, in O(log N) plus the ones
+  /// visited. The range is clamped to the list.
+  pub fn range(&self, start: usize, end: usize) -> Iter<'_, K> {
+    let end = end.min(self.len);
+    if start >= end {
+      return Iter { list: self, front: None, back: None, len: 0 };
+    }
+    let front = self.at(start);
+    let back = if end == self.len { self.tail } else { self.at(end - 1) };
+    Iter { list: self, front, back, len: end - start }
+  }
+
+  pub fn iter(&self) -> Iter<'_, K> {
+    self.range(0, self.len)
+  }
+
+  /// The node at 0-based position `rank`.
+  fn at(&self, rank: usize) -> Option<usize> {
+    let mut traversed = 0;
+    let mut x = None;
+    for i in (0..self.head.len()).rev() {
+      while let Some(next) = self.link(x, i).forward {
+        if traversed + self.link(x, i).span > rank + 1 {
+          break;
+        }
+        traversed += self.link(x, i).span;
+        x = Some(next);
+      }
+      if traversed == rank + 1 {
+        return x;
+      }
+    }
+    None
+  }
+}
+
+impl<K> SkipList<K> {
+  fn node(&self, index: usize) -> &Node<K> {
+    self.nodes[index].as_ref().expect("links only point to live nodes")
+  }
+
+  fn node_mut(&mut self, index: usize) -> &mut Node<K> {
+    self.nodes[index].as_mut().expect("links only point to live nodes")
+  }
+
+  fn key(&self, index: usize) -> &K {
+    &self.node(index).key
+  }
+
+  /// The link on `level` of node `x`, `None` being the header.
+  fn link(&self, x: Option<usize>, level: usize) -> &Link {
+    match x {
+      Some(index) => &self.node(index).links[level],
+      None => &self.head[level],
+    }
+  }
+
+  fn link_mut(&mut self, x: Option<usize>, level: usize) -> &mut Link {
+    match x {
+      Some(index) => &mut self.node_mut(index).links[level],
+      None => &mut self.head[level],
+    }
+  }
+}
+
+/// A level between 1 and `MAX_LEVEL`, each one up being 4 times less likely.
+fn random_level() -> usize {
+  let mut rng = rand::thread_rng();
+  let mut level = 1;
+  while level < MAX_LEVEL && rng.gen_ratio(1, 4) {
+    level += 1;
+  }
+  level
+}
+
+/// Keys of a [`SkipList`] between two positions, walked from either end.
+#[derive(Debug)]
+pub(super) struct Iter<'a, K> {
+  list: &'a SkipList<K>,
+  front: Option<usize>,
+  back: Option<usize>,
+  len: usize,
+}
+
+impl<'a, K> Iterator for Iter<'a, K> {
+  type Item = &'a K;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.len == 0 {
+      return None;
+    }
+    let index = self.front?;
+    self.len -= 1;
+    self.front = self.list.node(index).links[0].forward;
+    Some(self.list.key(index))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.len, Some(self.len))
+  }
+}
+
+impl<K> DoubleEndedIterator for Iter<'_, K> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.len == 0 {
+      return None;
+    }
+    let index = self.back?;
+    self.len -= 1;
+    self.back = self.list.node(index).backward;
+    Some(self.list.key(index))
+  }
+}
+
+impl<K> ExactSizeIterator for Iter<'_, K> {}
+
+impl<K> FusedIterator for Iter<'_, K> {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_skiplist_positions() {
+    let mut list = SkipList::default();
+    // enough keys for several levels, inserted out of order
+    for n in (0..1000).map(|n| n * 7 % 1000) {
+      list.insert(n);
+    }
+    assert_eq!(list.iter().len(), 1000);
+    assert_eq!((list.first(), list.last()), (Some(&0), Some(&999)));
+    assert_eq!(list.position(|&k| k < 500), 500);
+    assert_eq!(list.range(10, 13).copied().collect::<Vec<_>>(), vec![10, 11, 12]);
+    assert_eq!(list.range(997, 2000).rev().copied().collect::<Vec<_>>(), vec![999, 998, 997]);
+    assert_eq!(list.range(5, 5).count(), 0);
+
+    for n in (0..1000).filter(|n| n % 2 == 1) {
+      assert!(list.remove(&n));
+    }
+    assert!(!list.remove(&1));
+    assert_eq!(list.iter().len(), 500);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), (0..1000).step_by(2).collect::<Vec<_>>());
+    for rank in [0, 1, 250, 499] {
+      assert_eq!(list.range(rank, rank + 1).next(), Some(&(rank * 2)));
+      assert_eq!(list.position(|&k| k < rank * 2), rank);
+    }
+    assert_eq!(list.last(), Some(&998));
+
+    // freed nodes are reused
+    list.insert(1);
+    assert_eq!(list.range(0, 3).copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(list.nodes.len(), 1000);
+  }
+
+  #[test]
+  fn test_skiplist_matches_btreeset() {
+    let (mut list, mut set) = (SkipList::default(), std::collections::BTreeSet::new());
+    let mut rng = rand::thread_rng();
+    for _ in 0..5000 {
+      let key = rng.gen_range(0..500);
+      if set.insert(key) {
+        list.insert(key);
+      } else {
+        assert!(list.remove(&key));
+        set.remove(&key);
+      }
+      let start = rng.gen_range(0..=set.len());
+      let end = rng.gen_range(start..=set.len());
+      let expected = set.iter().skip(start).take(end - start).collect::<Vec<_>>();
+      assert_eq!(list.range(start, end).collect::<Vec<_>>(), expected);
+      assert_eq!(list.position(|&k| k < key), set.range(..key).count());
+    }
+    assert!(list.iter().eq(set.iter()));
+    assert!(list.iter().rev().eq(set.iter().rev()));
+  }
+}
@@ -0,0 +1,407 @@
+use super::scan::Entry;
+use super::{now_ms, stream_group::ConsumerGroup, Backend, Value};
+use crate::{CommandError, RespFrame};
+use std::{cmp::Ordering, collections::BTreeMap, fmt};
+
+/// Entries per radix tree node in redis, approximate trimming only drops whole nodes.
+const STREAM_NODE_MAX_ENTRIES: usize = 100;
+
+/// A stream entry ID, `<ms>-<seq>`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StreamId {
+  pub ms: u64,
+  pub seq: u64,
+}
+
+/// How `XADD` picks the ID of a new entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamIdSpec {
+  /// `*`
+  Auto,
+  /// `<ms>-*`
+  Partial(u64),
+  Explicit(StreamId),
+}
+
+/// The `MAXLEN | MINID` part of `XADD` and `XTRIM`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrimStrategy {
+  MaxLen(usize),
+  MinId(StreamId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamTrim {
+  pub strategy: TrimStrategy,
+  /// `~`, only whole nodes of entries are removed.
+  pub approx: bool,
+  /// The most entries removed at once, `None` for the default and 0 for no limit.
+  pub limit: Option<usize>,
+}
+
+/// The entries of a stream ordered by ID, each a flat list of field value pairs.
+#[derive(Debug, Clone, Default)]
+pub struct Stream {
+  pub(super) entries: BTreeMap<StreamId, Vec<RespFrame>>,
+  // the greatest ID ever added, new IDs must be above it even after deletes
+  pub(super) last_id: StreamId,
+  pub(super) entries_added: u64,
+  pub(super) max_deleted_id: StreamId,
+  pub(super) groups: BTreeMap<String, ConsumerGroup>,
+}
+
+impl StreamId {
+  pub const MIN: StreamId = StreamId { ms: 0, seq: 0 };
+  pub const MAX: StreamId = StreamId { ms: u64::MAX, seq: u64::MAX };
+
+  pub fn new(ms: u64, seq: u64) -> Self {
+    Self { ms, seq }
+  }
+
+  /// Parse `<ms>-<seq>` or a bare `<ms>`, whose sequence is then `missing_seq`.
+  pub fn parse(s: &str, missing_seq: u64) -> Result<Self, CommandError> {
+    fn number(s: &str) -> Result<u64, CommandError> {
+      if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(CommandError::InvalidStreamId);
+      }
+      s.parse().map_err(|_| CommandError::InvalidStreamId)
+    }
+
+    match s.split_once('-') {
+      Some((ms, seq)) => Ok(Self::new(number(ms)?, number(seq)?)),
+      None => Ok(Self::new(number(s)?, missing_seq)),
+    }
+  }
+
+  /// The smallest ID above this one.
+  pub fn next(self) -> Option<Self> {
+    match self.seq.checked_add(1) {
+      Some(seq) => Some(Self::new(self.ms, seq)),
+      None => Some(Self::new(self.ms.checked_add(1)?, 0)),
+    }
+  }
+
+  /// The greatest ID below this one.
+  pub fn prev(self) -> Option<Self> {
+    match self.seq.checked_sub(1) {
+      Some(seq) => Some(Self::new(self.ms, seq)),
+      None => Some(Self::new(self.ms.checked_sub(1)?, u64::MAX)),
+    }
+  }
+}
+
+impl fmt::Display for StreamId {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}-{}", self.ms, self.seq)
+  }
+}
+
+impl Stream {
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn last_id(&self) -> StreamId {
+    self.last_id
+  }
+
+  fn next_id(&self, spec: StreamIdSpec) -> Result<StreamId, CommandError> {
+    let last = self.last_id;
+    match spec {
+      StreamIdSpec::Auto => {
+        let ms = (now_ms().max(0) as u64).max(last.ms);
+        let id = if ms > last.ms { Some(StreamId::new(ms, 0)) } else { last.next() };
+        id.ok_or(CommandError::StreamExhausted)
+      }
+      StreamIdSpec::Partial(ms) => match ms.cmp(&last.ms) {
+        Ordering::Greater => Ok(StreamId::new(ms, 0)),
+        Ordering::Equal => last
+          .seq
+          .checked_add(1)
+          .map(|seq| StreamId::new(ms, seq))
+          .ok_or(CommandError::StreamIdTooSmall),
+        Ordering::Less => Err(CommandError::StreamIdTooSmall),
+      },
+      StreamIdSpec::Explicit(id) if id == StreamId::MIN => Err(CommandError::StreamIdZero),
+      StreamIdSpec::Explicit(id) if id <= last => Err(CommandError::StreamIdTooSmall),
+      StreamIdSpec::Explicit(id) => Ok(id),
+    }
+  }
+
+  /// Append an entry, then apply `trim`. Nothing changes if the ID is rejected.
+  fn add(
+    &mut self,
+    spec: StreamIdSpec,
+    fields: Vec<RespFrame>,
+    trim: Option<&StreamTrim>,
+  ) -> Result<StreamId, CommandError> {
+    let id = self.next_id(spec)?;
+    self.entries.insert(id, fields);
+    self.last_id = id;
+    self.entries_added += 1;
+    if let Some(trim) = trim {
+      self.trim(trim);
+    }
+    Ok(id)
+  }
+
+  /// Remove the oldest entries as asked by `trim`, returns how many were removed.
+  fn trim(&mut self, trim: &StreamTrim) -> usize {
+    let mut count = match trim.strategy {
+      TrimStrategy::MaxLen(max) => self.len().saturating_sub(max),
+      TrimStrategy::MinId(id) => self.entries.range(..id).count(),
+    };
+    if trim.approx {
+      let limit = trim.limit.unwrap_or(100 * STREAM_NODE_MAX_ENTRIES);
+      if limit > 0 {
+        count = count.min(limit);
+      }
+      count -= count % STREAM_NODE_MAX_ENTRIES;
+    } else if let Some(limit) = trim.limit.filter(|&limit| limit > 0) {
+      count = count.min(limit);
+    }
+    for _ in 0..count {
+      self.entries.pop_first();
+    }
+    count
+  }
+
+  /// The entries between `start` and `end` inclusive, newest first if `rev` is set.
+  pub fn range(
+    &self,
+    start: StreamId,
+    end: StreamId,
+    rev: bool,
+    count: Option<usize>,
+  ) -> Vec<(StreamId, Vec<RespFrame>)> {
+    if start > end {
+      return vec![];
+    }
+    let range = self.entries.range(start..=end);
+    let count = count.unwrap_or(usize::MAX);
+    let entry = |(id, fields): (&StreamId, &Vec<RespFrame>)| (*id, fields.clone());
+    match rev {
+      true => range.rev().take(count).map(entry).collect(),
+      false => range.take(count).map(entry).collect(),
+    }
+  }
+}
+
+impl Backend {
+  /// Run `f` on the stream at `key`. Unlike other types, an empty stream stays in the keyspace.
+  pub(super) fn with_stream<T>(
+    &self,
+    key: &str,
+    f: impl FnOnce(&mut Stream) -> T,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get_mut(key).as_deref_mut() {
+      Some(Value::Stream(stream)) => Ok(Some(f(stream))),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
+  }
+
+  pub(super) fn read_stream<T>(
+    &self,
+    key: &str,
+    f: impl FnOnce(&Stream) -> T,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::Stream(stream)) => Ok(Some(f(stream))),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
+  }
+
+  /// Append an entry, returns its ID or `None` if the key is missing and `nomkstream` is set.
+  pub fn xadd(
+    &self,
+    key: &str,
+    spec: StreamIdSpec,
+    fields: Vec<RespFrame>,
+    nomkstream: bool,
+    trim: Option<StreamTrim>,
+  ) -> Result<Option<StreamId>, CommandError> {
+    self.expire_if_needed(key);
+    let id = match self.keyspace.entry(key.to_string()) {
+      Entry::Occupied(mut entry) => {
+        let Value::Stream(stream) = entry.get_mut() else {
+          return Err(CommandError::WrongType);
+        };
+        stream.add(spec, fields, trim.as_ref())?
+      }
+      Entry::Vacant(_) if nomkstream => return Ok(None),
+      Entry::Vacant(entry) => {
+        let mut stream = Stream::default();
+        let id = stream.add(spec, fields, trim.as_ref())?;
+        entry.insert(Value::Stream(stream));
+        id
+      }
+    };
+    self.signal_ready(key);
+    Ok(Some(id))
+  }
+
+  pub fn xlen(&self, key: &str) -> Result<usize, CommandError> {
+    Ok(self.read_stream(key, Stream::len)?.unwrap_or_default())
+  }
+
+  pub fn xrange(
+    &self,
+    key: &str,
+    start: StreamId,
+    end: StreamId,
+    rev: bool,
+    count: Option<usize>,
+  ) -> Result<Vec<(StreamId, Vec<RespFrame>)>, CommandError> {
+    let entries = self.read_stream(key, |stream| stream.range(start, end, rev, count))?;
+    Ok(entries.unwrap_or_default())
+  }
+
+  /// The greatest ID ever added to the stream at `key`, 0-0 if it is missing.
+  pub fn xlast_id(&self, key: &str) -> Result<StreamId, CommandError> {
+    Ok(self.read_stream(key, Stream::last_id)?.unwrap_or_default())
+  }
+
+  /// The entries after `id`, as read by `XREAD`.
+  pub fn xread(
+    &self,
+    key: &str,
+    id: StreamId,
+    count: Option<usize>,
+  ) -> Result<Vec<(StreamId, Vec<RespFrame>)>, CommandError> {
+    match id.next() {
+      Some(start) => self.xrange(key, start, StreamId::MAX, false, count),
+      None => Ok(vec![]),
+    }
+  }
+
+  /// Delete entries by ID, returns how many existed.
+  pub fn xdel(&self, key: &str, ids: &[StreamId]) -> Result<usize, CommandError> {
+    let deleted = self.with_stream(key, |stream| {
+      let deleted = ids.iter().filter(|id| stream.entries.remove(id).is_some());
+      deleted.fold(0, |count, id| {
+        stream.max_deleted_id = stream.max_deleted_id.max(*id);
+        count + 1
+      })
+    })?;
+    Ok(deleted.unwrap_or_default())
+  }
+
+  pub fn xtrim(&self, key: &str, trim: StreamTrim) -> Result<usize, CommandError> {
+    Ok(self.with_stream(key, |stream| stream.trim(&trim))?.unwrap_or_default())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::BulkString;
+
+  fn fields() -> Vec<RespFrame> {
+    vec![BulkString::from("field").into(), BulkString::from("value").into()]
+  }
+
+  fn add(backend: &Backend, spec: StreamIdSpec) -> Result<Option<StreamId>, CommandError> {
+    backend.xadd("s", spec, fields(), false, None)
+  }
+
+  #[test]
+  fn test_stream_id_parse() -> Result<(), CommandError> {
+    assert_eq!(StreamId::parse("5-3", 0)?, StreamId::new(5, 3));
+    assert_eq!(StreamId::parse("5", u64::MAX)?, StreamId::new(5, u64::MAX));
+    assert_eq!(StreamId::parse("5-", 0), Err(CommandError::InvalidStreamId));
+    assert_eq!(StreamId::parse("+5", 0), Err(CommandError::InvalidStreamId));
+    assert_eq!(StreamId::new(5, u64::MAX).next(), Some(StreamId::new(6, 0)));
+    assert_eq!(StreamId::MAX.next(), None);
+    assert_eq!(StreamId::MIN.prev(), None);
+    Ok(())
+  }
+
+  #[test]
+  fn test_xadd_ids() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    let explicit = |ms, seq| StreamIdSpec::Explicit(StreamId::new(ms, seq));
+
+    assert_eq!(add(&backend, explicit(0, 0)), Err(CommandError::StreamIdZero));
+    assert!(!backend.exists("s"));
+    assert_eq!(add(&backend, StreamIdSpec::Partial(0))?, Some(StreamId::new(0, 1)));
+    assert_eq!(add(&backend, explicit(5, 1))?, Some(StreamId::new(5, 1)));
+    assert_eq!(add(&backend, explicit(5, 1)), Err(CommandError::StreamIdTooSmall));
+    assert_eq!(add(&backend, StreamIdSpec::Partial(5))?, Some(StreamId::new(5, 2)));
+    assert_eq!(add(&backend, StreamIdSpec::Partial(4)), Err(CommandError::StreamIdTooSmall));
+    assert_eq!(add(&backend, StreamIdSpec::Partial(7))?, Some(StreamId::new(7, 0)));
+
+    let id = add(&backend, StreamIdSpec::Auto)?.unwrap();
+    assert!(id.ms >= now_ms() as u64 - 1000 && id.seq == 0);
+    let far = StreamId::new(u64::MAX - 1, 0);
+    add(&backend, StreamIdSpec::Explicit(far))?;
+    assert_eq!(add(&backend, StreamIdSpec::Auto)?, Some(StreamId::new(u64::MAX - 1, 1)));
+    add(&backend, StreamIdSpec::Explicit(StreamId::MAX))?;
+    assert_eq!(add(&backend, StreamIdSpec::Auto), Err(CommandError::StreamExhausted));
+
+    assert_eq!(backend.xlen("s")?, 8);
+    assert_eq!(backend.xadd("other", StreamIdSpec::Auto, fields(), true, None)?, None);
+    assert!(!backend.exists("other"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_xrange_xdel() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    for seq in 1..=5 {
+      add(&backend, StreamIdSpec::Explicit(StreamId::new(1, seq)))?;
+    }
+
+    let ids = |entries: Vec<(StreamId, Vec<RespFrame>)>| {
+      entries.into_iter().map(|(id, _)| id.seq).collect::<Vec<_>>()
+    };
+    let all = backend.xrange("s", StreamId::MIN, StreamId::MAX, false, None)?;
+    assert_eq!(ids(all), vec![1, 2, 3, 4, 5]);
+    let rev = backend.xrange("s", StreamId::new(1, 2), StreamId::MAX, true, Some(2))?;
+    assert_eq!(ids(rev), vec![5, 4]);
+    assert!(backend.xrange("s", StreamId::new(2, 0), StreamId::new(1, 0), false, None)?.is_empty());
+
+    let deleted = [StreamId::new(1, 1), StreamId::new(1, 5), StreamId::new(9, 9)];
+    assert_eq!(backend.xdel("s", &deleted)?, 2);
+    assert_eq!(backend.xlen("s")?, 3);
+    // the last ID is kept, so a deleted top entry can't be added back
+    let top = StreamIdSpec::Explicit(StreamId::new(1, 5));
+    assert_eq!(add(&backend, top), Err(CommandError::StreamIdTooSmall));
+
+    backend.xdel("s", &[StreamId::new(1, 2), StreamId::new(1, 3), StreamId::new(1, 4)])?;
+    assert!(backend.exists("s"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_xtrim() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    for seq in 1..=250 {
+      add(&backend, StreamIdSpec::Explicit(StreamId::new(1, seq)))?;
+    }
+    let trim = |strategy, approx, limit| StreamTrim { strategy, approx, limit };
+
+    assert_eq!(backend.xtrim("s", trim(TrimStrategy::MaxLen(200), false, None))?, 50);
+    // approximate trimming only removes whole nodes of 100 entries
+    assert_eq!(backend.xtrim("s", trim(TrimStrategy::MaxLen(150), true, None))?, 0);
+    assert_eq!(backend.xtrim("s", trim(TrimStrategy::MaxLen(50), true, None))?, 100);
+    assert_eq!(backend.xtrim("s", trim(TrimStrategy::MaxLen(0), true, Some(99)))?, 0);
+
+    let minid = TrimStrategy::MinId(StreamId::new(1, 200));
+    assert_eq!(backend.xtrim("s", trim(minid, false, Some(10)))?, 10);
+    assert_eq!(backend.xtrim("s", trim(minid, false, None))?, 39);
+    assert_eq!(backend.xlen("s")?, 51);
+
+    let trimmed = Some(trim(TrimStrategy::MaxLen(1), false, None));
+    backend.xadd("s", StreamIdSpec::Auto, fields(), false, trimmed)?;
+    assert_eq!(backend.xlen("s")?, 1);
+    Ok(())
+  }
+}
@@ -0,0 +1,790 @@
+use super::scan::Entry;
+use super::{now_ms, Backend, Stream, StreamId, Value};
+use crate::{CommandError, RespFrame};
+use std::{
+  cmp::Ordering,
+  collections::{btree_map, BTreeMap, BTreeSet},
+  ops::Bound::{Excluded, Unbounded},
+};
+
+/// A consumer group of a stream, with the entries delivered to its consumers but not acknowledged.
+#[derive(Debug, Clone, Default)]
+pub struct ConsumerGroup {
+  last_delivered: StreamId,
+  // entries delivered so far, `None` when it has to be estimated from the stream
+  entries_read: Option<u64>,
+  pending: BTreeMap<StreamId, PendingEntry>,
+  consumers: BTreeMap<String, Consumer>,
+}
+
+/// An entry of the pending entries list of a group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingEntry {
+  pub consumer: String,
+  /// The last delivery in unix milliseconds.
+  pub delivered_at: i64,
+  pub delivery_count: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Consumer {
+  // last attempted interaction, and last one that read or claimed entries
+  seen_at: i64,
+  active_at: Option<i64>,
+  // the IDs of the group's pending entries owned by the consumer
+  pending: BTreeSet<StreamId>,
+}
+
+/// Where `XREADGROUP` reads from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupRead {
+  /// `>`, entries never delivered to the group.
+  New,
+  /// The pending entries of the consumer after the given ID.
+  Pending(StreamId),
+}
+
+/// The `IDLE | TIME`, `RETRYCOUNT`, `FORCE`, `JUSTID` and `LASTID` options of `XCLAIM`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimOptions {
+  /// The new delivery time in unix milliseconds, now if unset.
+  pub delivered_at: Option<i64>,
+  pub retry_count: Option<u64>,
+  pub force: bool,
+  pub just_id: bool,
+  pub last_id: Option<StreamId>,
+}
+
+/// The summary form of `XPENDING`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingSummary {
+  pub count: usize,
+  /// The lowest and highest pending IDs.
+  pub range: Option<(StreamId, StreamId)>,
+  pub consumers: Vec<(String, usize)>,
+}
+
+/// The reply of `XINFO STREAM`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamInfo {
+  pub length: usize,
+  pub last_generated_id: StreamId,
+  pub max_deleted_id: StreamId,
+  pub entries_added: u64,
+  pub recorded_first_id: StreamId,
+  pub groups: usize,
+  pub first_entry: Option<(StreamId, Vec<RespFrame>)>,
+  pub last_entry: Option<(StreamId, Vec<RespFrame>)>,
+}
+
+/// An element of the reply of `XINFO GROUPS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupInfo {
+  pub name: String,
+  pub consumers: usize,
+  pub pending: usize,
+  pub last_delivered: StreamId,
+  pub entries_read: Option<u64>,
+  /// Entries not yet delivered to the group, `None` when it can't be told.
+  pub lag: Option<u64>,
+}
+
+/// An element of the reply of `XINFO CONSUMERS`, times are in milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerInfo {
+  pub name: String,
+  pub pending: usize,
+  pub idle: i64,
+  /// -1 if the consumer never read or claimed an entry.
+  pub inactive: i64,
+}
+
+type Entries = Vec<(StreamId, Option<Vec<RespFrame>>)>;
+type Claimed = Vec<(StreamId, Vec<RespFrame>)>;
+
+impl ConsumerGroup {
+  /// Record an interaction of `consumer`, creating it if needed.
+  fn touch(&mut self, consumer: &str, now: i64, active: bool) {
+    let consumer = self.consumer(consumer, now);
+    consumer.seen_at = now;
+    if active {
+      consumer.active_at = Some(now);
+    }
+  }
+
+  fn consumer(&mut self, name: &str, now: i64) -> &mut Consumer {
+    let consumer = || Consumer { seen_at: now, active_at: None, pending: BTreeSet::new() };
+    self.consumers.entry(name.to_string()).or_insert_with(consumer)
+  }
+
+  /// Make `id` pending for `consumer`, taking it from its previous owner if it already was.
+  /// A new entry is delivered `now` and never yet counted as delivered.
+  fn assign(&mut self, id: StreamId, consumer: &str, now: i64) -> &mut PendingEntry {
+    self.consumer(consumer, now).pending.insert(id);
+    match self.pending.entry(id) {
+      btree_map::Entry::Occupied(entry) => {
+        let pending = entry.into_mut();
+        if pending.consumer != consumer {
+          if let Some(owner) = self.consumers.get_mut(&pending.consumer) {
+            owner.pending.remove(&id);
+          }
+          pending.consumer = consumer.to_string();
+        }
+        pending
+      }
+      btree_map::Entry::Vacant(entry) => entry.insert(PendingEntry {
+        consumer: consumer.to_string(),
+        delivered_at: now,
+        delivery_count: 0,
+      }),
+    }
+  }
+
+  /// Drop `id` from the pending entries, returns whether it was pending.
+  fn acknowledge(&mut self, id: &StreamId) -> bool {
+    let Some(pending) = self.pending.remove(id) else {
+      return false;
+    };
+    if let Some(owner) = self.consumers.get_mut(&pending.consumer) {
+      owner.pending.remove(id);
+    }
+    true
+  }
+}
+
+impl Stream {
+  fn first_id(&self) -> StreamId {
+    self.entries.keys().next().copied().unwrap_or_default()
+  }
+
+  /// Whether an entry deleted by `XDEL` lies between `start` and the last ID.
+  fn has_tombstones(&self, start: StreamId) -> bool {
+    !self.is_empty()
+      && self.max_deleted_id != StreamId::MIN
+      && start <= self.max_deleted_id
+      && self.max_deleted_id <= self.last_id
+  }
+
+  /// How many entries were ever added up to `id`, when that can be told from the stream alone.
+  fn entries_up_to(&self, id: StreamId) -> Option<u64> {
+    if self.entries_added == 0 {
+      return Some(0);
+    }
+    if self.is_empty() && id <= self.max_deleted_id {
+      return Some(self.entries_added);
+    }
+    match id.cmp(&self.last_id) {
+      Ordering::Equal => return Some(self.entries_added),
+      Ordering::Greater => return None,
+      Ordering::Less => {}
+    }
+    let first = self.first_id();
+    if self.max_deleted_id == StreamId::MIN || self.max_deleted_id < first {
+      let before_first = self.entries_added - self.len() as u64;
+      match id.cmp(&first) {
+        Ordering::Less => return Some(before_first),
+        Ordering::Equal => return Some(before_first + 1),
+        Ordering::Greater => {}
+      }
+    }
+    None
+  }
+
+  /// The read counter of a group after it is delivered `id`.
+  fn entries_read_after(&self, entries_read: Option<u64>, id: StreamId) -> Option<u64> {
+    match entries_read {
+      Some(read) if !self.has_tombstones(id) => Some(read + 1),
+      _ => self.entries_up_to(id),
+    }
+  }
+
+  fn lag(&self, group: &ConsumerGroup) -> Option<u64> {
+    if self.entries_added == 0 {
+      return Some(0);
+    }
+    let read = match group.entries_read {
+      Some(read) if !self.has_tombstones(group.last_delivered) => Some(read),
+      _ => self.entries_up_to(group.last_delivered),
+    };
+    read.map(|read| self.entries_added.saturating_sub(read))
+  }
+
+  fn group(&self, key: &str, group: &str) -> Result<&ConsumerGroup, CommandError> {
+    self.groups.get(group).ok_or_else(|| CommandError::NoGroup(key.to_string(), group.to_string()))
+  }
+
+  fn group_mut(&mut self, key: &str, group: &str) -> Result<&mut ConsumerGroup, CommandError> {
+    let err = || CommandError::NoGroup(key.to_string(), group.to_string());
+    self.groups.get_mut(group).ok_or_else(err)
+  }
+
+  fn read_group(
+    &mut self,
+    name: &str,
+    consumer: &str,
+    read: GroupRead,
+    count: Option<usize>,
+    noack: bool,
+  ) -> Entries {
+    let now = now_ms();
+    let count = count.unwrap_or(usize::MAX);
+    // taken out of the map so the stream can be read while the group is updated
+    let Some(mut group) = self.groups.remove(name) else {
+      return vec![];
+    };
+
+    let entries = match read {
+      GroupRead::New => {
+        let entries = self
+          .entries
+          .range((Excluded(group.last_delivered), Unbounded))
+          .take(count)
+          .map(|(id, fields)| (*id, Some(fields.clone())))
+          .collect::<Entries>();
+        group.touch(consumer, now, !entries.is_empty());
+        for (id, _) in &entries {
+          group.last_delivered = *id;
+          group.entries_read = self.entries_read_after(group.entries_read, *id);
+          if !noack {
+            let pending = group.assign(*id, consumer, now);
+            pending.delivered_at = now;
+            pending.delivery_count = 1;
+          }
+        }
+        entries
+      }
+      GroupRead::Pending(start) => {
+        group.touch(consumer, now, false);
+        let owned = &group.consumers[consumer].pending;
+        owned
+          .range((Excluded(start), Unbounded))
+          .take(count)
+          .filter_map(|&id| {
+            let pending = group.pending.get_mut(&id)?;
+            pending.delivered_at = now;
+            pending.delivery_count += 1;
+            Some((id, self.entries.get(&id).cloned()))
+          })
+          .collect()
+      }
+    };
+    self.groups.insert(name.to_string(), group);
+    entries
+  }
+}
+
+impl Backend {
+  /// The stream at `key`, which `XGROUP` requires to exist.
+  fn with_group_stream<T>(
+    &self,
+    key: &str,
+    f: impl FnOnce(&mut Stream) -> Result<T, CommandError>,
+  ) -> Result<T, CommandError> {
+    self.with_stream(key, f)?.unwrap_or(Err(CommandError::StreamKeyMissing))
+  }
+
+  /// Create a group delivering entries after `id`, or after the last one if `id` is `None`.
+  pub fn xgroup_create(
+    &self,
+    key: &str,
+    group: &str,
+    id: Option<StreamId>,
+    mkstream: bool,
+    entries_read: Option<u64>,
+  ) -> Result<(), CommandError> {
+    self.expire_if_needed(key);
+    let mut entry = match self.keyspace.entry(key.to_string()) {
+      Entry::Occupied(entry) => entry,
+      Entry::Vacant(_) if !mkstream => return Err(CommandError::StreamKeyMissing),
+      Entry::Vacant(entry) => entry.insert_entry(Value::Stream(Stream::default())),
+    };
+    let Value::Stream(stream) = entry.get_mut() else {
+      return Err(CommandError::WrongType);
+    };
+    if stream.groups.contains_key(group) {
+      return Err(CommandError::BusyGroup);
+    }
+    let last_delivered = id.unwrap_or(stream.last_id);
+    let group_state = ConsumerGroup { last_delivered, entries_read, ..Default::default() };
+    stream.groups.insert(group.to_string(), group_state);
+    Ok(())
+  }
+
+  pub fn xgroup_setid(
+    &self,
+    key: &str,
+    group: &str,
+    id: Option<StreamId>,
+    entries_read: Option<u64>,
+  ) -> Result<(), CommandError> {
+    self.with_group_stream(key, |stream| {
+      let last_id = stream.last_id;
+      let group = stream.group_mut(key, group)?;
+      group.last_delivered = id.unwrap_or(last_id);
+      group.entries_read = entries_read;
+      Ok(())
+    })
+  }
+
+  pub fn xgroup_destroy(&self, key: &str, group: &str) -> Result<bool, CommandError> {
+    self.with_group_stream(key, |stream| Ok(stream.groups.remove(group).is_some()))
+  }
+
+  /// Returns whether the consumer was created.
+  pub fn xgroup_createconsumer(
+    &self,
+    key: &str,
+    group: &str,
+    consumer: &str,
+  ) -> Result<bool, CommandError> {
+    self.with_group_stream(key, |stream| {
+      let group = stream.group_mut(key, group)?;
+      if group.consumers.contains_key(consumer) {
+        return Ok(false);
+      }
+      group.touch(consumer, now_ms(), false);
+      Ok(true)
+    })
+  }
+
+  /// Delete a consumer along with its pending entries, returns how many it had.
+  pub fn xgroup_delconsumer(
+    &self,
+    key: &str,
+    group: &str,
+    consumer: &str,
+  ) -> Result<usize, CommandError> {
+    self.with_group_stream(key, |stream| {
+      let group = stream.group_mut(key, group)?;
+      let Some(consumer) = group.consumers.remove(consumer) else {
+        return Ok(0);
+      };
+      for id in &consumer.pending {
+        group.pending.remove(id);
+      }
+      Ok(consumer.pending.len())
+    })
+  }
+
+  /// Read for `consumer` from several streams. Streams without new entries are left out of the
+  /// reply, while reading history always lists the stream.
+  pub fn xreadgroup(
+    &self,
+    group: &str,
+    consumer: &str,
+    streams: &[(String, GroupRead)],
+    count: Option<usize>,
+    noack: bool,
+  ) -> Result<Vec<(String, Entries)>, CommandError> {
+    // fail before delivering anything
+    for (key, _) in streams {
+      let found = self.read_stream(key, |stream| stream.groups.contains_key(group))?;
+      if found != Some(true) {
+        return Err(CommandError::NoGroup(key.clone(), group.to_string()));
+      }
+    }
+
+    let mut ret = vec![];
+    for (key, read) in streams {
+      let entries = self
+        .with_stream(key, |stream| stream.read_group(group, consumer, *read, count, noack))?
+        .unwrap_or_default();
+      if !entries.is_empty() || *read != GroupRead::New {
+        ret.push((key.clone(), entries));
+      }
+    }
+    Ok(ret)
+  }
+
+  /// Acknowledge entries, returns how many were pending.
+  pub fn xack(&self, key: &str, group: &str, ids: &[StreamId]) -> Result<usize, CommandError> {
+    let acked = self.with_stream(key, |stream| match stream.groups.get_mut(group) {
+      Some(group) => ids.iter().filter(|id| group.acknowledge(id)).count(),
+      None => 0,
+    })?;
+    Ok(acked.unwrap_or_default())
+  }
+
+  fn read_group<T>(
+    &self,
+    key: &str,
+    group: &str,
+    f: impl FnOnce(&ConsumerGroup) -> T,
+  ) -> Result<T, CommandError> {
+    let no_group = || CommandError::NoGroup(key.to_string(), group.to_string());
+    self
+      .read_stream(key, |stream| stream.group(key, group).map(f))?
+      .unwrap_or_else(|| Err(no_group()))
+  }
+
+  pub fn xpending_summary(&self, key: &str, group: &str) -> Result<PendingSummary, CommandError> {
+    self.read_group(key, group, |group| {
+      let first = group.pending.first_key_value().map(|(id, _)| *id);
+      let last = group.pending.last_key_value().map(|(id, _)| *id);
+      let consumers = group.consumers.iter().filter(|(_, consumer)| !consumer.pending.is_empty());
+      PendingSummary {
+        count: group.pending.len(),
+        range: first.zip(last),
+        consumers: consumers
+          .map(|(name, consumer)| (name.clone(), consumer.pending.len()))
+          .collect(),
+      }
+    })
+  }
+
+  /// The pending entries between `start` and `end`, optionally only those idle for `min_idle`
+  /// milliseconds or owned by `consumer`.
+  pub fn xpending(
+    &self,
+    key: &str,
+    group: &str,
+    min_idle: i64,
+    (start, end): (StreamId, StreamId),
+    count: usize,
+    consumer: Option<&str>,
+  ) -> Result<Vec<(StreamId, PendingEntry)>, CommandError> {
+    let now = now_ms();
+    self.read_group(key, group, |group| {
+      if start > end {
+        return vec![];
+      }
+      let ids: Box<dyn Iterator<Item = &StreamId>> = match consumer {
+        Some(consumer) => match group.consumers.get(consumer) {
+          Some(consumer) => Box::new(consumer.pending.range(start..=end)),
+          None => return vec![],
+        },
+        None => Box::new(group.pending.range(start..=end).map(|(id, _)| id)),
+      };
+      ids
+        .filter_map(|id| group.pending.get_key_value(id))
+        .filter(|(_, p)| now - p.delivered_at >= min_idle)
+        .take(count)
+        .map(|(id, p)| (*id, p.clone()))
+        .collect()
+    })
+  }
+
+  /// Give `consumer` the entries among `ids` pending for at least `min_idle` milliseconds.
+  /// Entries deleted from the stream are dropped from the pending list instead.
+  pub fn xclaim(
+    &self,
+    key: &str,
+    group: &str,
+    consumer: &str,
+    min_idle: i64,
+    ids: &[StreamId],
+    opts: &ClaimOptions,
+  ) -> Result<Claimed, CommandError> {
+    let now = now_ms();
+    let delivered_at = opts.delivered_at.unwrap_or(now).min(now);
+    let claimed = self.with_stream(key, |stream| {
+      let Stream { entries, groups, .. } = stream;
+      let no_group = || CommandError::NoGroup(key.to_string(), group.to_string());
+      let group = groups.get_mut(group).ok_or_else(no_group)?;
+      if let Some(last_id) = opts.last_id {
+        group.last_delivered = group.last_delivered.max(last_id);
+      }
+
+      let mut claimed = vec![];
+      for id in ids {
+        let Some(fields) = entries.get(id) else {
+          group.acknowledge(id);
+          continue;
+        };
+        match group.pending.get(id) {
+          Some(pending) if now - pending.delivered_at < min_idle => continue,
+          Some(_) => {}
+          None if opts.force => {}
+          None => continue,
+        }
+        let pending = group.assign(*id, consumer, now);
+        pending.delivered_at = delivered_at;
+        match opts.retry_count {
+          Some(count) => pending.delivery_count = count,
+          None if !opts.just_id => pending.delivery_count += 1,
+          None => {}
+        }
+        claimed.push((*id, fields.clone()));
+      }
+      group.touch(consumer, now, !claimed.is_empty());
+      Ok(claimed)
+    })?;
+    claimed.unwrap_or_else(|| Err(CommandError::NoGroup(key.to_string(), group.to_string())))
+  }
+
+  /// Scan the pending list from `start` and claim up to `count` entries idle for `min_idle`
+  /// milliseconds. Returns the ID to resume from, 0-0 once the list is scanned, the claimed
+  /// entries and the IDs of deleted entries dropped on the way.
+  pub fn xautoclaim(
+    &self,
+    key: &str,
+    group: &str,
+    consumer: &str,
+    min_idle: i64,
+    (start, count): (StreamId, usize),
+    just_id: bool,
+  ) -> Result<(StreamId, Claimed, Vec<StreamId>), CommandError> {
+    let now = now_ms();
+    let ret = self.with_stream(key, |stream| {
+      let Stream { entries, groups, .. } = stream;
+      let no_group = || CommandError::NoGroup(key.to_string(), group.to_string());
+      let group = groups.get_mut(group).ok_or_else(no_group)?;
+      // like redis, look at no more than ten times the entries asked for
+      let attempts = count.saturating_mul(10);
+      let ids = group.pending.range(start..).take(attempts + 1).map(|(id, _)| *id);
+      let ids = ids.collect::<Vec<_>>();
+
+      let mut claimed = vec![];
+      let mut deleted = vec![];
+      let mut next = StreamId::MIN;
+      for (i, id) in ids.into_iter().enumerate() {
+        if i == attempts || claimed.len() == count {
+          next = id;
+          break;
+        }
+        let Some(fields) = entries.get(&id) else {
+          group.acknowledge(&id);
+          deleted.push(id);
+          continue;
+        };
+        match group.pending.get(&id) {
+          Some(pending) if now - pending.delivered_at >= min_idle => {}
+          _ => continue,
+        }
+        let pending = group.assign(id, consumer, now);
+        pending.delivered_at = now;
+        if !just_id {
+          pending.delivery_count += 1;
+        }
+        claimed.push((id, fields.clone()));
+      }
+      group.touch(consumer, now, !claimed.is_empty());
+      Ok((next, claimed, deleted))
+    })?;
+    ret.unwrap_or_else(|| Err(CommandError::NoGroup(key.to_string(), group.to_string())))
+  }
+
+  pub fn xinfo_stream(&self, key: &str) -> Result<StreamInfo, CommandError> {
+    let info = self.read_stream(key, |stream| {
+      let entry = |(id, fields): (&StreamId, &Vec<RespFrame>)| (*id, fields.clone());
+      StreamInfo {
+        length: stream.len(),
+        last_generated_id: stream.last_id,
+        max_deleted_id: stream.max_deleted_id,
+        entries_added: stream.entries_added,
+        recorded_first_id: stream.first_id(),
+        groups: stream.groups.len(),
+        first_entry: stream.entries.first_key_value().map(entry),
+        last_entry: stream.entries.last_key_value().map(entry),
+      }
+    })?;
+    info.ok_or(CommandError::NoSuchKey)
+  }
+
+  pub fn xinfo_groups(&self, key: &str) -> Result<Vec<GroupInfo>, CommandError> {
+    let groups = self.read_stream(key, |stream| {
+      stream
+        .groups
+        .iter()
+        .map(|(name, group)| GroupInfo {
+          name: name.clone(),
+          consumers: group.consumers.len(),
+          pending: group.pending.len(),
+          last_delivered: group.last_delivered,
+          entries_read: group.entries_read,
+          lag: stream.lag(group),
+        })
+        .collect()
+    })?;
+    groups.ok_or(CommandError::NoSuchKey)
+  }
+
+  pub fn xinfo_consumers(&self, key: &str, group: &str) -> Result<Vec<ConsumerInfo>, CommandError> {
+    let now = now_ms();
+    self.read_group(key, group, |group| {
+      group
+        .consumers
+        .iter()
+        .map(|(name, consumer)| ConsumerInfo {
+          name: name.clone(),
+          pending: consumer.pending.len(),
+          idle: now - consumer.seen_at,
+          inactive: consumer.active_at.map_or(-1, |at| now - at),
+        })
+        .collect()
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, StreamIdSpec};
+
+  fn setup(backend: &Backend, n: u64) -> Result<(), CommandError> {
+    for seq in 1..=n {
+      let fields = vec![BulkString::from("n").into(), RespFrame::Integer(seq as i64)];
+      let id = StreamIdSpec::Explicit(StreamId::new(1, seq));
+      backend.xadd("s", id, fields, false, None)?;
+    }
+    backend.xgroup_create("s", "g", Some(StreamId::MIN), false, None)
+  }
+
+  fn ids(entries: &[(StreamId, impl Sized)]) -> Vec<u64> {
+    entries.iter().map(|(id, _)| id.seq).collect()
+  }
+
+  fn read(backend: &Backend, consumer: &str, read: GroupRead, count: usize) -> Vec<u64> {
+    let streams = [("s".to_string(), read)];
+    let ret = backend.xreadgroup("g", consumer, &streams, Some(count), false).unwrap();
+    ret.first().map(|(_, entries)| ids(entries)).unwrap_or_default()
+  }
+
+  #[test]
+  fn test_xgroup() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    let create = |mkstream| backend.xgroup_create("s", "g", None, mkstream, None);
+    assert_eq!(create(false), Err(CommandError::StreamKeyMissing));
+    create(true)?;
+    assert_eq!(create(true), Err(CommandError::BusyGroup));
+    assert_eq!(backend.xlen("s")?, 0);
+
+    assert!(backend.xgroup_createconsumer("s", "g", "alice")?);
+    assert!(!backend.xgroup_createconsumer("s", "g", "alice")?);
+    let no_group = CommandError::NoGroup("s".to_string(), "x".to_string());
+    assert_eq!(backend.xgroup_createconsumer("s", "x", "alice"), Err(no_group));
+    assert!(backend.xgroup_destroy("s", "g")?);
+    assert!(!backend.xgroup_destroy("s", "g")?);
+    Ok(())
+  }
+
+  #[test]
+  fn test_xreadgroup_xack() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    setup(&backend, 5)?;
+
+    assert_eq!(read(&backend, "alice", GroupRead::New, 2), vec![1, 2]);
+    assert_eq!(read(&backend, "bob", GroupRead::New, 2), vec![3, 4]);
+    assert_eq!(read(&backend, "alice", GroupRead::Pending(StreamId::MIN), 10), vec![1, 2]);
+    assert_eq!(read(&backend, "alice", GroupRead::Pending(StreamId::new(1, 1)), 10), vec![2]);
+
+    assert_eq!(backend.xack("s", "g", &[StreamId::new(1, 1), StreamId::new(1, 5)])?, 1);
+    backend.xdel("s", &[StreamId::new(1, 2)])?;
+    let streams = [("s".to_string(), GroupRead::Pending(StreamId::MIN))];
+    let ret = backend.xreadgroup("g", "alice", &streams, None, false)?;
+    assert_eq!(ret, vec![("s".to_string(), vec![(StreamId::new(1, 2), None)])]);
+
+    let summary = backend.xpending_summary("s", "g")?;
+    assert_eq!(summary.count, 3);
+    assert_eq!(summary.range, Some((StreamId::new(1, 2), StreamId::new(1, 4))));
+    assert_eq!(summary.consumers, vec![("alice".to_string(), 1), ("bob".to_string(), 2)]);
+    let range = (StreamId::MIN, StreamId::MAX);
+    let pending = backend.xpending("s", "g", 0, range, 10, Some("bob"))?;
+    assert_eq!(ids(&pending), vec![3, 4]);
+    assert_eq!(pending[0].1.delivery_count, 1);
+
+    // a missing group fails before anything is delivered
+    backend.xadd("t", StreamIdSpec::Auto, vec![], false, None)?;
+    let streams = [("s".to_string(), GroupRead::New), ("t".to_string(), GroupRead::New)];
+    let no_group = CommandError::NoGroup("t".to_string(), "g".to_string());
+    assert_eq!(backend.xreadgroup("g", "alice", &streams, None, false), Err(no_group));
+    assert_eq!(read(&backend, "alice", GroupRead::New, 10), vec![5]);
+    assert_eq!(read(&backend, "alice", GroupRead::New, 10), Vec::<u64>::new());
+    Ok(())
+  }
+
+  #[test]
+  fn test_pending_moves_between_consumers() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    setup(&backend, 4)?;
+    assert_eq!(read(&backend, "alice", GroupRead::New, 3), vec![1, 2, 3]);
+    // delivering the entries again hands them over to the new reader
+    backend.xgroup_setid("s", "g", Some(StreamId::new(1, 1)), None)?;
+    assert_eq!(read(&backend, "bob", GroupRead::New, 2), vec![2, 3]);
+
+    assert_eq!(read(&backend, "alice", GroupRead::Pending(StreamId::MIN), 10), vec![1]);
+    assert_eq!(read(&backend, "bob", GroupRead::Pending(StreamId::MIN), 10), vec![2, 3]);
+    let summary = backend.xpending_summary("s", "g")?;
+    assert_eq!(summary.consumers, vec![("alice".to_string(), 1), ("bob".to_string(), 2)]);
+
+    backend.xack("s", "g", &[StreamId::new(1, 2)])?;
+    let range = (StreamId::MIN, StreamId::MAX);
+    assert_eq!(ids(&backend.xpending("s", "g", 0, range, 10, Some("bob"))?), vec![3]);
+    assert!(backend.xpending("s", "g", 0, range, 10, Some("carol"))?.is_empty());
+    assert_eq!(backend.xgroup_delconsumer("s", "g", "bob")?, 1);
+    assert_eq!(ids(&backend.xpending("s", "g", 0, range, 10, None)?), vec![1]);
+    let consumers = backend.xinfo_consumers("s", "g")?;
+    let pending = consumers.iter().map(|c| (c.name.as_str(), c.pending)).collect::<Vec<_>>();
+    assert_eq!(pending, vec![("alice", 1)]);
+    Ok(())
+  }
+
+  #[test]
+  fn test_xclaim_xautoclaim() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    setup(&backend, 5)?;
+    read(&backend, "alice", GroupRead::New, 5);
+
+    let ids = [StreamId::new(1, 1), StreamId::new(1, 2)];
+    let opts = ClaimOptions::default();
+    assert!(backend.xclaim("s", "g", "bob", 60_000, &ids, &opts)?.is_empty());
+    let claimed = backend.xclaim("s", "g", "bob", 0, &ids, &opts)?;
+    assert_eq!(super::tests::ids(&claimed), vec![1, 2]);
+    let pending = backend.xpending("s", "g", 0, (StreamId::MIN, StreamId::MAX), 10, Some("bob"))?;
+    assert_eq!(pending[0].1.delivery_count, 2);
+
+    backend.xack("s", "g", &ids)?;
+    let opts = ClaimOptions { force: true, retry_count: Some(7), ..Default::default() };
+    let claimed = backend.xclaim("s", "g", "carol", 0, &ids[..1], &opts)?;
+    assert_eq!(super::tests::ids(&claimed), vec![1]);
+    let pending = backend.xpending("s", "g", 0, (StreamId::MIN, StreamId::MAX), 1, None)?;
+    assert_eq!(
+      pending[0].1,
+      PendingEntry { consumer: "carol".to_string(), ..pending[0].1.clone() }
+    );
+    assert_eq!(pending[0].1.delivery_count, 7);
+
+    backend.xdel("s", &[StreamId::new(1, 4)])?;
+    let (next, claimed, deleted) =
+      backend.xautoclaim("s", "g", "dave", 0, (StreamId::MIN, 2), false)?;
+    assert_eq!(next, StreamId::new(1, 4));
+    assert_eq!(super::tests::ids(&claimed), vec![1, 3]);
+    assert!(deleted.is_empty());
+    let (next, claimed, deleted) = backend.xautoclaim("s", "g", "dave", 0, (next, 2), true)?;
+    assert_eq!(next, StreamId::MIN);
+    assert_eq!((super::tests::ids(&claimed), deleted), (vec![5], vec![StreamId::new(1, 4)]));
+    let (_, _, deleted) =
+      backend.xautoclaim("s", "g", "dave", 0, (StreamId::new(1, 4), 2), true)?;
+    assert!(deleted.is_empty());
+
+    let consumers = backend.xinfo_consumers("s", "g")?;
+    let pending = consumers.iter().map(|c| (c.name.as_str(), c.pending)).collect::<Vec<_>>();
+    assert_eq!(pending, vec![("alice", 0), ("bob", 0), ("carol", 0), ("dave", 3)]);
+    Ok(())
+  }
+
+  #[test]
+  fn test_xinfo_lag() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    setup(&backend, 5)?;
+    let group = || backend.xinfo_groups("s").map(|groups| groups[0].clone());
+
+    assert_eq!(group()?.lag, Some(5));
+    assert_eq!(group()?.entries_read, None);
+    read(&backend, "alice", GroupRead::New, 2);
+    assert_eq!((group()?.entries_read, group()?.lag), (Some(2), Some(3)));
+
+    // a deleted entry after the last delivered one makes the lag unknown
+    backend.xdel("s", &[StreamId::new(1, 4)])?;
+    assert_eq!(group()?.lag, None);
+    read(&backend, "alice", GroupRead::New, 10);
+    assert_eq!((group()?.entries_read, group()?.lag), (Some(5), Some(0)));
+
+    let info = backend.xinfo_stream("s")?;
+    assert_eq!((info.length, info.entries_added, info.groups), (4, 5, 1));
+    assert_eq!(info.max_deleted_id, StreamId::new(1, 4));
+    assert_eq!(info.first_entry.map(|(id, _)| id), Some(StreamId::new(1, 1)));
+    assert_eq!(backend.xinfo_stream("missing"), Err(CommandError::NoSuchKey));
+    Ok(())
+  }
+}
@@ -0,0 +1,409 @@
+use super::scan::Entry;
+use super::{list::normalize_range, now_ms, Backend, SetTtl, Value};
+use crate::{BulkString, CommandError, RespEncode, RespFrame};
+
+// the largest string SETRANGE may grow a value to, redis' default proto-max-bulk-len
+pub(super) const MAX_STRING_LEN: usize = 512 * 1024 * 1024;
+
+/// A common substring found by `LCS`, as inclusive byte ranges into both strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LcsMatch {
+  pub a: (usize, usize),
+  pub b: (usize, usize),
+}
+
+impl LcsMatch {
+  pub fn match_len(&self) -> usize {
+    self.a.1 - self.a.0 + 1
+  }
+}
+
+/// The bytes of a string value. `SET` always stores bulk strings, other frames are rendered
+/// the way a client would have sent them.
+pub(crate) fn string_bytes(frame: &RespFrame) -> Vec<u8> {
+  match frame {
+    RespFrame::BulkString(s) => s.0.clone().unwrap_or_default(),
+    RespFrame::SimpleString(s) => s.0.clone().into_bytes(),
+    RespFrame::Integer(n) => n.to_string().into_bytes(),
+    frame => frame.clone().encode(),
+  }
+}
+
+/// Parse `bytes` as a 64-bit integer written the canonical way, so `+1`, `01` or ` 1` are not.
+pub(super) fn parse_i64(bytes: &[u8]) -> Option<i64> {
+  let s = std::str::from_utf8(bytes).ok()?;
+  s.parse().ok().filter(|n: &i64| n.to_string() == s)
+}
+
+/// Render a float the way `INCRBYFLOAT` replies with it: no exponent and no trailing zeros.
+///
+/// Like redis, the value is rounded to a fixed number of significant digits so that 1.1 + 2.2
+/// reads 3.3. Redis prints 17 digits of a long double, a double only has `f64::DIGITS` exact
+/// ones: the 17th would show its rounding error.
+pub(crate) fn format_float(n: f64) -> String {
+  if n == 0.0 || !n.is_finite() {
+    // -0 reads as 0
+    return format!("{}", n + 0.0);
+  }
+  let exp = n.abs().log10().floor() as i32;
+  let decimals = (f64::DIGITS as i32 - 1 - exp).max(0) as usize;
+  let s = format!("{:.*}", decimals, n);
+  let s = if s.contains('.') { s.trim_end_matches('0').trim_end_matches('.') } else { &s };
+  match s {
+    // a negative value rounded to zero
+    "-0" => "0".to_string(),
+    s => s.to_string(),
+  }
+}
+
+impl Backend {
+  /// Replace the string at `key` by what `f` makes of its current bytes, `None` if missing,
+  /// and return the rest of what `f` returns. The ttl of the key is kept.
+  pub(super) fn update_string<T>(
+    &self,
+    key: &str,
+    f: impl FnOnce(Option<&[u8]>) -> Result<(Vec<u8>, T), CommandError>,
+  ) -> Result<T, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.entry(key.to_string()) {
+      Entry::Occupied(mut entry) => {
+        let Value::String(frame) = entry.get() else {
+          return Err(CommandError::WrongType);
+        };
+        let (bytes, ret) = f(Some(&string_bytes(frame)))?;
+        entry.insert(Value::String(BulkString::new(bytes).into()));
+        Ok(ret)
+      }
+      Entry::Vacant(entry) => {
+        let (bytes, ret) = f(None)?;
+        entry.insert(Value::String(BulkString::new(bytes).into()));
+        Ok(ret)
+      }
+    }
+  }
+
+  /// The bytes of the string at `key`, `None` if missing.
+  pub(super) fn read_string(&self, key: &str) -> Result<Option<Vec<u8>>, CommandError> {
+    Ok(self.get(key)?.as_ref().map(string_bytes))
+  }
+
+  /// Append `value` to the string at `key`, returns the new length.
+  pub fn append(&self, key: &str, value: &[u8]) -> Result<usize, CommandError> {
+    self.update_string(key, |old| {
+      let mut bytes = old.map(<[u8]>::to_vec).unwrap_or_default();
+      bytes.extend_from_slice(value);
+      let len = bytes.len();
+      Ok((bytes, len))
+    })
+  }
+
+  pub fn strlen(&self, key: &str) -> Result<usize, CommandError> {
+    Ok(self.read_string(key)?.map_or(0, |bytes| bytes.len()))
+  }
+
+  /// The bytes from `start` to `end` inclusive, negative offsets counting from the end.
+  pub fn getrange(&self, key: &str, start: i64, end: i64) -> Result<Vec<u8>, CommandError> {
+    let bytes = self.read_string(key)?.unwrap_or_default();
+    Ok(match normalize_range(start, end, bytes.len()) {
+      Some((start, end)) => bytes[start..end].to_vec(),
+      None => Vec::new(),
+    })
+  }
+
+  /// Overwrite the string at `key` from `offset` on, padding it with zero bytes if it is
+  /// shorter. Returns the new length, an empty `value` leaves the key untouched.
+  pub fn setrange(&self, key: &str, offset: usize, value: &[u8]) -> Result<usize, CommandError> {
+    if value.is_empty() {
+      return self.strlen(key);
+    }
+    let end =
+      offset.checked_add(value.len()).filter(|end| *end <= MAX_STRING_LEN).ok_or_else(|| {
+        CommandError::InvalidArgument(
+          "string exceeds maximum allowed size (proto-max-bulk-len)".to_string(),
+        )
+      })?;
+    self.update_string(key, |old| {
+      let mut bytes = old.map(<[u8]>::to_vec).unwrap_or_default();
+      if bytes.len() < end {
+        bytes.resize(end, 0);
+      }
+      bytes[offset..end].copy_from_slice(value);
+      let len = bytes.len();
+      Ok((bytes, len))
+    })
+  }
+
+  /// Remove the string at `key` and return it.
+  pub fn getdel(&self, key: &str) -> Result<Option<RespFrame>, CommandError> {
+    self.expire_if_needed(key);
+    let Entry::Occupied(entry) = self.keyspace.entry(key.to_string()) else {
+      return Ok(None);
+    };
+    if !matches!(entry.get(), Value::String(_)) {
+      return Err(CommandError::WrongType);
+    }
+    self.expires.remove(key);
+    match entry.remove() {
+      Value::String(value) => Ok(Some(value)),
+      _ => unreachable!("checked to be a string above"),
+    }
+  }
+
+  /// Return the string at `key` and update its ttl, a deadline in the past deletes it.
+  pub fn getex(&self, key: &str, ttl: SetTtl) -> Result<Option<RespFrame>, CommandError> {
+    self.expire_if_needed(key);
+    let Entry::Occupied(entry) = self.keyspace.entry(key.to_string()) else {
+      return Ok(None);
+    };
+    let Value::String(value) = entry.get() else {
+      return Err(CommandError::WrongType);
+    };
+    let value = value.clone();
+    match ttl {
+      SetTtl::Keep => {}
+      SetTtl::Discard => {
+        self.expires.remove(key);
+      }
+      SetTtl::At(at) if at <= now_ms() => {
+        self.expires.remove(key);
+        entry.remove();
+      }
+      SetTtl::At(at) => {
+        self.expires.insert(key.to_string(), at);
+      }
+    }
+    Ok(Some(value))
+  }
+
+  /// The longest common subsequence of the strings at `key1` and `key2`, missing keys being
+  /// empty strings, along with the matching ranges from the end of the strings backwards.
+  pub fn lcs(&self, key1: &str, key2: &str) -> Result<(Vec<u8>, Vec<LcsMatch>), CommandError> {
+    let a = self.read_string(key1)?.unwrap_or_default();
+    let b = self.read_string(key2)?.unwrap_or_default();
+    let cells = (a.len() + 1).checked_mul(b.len() + 1).filter(|n| *n <= MAX_STRING_LEN / 4);
+    if cells.is_none() {
+      return Err(CommandError::InvalidArgument(
+        "Insufficient memory, transient memory for LCS exceeds proto-max-bulk-len".to_string(),
+      ));
+    }
+    Ok(lcs(&a, &b))
+  }
+
+  /// Add `delta` to the integer stored at `key`, missing keys counting as 0.
+  pub fn incr_by(&self, key: &str, delta: i64) -> Result<i64, CommandError> {
+    self.update_string(key, |old| {
+      let n = match old {
+        Some(old) => parse_i64(old).ok_or(CommandError::NotInteger)?,
+        None => 0,
+      };
+      let n = n.checked_add(delta).ok_or(CommandError::NotInteger)?;
+      Ok((n.to_string().into_bytes(), n))
+    })
+  }
+
+  /// Add `delta` to the float stored at `key`, missing keys counting as 0. Returns the new
+  /// value as it is stored.
+  pub fn incr_by_float(&self, key: &str, delta: f64) -> Result<String, CommandError> {
+    self.update_string(key, |old| {
+      let n = match old {
+        Some(old) => std::str::from_utf8(old)
+          .ok()
+          .and_then(|s| s.parse::<f64>().ok())
+          .filter(|n| n.is_finite())
+          .ok_or(CommandError::NotFloat)?,
+        None => 0.0,
+      };
+      let n = n + delta;
+      if !n.is_finite() {
+        return Err(CommandError::InvalidArgument(
+          "increment would produce NaN or Infinity".to_string(),
+        ));
+      }
+      let s = format_float(n);
+      Ok((s.clone().into_bytes(), s))
+    })
+  }
+}
+
+fn lcs(a: &[u8], b: &[u8]) -> (Vec<u8>, Vec<LcsMatch>) {
+  // table[i][j] is the length of the lcs of a[..i] and b[..j]
+  let width = b.len() + 1;
+  let mut table = vec![0u32; (a.len() + 1) * width];
+  for i in 1..=a.len() {
+    for j in 1..=b.len() {
+      table[i * width + j] = if a[i - 1] == b[j - 1] {
+        table[(i - 1) * width + j - 1] + 1
+      } else {
+        table[(i - 1) * width + j].max(table[i * width + j - 1])
+      };
+    }
+  }
+
+  // walk back from the end, growing the current match while both strings stay contiguous
+  let mut sequence = Vec::with_capacity(table[a.len() * width + b.len()] as usize);
+  let mut matches = Vec::new();
+  let mut current: Option<LcsMatch> = None;
+  let (mut i, mut j) = (a.len(), b.len());
+  while i > 0 && j > 0 {
+    if a[i - 1] == b[j - 1] {
+      sequence.push(a[i - 1]);
+      match &mut current {
+        Some(m) if m.a.0 == i && m.b.0 == j => {
+          m.a.0 -= 1;
+          m.b.0 -= 1;
+        }
+        Some(m) => {
+          matches.push(*m);
+          current = Some(LcsMatch { a: (i - 1, i - 1), b: (j - 1, j - 1) });
+        }
+        None => current = Some(LcsMatch { a: (i - 1, i - 1), b: (j - 1, j - 1) }),
+      }
+      i -= 1;
+      j -= 1;
+    } else {
+      if table[(i - 1) * width + j] > table[i * width + j - 1] {
+        i -= 1;
+      } else {
+        j -= 1;
+      }
+      matches.extend(current.take());
+    }
+  }
+  matches.extend(current);
+  sequence.reverse();
+  (sequence, matches)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{ExpireCondition, SimpleString};
+
+  #[test]
+  fn test_incr_by() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    assert_eq!(backend.incr_by("n", 5)?, 5);
+    assert_eq!(backend.incr_by("n", -7)?, -2);
+    assert_eq!(backend.get("n")?, Some(BulkString::from("-2").into()));
+
+    backend.set("n".to_string(), BulkString::from(i64::MAX.to_string()).into());
+    assert_eq!(backend.incr_by("n", 1), Err(CommandError::NotInteger));
+    for bad in ["+1", "01", " 1", "1.0", "abc", ""] {
+      backend.set("n".to_string(), BulkString::from(bad).into());
+      assert_eq!(backend.incr_by("n", 1), Err(CommandError::NotInteger), "{:?}", bad);
+    }
+
+    backend.set("n".to_string(), SimpleString::new("10").into());
+    assert_eq!(backend.incr_by("n", 1)?, 11);
+
+    backend.sadd("set", "a")?;
+    assert_eq!(backend.incr_by("set", 1), Err(CommandError::WrongType));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_incr_keeps_ttl() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.incr_by("n", 1)?;
+    let at = crate::now_ms() + 10_000;
+    backend.expire_at("n", at, ExpireCondition::default());
+    backend.incr_by("n", 1)?;
+    assert_eq!(backend.expire_time("n"), Some(Some(at)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_incr_by_float() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.set("f".to_string(), BulkString::from("10.50").into());
+    assert_eq!(backend.incr_by_float("f", 0.1)?, "10.6");
+    assert_eq!(backend.incr_by_float("f", -5.6)?, "5");
+    assert_eq!(backend.incr_by_float("missing", 3.0e3)?, "3000");
+    assert!(backend.incr_by_float("f", f64::INFINITY).is_err());
+
+    backend.set("f".to_string(), BulkString::from("1.1").into());
+    assert_eq!(backend.incr_by_float("f", 2.2)?, "3.3");
+    assert_eq!(backend.incr_by_float("f", -3.3)?, "0");
+
+    backend.set("f".to_string(), BulkString::from("inf").into());
+    assert_eq!(backend.incr_by_float("f", 1.0), Err(CommandError::NotFloat));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_format_float() {
+    assert_eq!(format_float(0.1 + 0.2), "0.3");
+    assert_eq!(format_float(-0.0), "0");
+    assert_eq!(format_float(-1e-20), "-0.00000000000000000001");
+    assert_eq!(format_float(1.5e20), "150000000000000000000");
+    assert_eq!(format_float(-2.5), "-2.5");
+    assert_eq!(format_float(9.9999999999999999), "10");
+    assert_eq!(format_float(123456.789), "123456.789");
+  }
+
+  #[test]
+  fn test_append_and_ranges() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    assert_eq!(backend.append("s", b"Hello")?, 5);
+    assert_eq!(backend.append("s", b" World")?, 11);
+    assert_eq!(backend.strlen("s")?, 11);
+    assert_eq!(backend.strlen("missing")?, 0);
+
+    assert_eq!(backend.getrange("s", 0, 4)?, b"Hello");
+    assert_eq!(backend.getrange("s", -3, -1)?, b"rld");
+    assert_eq!(backend.getrange("s", 0, -1)?, b"Hello World");
+    assert_eq!(backend.getrange("s", 10, 100)?, b"d");
+    assert_eq!(backend.getrange("s", 5, 3)?, b"");
+    assert_eq!(backend.getrange("missing", 0, -1)?, b"");
+
+    assert_eq!(backend.setrange("s", 6, b"Redis")?, 11);
+    assert_eq!(backend.get("s")?, Some(BulkString::from("Hello Redis").into()));
+    assert_eq!(backend.setrange("padded", 3, b"x")?, 4);
+    assert_eq!(backend.get("padded")?, Some(BulkString::new(b"\0\0\0x".to_vec()).into()));
+    assert_eq!(backend.setrange("empty", 3, b"")?, 0);
+    assert!(!backend.exists("empty"));
+    assert!(backend.setrange("s", MAX_STRING_LEN, b"x").is_err());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_getdel_and_getex() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.set("s".to_string(), BulkString::from("v").into());
+    assert_eq!(
+      backend.getex("s", SetTtl::At(now_ms() + 10_000))?,
+      Some(BulkString::from("v").into())
+    );
+    assert!(matches!(backend.expire_time("s"), Some(Some(_))));
+    assert_eq!(backend.getex("s", SetTtl::Discard)?, Some(BulkString::from("v").into()));
+    assert_eq!(backend.expire_time("s"), Some(None));
+    assert_eq!(backend.getex("s", SetTtl::At(1))?, Some(BulkString::from("v").into()));
+    assert!(!backend.exists("s"));
+
+    backend.set("s".to_string(), BulkString::from("v").into());
+    assert_eq!(backend.getdel("s")?, Some(BulkString::from("v").into()));
+    assert_eq!(backend.getdel("s")?, None);
+
+    backend.sadd("set", "a")?;
+    assert_eq!(backend.getdel("set"), Err(CommandError::WrongType));
+    assert_eq!(backend.getex("set", SetTtl::Keep), Err(CommandError::WrongType));
+    assert!(backend.exists("set"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lcs() {
+    let (sequence, matches) = lcs(b"ohmytext", b"mynewtext");
+    assert_eq!(sequence, b"mytext");
+    assert_eq!(matches, [LcsMatch { a: (4, 7), b: (5, 8) }, LcsMatch { a: (2, 3), b: (0, 1) }]);
+    assert_eq!(matches[0].match_len(), 4);
+
+    assert_eq!(lcs(b"", b"abc"), (Vec::new(), Vec::new()));
+    assert_eq!(lcs(b"abc", b"xyz"), (Vec::new(), Vec::new()));
+  }
+}
@@ -0,0 +1,41 @@
+use super::{Scannable, SortedSet, Stream};
+use crate::RespFrame;
+use dashmap::{DashMap, DashSet};
+use std::collections::VecDeque;
+
+/// A value stored in the keyspace, tagged with its redis type.
+#[derive(Debug, Clone)]
+pub enum Value {
+  String(RespFrame),
+  Hash(Scannable<DashMap<String, RespFrame>>),
+  Set(Scannable<DashSet<String>>),
+  List(VecDeque<RespFrame>),
+  ZSet(SortedSet),
+  Stream(Stream),
+}
+
+impl Value {
+  /// The name reported by the `TYPE` command.
+  pub fn type_name(&self) -> &'static str {
+    match self {
+      Value::String(_) => "string",
+      Value::Hash(_) => "hash",
+      Value::Set(_) => "set",
+      Value::List(_) => "list",
+      Value::ZSet(_) => "zset",
+      Value::Stream(_) => "stream",
+    }
+  }
+
+  /// Roughly how much work it takes to free the value.
+  pub fn free_effort(&self) -> usize {
+    match self {
+      Value::String(_) => 1,
+      Value::Hash(hmap) => hmap.len(),
+      Value::Set(set) => set.len(),
+      Value::List(list) => list.len(),
+      Value::ZSet(zset) => zset.len(),
+      Value::Stream(stream) => stream.len(),
+    }
+  }
+}
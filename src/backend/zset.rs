@@ -0,0 +1,627 @@
+use super::scan::Entry;
+use super::{list::normalize_range, skiplist::SkipList, Backend, Value};
+use crate::CommandError;
+use std::{
+  cmp::Ordering,
+  collections::HashMap,
+  ops::{Bound, RangeBounds},
+};
+
+/// A score ordered by `f64::total_cmp`, scores are never NaN.
+#[derive(Debug, Clone, Copy)]
+struct Score(f64);
+
+impl PartialEq for Score {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Score {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.0.total_cmp(&other.0)
+  }
+}
+
+/// The members of a sorted set ordered by score then member, plus a member -> score index.
+#[derive(Debug, Clone, Default)]
+pub struct SortedSet {
+  scores: HashMap<String, f64>,
+  ordered: SkipList<(Score, String)>,
+}
+
+/// What a `ZRANGE` selects, intervals are given from the lowest to the highest element.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZRangeBy {
+  Rank(i64, i64),
+  Score(Bound<f64>, Bound<f64>),
+  Lex(Bound<String>, Bound<String>),
+}
+
+/// How `ZUNION`, `ZINTER` and `ZDIFF` combine their inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZSetOp {
+  Union,
+  Inter,
+  Diff,
+}
+
+/// The `AGGREGATE` option, how the scores of a member found in several inputs are combined.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+  #[default]
+  Sum,
+  Min,
+  Max,
+}
+
+impl Aggregate {
+  fn apply(self, a: f64, b: f64) -> f64 {
+    match self {
+      // inf + -inf is NaN, which redis turns into 0
+      Aggregate::Sum => zero_if_nan(a + b),
+      Aggregate::Min => a.min(b),
+      Aggregate::Max => a.max(b),
+    }
+  }
+}
+
+fn zero_if_nan(score: f64) -> f64 {
+  if score.is_nan() {
+    0.0
+  } else {
+    score
+  }
+}
+
+/// The `NX | XX`, `GT | LT`, `CH` and `INCR` options of `ZADD`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ZAddOptions {
+  pub nx: bool,
+  pub xx: bool,
+  pub gt: bool,
+  pub lt: bool,
+  pub ch: bool,
+  pub incr: bool,
+}
+
+impl SortedSet {
+  pub fn len(&self) -> usize {
+    self.scores.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.scores.is_empty()
+  }
+
+  pub fn score(&self, member: &str) -> Option<f64> {
+    self.scores.get(member).copied()
+  }
+
+  /// Set the score of `member`, returning the previous one.
+  pub fn insert(&mut self, member: String, score: f64) -> Option<f64> {
+    // -0.0 and 0.0 are the same score
+    let score = score + 0.0;
+    let old = self.scores.insert(member.clone(), score);
+    if let Some(old) = old {
+      self.ordered.remove(&(Score(old), member.clone()));
+    }
+    self.ordered.insert((Score(score), member));
+    old
+  }
+
+  pub fn remove(&mut self, member: &str) -> Option<f64> {
+    let (member, score) = self.scores.remove_entry(member)?;
+    self.ordered.remove(&(Score(score), member));
+    Some(score)
+  }
+
+  /// The 0-based position of `member` in ascending order, or descending if `rev` is set.
+  pub fn rank(&self, member: &str, rev: bool) -> Option<usize> {
+    let score = self.score(member)?;
+    let key = (Score(score), member.to_string());
+    let rank = self.ordered.position(|item| *item < key);
+    Some(if rev { self.len() - 1 - rank } else { rank })
+  }
+
+  /// Members and scores in ascending order.
+  pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, f64)> + DoubleEndedIterator {
+    self.ordered.iter().map(|(score, member)| (member.as_str(), score.0))
+  }
+
+  /// The ascending positions `start..end` of the elements with a score within `min` and `max`.
+  fn score_ranks(&self, min: Bound<f64>, max: Bound<f64>) -> (usize, usize) {
+    // compared as f64 rather than by `Score`, so a -0 bound is the same as 0
+    let start = match min {
+      Bound::Included(min) => self.ordered.position(|(score, _)| score.0 < min),
+      Bound::Excluded(min) => self.ordered.position(|(score, _)| score.0 <= min),
+      Bound::Unbounded => 0,
+    };
+    let end = match max {
+      Bound::Included(max) => self.ordered.position(|(score, _)| score.0 <= max),
+      Bound::Excluded(max) => self.ordered.position(|(score, _)| score.0 < max),
+      Bound::Unbounded => self.len(),
+    };
+    (start, end.max(start))
+  }
+
+  /// The ascending positions `start..end` of the elements with a member within `min` and
+  /// `max`. Like redis this expects every score to be the same, `None` if they are not.
+  fn lex_ranks(&self, min: &Bound<String>, max: &Bound<String>) -> Option<(usize, usize)> {
+    if self.ordered.first().map(|(score, _)| score) != self.ordered.last().map(|(score, _)| score) {
+      return None;
+    }
+    let start = match min {
+      Bound::Included(min) => self.ordered.position(|(_, member)| member < min),
+      Bound::Excluded(min) => self.ordered.position(|(_, member)| member <= min),
+      Bound::Unbounded => 0,
+    };
+    let end = match max {
+      Bound::Included(max) => self.ordered.position(|(_, member)| member <= max),
+      Bound::Excluded(max) => self.ordered.position(|(_, member)| member < max),
+      Bound::Unbounded => self.len(),
+    };
+    Some((start, end.max(start)))
+  }
+
+  /// How many elements have a score within `min` and `max`.
+  pub fn count(&self, min: Bound<f64>, max: Bound<f64>) -> usize {
+    let (start, end) = self.score_ranks(min, max);
+    end - start
+  }
+
+  /// The elements selected by `by`, in descending order if `rev` is set. `limit` is an
+  /// `offset count` pair applied after ordering, a negative count meaning all the rest.
+  pub fn range(&self, by: &ZRangeBy, rev: bool, limit: Option<(i64, i64)>) -> Vec<(&str, f64)> {
+    let (offset, count) = match limit {
+      Some((offset, _)) if offset < 0 => return vec![],
+      Some((offset, count)) if count < 0 => (offset as usize, usize::MAX),
+      Some((offset, count)) => (offset as usize, count as usize),
+      None => (0, usize::MAX),
+    };
+    let (start, end) = match by {
+      ZRangeBy::Rank(start, stop) => match normalize_range(*start, *stop, self.len()) {
+        // ranks count from the highest score with REV
+        Some((start, end)) if rev => (self.len() - end, self.len() - start),
+        Some(range) => range,
+        None => return vec![],
+      },
+      ZRangeBy::Score(min, max) => self.score_ranks(*min, *max),
+      ZRangeBy::Lex(min, max) => match self.lex_ranks(min, max) {
+        Some(range) => range,
+        None => {
+          // scores differ, so the members are not in order: check every one of them
+          let range = (min.as_ref().map(String::as_str), max.as_ref().map(String::as_str));
+          let items =
+            self.iter().filter(|(member, _)| RangeBounds::<str>::contains(&range, *member));
+          let items: Vec<_> = if rev { items.rev().collect() } else { items.collect() };
+          return items.into_iter().skip(offset).take(count).collect();
+        }
+      },
+    };
+
+    // apply LIMIT to the positions, counting from the end with REV
+    let offset = offset.min(end - start);
+    let count = count.min(end - start - offset);
+    let (start, end) = if rev {
+      (end - offset - count, end - offset)
+    } else {
+      (start + offset, start + offset + count)
+    };
+    let items = self.ordered.range(start, end).map(|(score, member)| (member.as_str(), score.0));
+    if rev {
+      items.rev().collect()
+    } else {
+      items.collect()
+    }
+  }
+}
+
+impl Backend {
+  /// Run `f` against the sorted set at `key`, created empty if missing unless `create` is
+  /// false; the key is removed if `f` leaves the set empty. Returns `None` if there is no set.
+  fn with_zset<T>(
+    &self,
+    key: &str,
+    create: bool,
+    f: impl FnOnce(&mut SortedSet) -> T,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    let mut entry = match self.keyspace.entry(key.to_string()) {
+      Entry::Occupied(entry) => entry,
+      Entry::Vacant(_) if !create => return Ok(None),
+      Entry::Vacant(entry) => entry.insert_entry(Value::ZSet(SortedSet::default())),
+    };
+    let Value::ZSet(zset) = entry.get_mut() else {
+      return Err(CommandError::WrongType);
+    };
+    let ret = f(zset);
+    if zset.is_empty() {
+      entry.remove();
+      self.expires.remove(key);
+    }
+    Ok(Some(ret))
+  }
+
+  fn read_zset<T>(
+    &self,
+    key: &str,
+    f: impl FnOnce(&SortedSet) -> T,
+  ) -> Result<Option<T>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::ZSet(zset)) => Ok(Some(f(zset))),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(None),
+    }
+  }
+
+  /// Add or update `(score, member)` pairs under `opts`. Returns the number of added members,
+  /// plus updated ones with `CH`, and the score of the last member if it was written, which
+  /// is what `INCR` replies with.
+  pub fn zadd(
+    &self,
+    key: &str,
+    pairs: Vec<(f64, String)>,
+    opts: ZAddOptions,
+  ) -> Result<(usize, Option<f64>), CommandError> {
+    let ret = self.with_zset(key, !opts.xx, |zset| {
+      let (mut added, mut changed, mut last) = (0, 0, None);
+      for (score, member) in pairs {
+        last = None;
+        let new = match zset.score(&member) {
+          None if opts.xx => continue,
+          None => score,
+          Some(_) if opts.nx => continue,
+          Some(old) => {
+            let new = if opts.incr { old + score } else { score };
+            if new.is_nan() {
+              return Err(CommandError::NotANumber);
+            }
+            if (opts.gt && new <= old) || (opts.lt && new >= old) {
+              continue;
+            }
+            new
+          }
+        };
+        match zset.insert(member, new) {
+          None => added += 1,
+          Some(old) if old != new => changed += 1,
+          Some(_) => {}
+        }
+        last = Some(new);
+      }
+      Ok((added, changed, last))
+    })?;
+    let (added, changed, last) = ret.unwrap_or(Ok((0, 0, None)))?;
+    if added > 0 {
+      self.signal_ready(key);
+    }
+    Ok((if opts.ch { added + changed } else { added }, last))
+  }
+
+  /// Pop up to `count` members with the lowest scores, or the highest if `max` is set.
+  /// Returns `None` if the key does not exist.
+  pub fn zpop(
+    &self,
+    key: &str,
+    max: bool,
+    count: usize,
+  ) -> Result<Option<Vec<(String, f64)>>, CommandError> {
+    self.with_zset(key, false, |zset| {
+      let mut items = Vec::with_capacity(count.min(zset.len()));
+      while items.len() < count {
+        let first = if max { zset.ordered.last() } else { zset.ordered.first() };
+        let Some((_, member)) = first.cloned() else {
+          break;
+        };
+        let score = zset.remove(&member).unwrap_or_default();
+        items.push((member, score));
+      }
+      items
+    })
+  }
+
+  /// Remove `members`, returns how many of them were in the set.
+  pub fn zrem(&self, key: &str, members: &[String]) -> Result<usize, CommandError> {
+    let ret = self.with_zset(key, false, |zset| {
+      members.iter().filter(|member| zset.remove(member).is_some()).count()
+    })?;
+    Ok(ret.unwrap_or(0))
+  }
+
+  pub fn zcard(&self, key: &str) -> Result<usize, CommandError> {
+    Ok(self.read_zset(key, |zset| zset.len())?.unwrap_or(0))
+  }
+
+  pub fn zscore(&self, key: &str, member: &str) -> Result<Option<f64>, CommandError> {
+    Ok(self.read_zset(key, |zset| zset.score(member))?.flatten())
+  }
+
+  pub fn zmscore(&self, key: &str, members: &[String]) -> Result<Vec<Option<f64>>, CommandError> {
+    let ret = self.read_zset(key, |zset| members.iter().map(|m| zset.score(m)).collect())?;
+    Ok(ret.unwrap_or_else(|| vec![None; members.len()]))
+  }
+
+  /// The rank of `member` along with its score.
+  pub fn zrank(
+    &self,
+    key: &str,
+    member: &str,
+    rev: bool,
+  ) -> Result<Option<(usize, f64)>, CommandError> {
+    let ret = self.read_zset(key, |zset| Some((zset.rank(member, rev)?, zset.score(member)?)))?;
+    Ok(ret.flatten())
+  }
+
+  pub fn zcount(&self, key: &str, min: Bound<f64>, max: Bound<f64>) -> Result<usize, CommandError> {
+    let ret = self.read_zset(key, |zset| zset.count(min, max))?;
+    Ok(ret.unwrap_or(0))
+  }
+
+  /// The members and scores of the sorted set at `key`, for a plain set every score is 1.
+  fn zset_source(&self, key: &str) -> Result<HashMap<String, f64>, CommandError> {
+    self.expire_if_needed(key);
+    match self.keyspace.get(key).as_deref() {
+      Some(Value::ZSet(zset)) => Ok(zset.scores.clone()),
+      Some(Value::Set(set)) => Ok(set.iter().map(|member| (member.clone(), 1.0)).collect()),
+      Some(_) => Err(CommandError::WrongType),
+      None => Ok(HashMap::new()),
+    }
+  }
+
+  /// Combine the sets at `keys` with `op`, the score of each input being multiplied by its
+  /// weight (1 if missing) except for `ZDIFF`. Returns the result in ascending order.
+  pub fn zcombine(
+    &self,
+    op: ZSetOp,
+    keys: &[String],
+    weights: &[f64],
+    aggregate: Aggregate,
+  ) -> Result<Vec<(String, f64)>, CommandError> {
+    let sources = keys.iter().map(|key| self.zset_source(key)).collect::<Result<Vec<_>, _>>()?;
+    let weight = |i: usize, score: f64| zero_if_nan(score * weights.get(i).copied().unwrap_or(1.0));
+
+    let mut sources = sources.into_iter().enumerate();
+    let Some((_, first)) = sources.next() else {
+      return Ok(vec![]);
+    };
+    let mut acc: HashMap<String, f64> = match op {
+      ZSetOp::Diff => first,
+      _ => first.into_iter().map(|(member, score)| (member, weight(0, score))).collect(),
+    };
+    for (i, source) in sources {
+      match op {
+        ZSetOp::Union => {
+          for (member, score) in source {
+            let score = weight(i, score);
+            acc
+              .entry(member)
+              .and_modify(|acc| *acc = aggregate.apply(*acc, score))
+              .or_insert(score);
+          }
+        }
+        ZSetOp::Inter => acc.retain(|member, acc| match source.get(member) {
+          Some(score) => {
+            *acc = aggregate.apply(*acc, weight(i, *score));
+            true
+          }
+          None => false,
+        }),
+        ZSetOp::Diff => acc.retain(|member, _| !source.contains_key(member)),
+      }
+    }
+
+    let mut items: Vec<_> = acc.into_iter().collect();
+    items.sort_by(|(m1, s1), (m2, s2)| s1.total_cmp(s2).then_with(|| m1.cmp(m2)));
+    Ok(items)
+  }
+
+  /// Replace `dst` with a sorted set of `items`, or delete it if there are none.
+  /// Returns the size of the new set.
+  pub fn zstore(&self, dst: &str, items: Vec<(String, f64)>) -> usize {
+    let mut zset = SortedSet::default();
+    for (member, score) in items {
+      zset.insert(member, score);
+    }
+    let len = zset.len();
+    self.remove(dst);
+    if len > 0 {
+      self.keyspace.insert(dst.to_string(), Value::ZSet(zset));
+      self.signal_ready(dst);
+    }
+    len
+  }
+
+  /// Remove the elements selected by `by`, returns how many were removed.
+  pub fn zremrange(&self, key: &str, by: &ZRangeBy) -> Result<usize, CommandError> {
+    let ret = self.with_zset(key, false, |zset| {
+      let members: Vec<String> =
+        zset.range(by, false, None).into_iter().map(|(member, _)| member.to_string()).collect();
+      for member in &members {
+        zset.remove(member);
+      }
+      members.len()
+    })?;
+    Ok(ret.unwrap_or(0))
+  }
+
+  pub fn zrange(
+    &self,
+    key: &str,
+    by: &ZRangeBy,
+    rev: bool,
+    limit: Option<(i64, i64)>,
+  ) -> Result<Vec<(String, f64)>, CommandError> {
+    let ret = self.read_zset(key, |zset| {
+      let items = zset.range(by, rev, limit);
+      items.into_iter().map(|(member, score)| (member.to_string(), score)).collect()
+    })?;
+    Ok(ret.unwrap_or_default())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespFrame;
+
+  fn pairs(items: &[(f64, &str)]) -> Vec<(f64, String)> {
+    items.iter().map(|(score, member)| (*score, member.to_string())).collect()
+  }
+
+  fn owned(items: &[(&str, f64)]) -> Vec<(String, f64)> {
+    items.iter().map(|(member, score)| (member.to_string(), *score)).collect()
+  }
+
+  #[test]
+  fn test_sorted_set_order_and_rank() {
+    let mut zset = SortedSet::default();
+    zset.insert("b".to_string(), 1.0);
+    zset.insert("a".to_string(), 1.0);
+    zset.insert("c".to_string(), -0.0);
+    assert_eq!(zset.insert("c".to_string(), 2.0), Some(0.0));
+
+    assert_eq!(zset.iter().collect::<Vec<_>>(), vec![("a", 1.0), ("b", 1.0), ("c", 2.0)]);
+    assert_eq!(zset.rank("b", false), Some(1));
+    assert_eq!(zset.rank("c", true), Some(0));
+    assert_eq!(zset.remove("a"), Some(1.0));
+    assert_eq!(zset.rank("b", false), Some(0));
+  }
+
+  #[test]
+  fn test_sorted_set_range_bounds() {
+    use Bound::{Excluded, Included, Unbounded};
+    let mut zset = SortedSet::default();
+    let items = [("f", f64::NEG_INFINITY), ("g", -0.0), ("a", 1.0), ("b", 2.0), ("c", 2.0)];
+    for (member, score) in items.into_iter().chain([("d", 3.0), ("e", f64::INFINITY)]) {
+      zset.insert(member.to_string(), score);
+    }
+    let members = |by: ZRangeBy, rev: bool| -> String {
+      zset.range(&by, rev, None).into_iter().map(|(member, _)| member).collect()
+    };
+
+    assert_eq!(members(ZRangeBy::Score(Excluded(2.0), Included(3.0)), false), "d");
+    assert_eq!(members(ZRangeBy::Score(Included(2.0), Excluded(2.0)), false), "");
+    assert_eq!(members(ZRangeBy::Score(Included(5.0), Included(1.0)), false), "");
+    assert_eq!(members(ZRangeBy::Score(Excluded(f64::INFINITY), Unbounded), false), "");
+    assert_eq!(members(ZRangeBy::Score(Included(f64::INFINITY), Unbounded), false), "e");
+    assert_eq!(members(ZRangeBy::Score(Excluded(f64::NEG_INFINITY), Excluded(1.0)), true), "g");
+    assert_eq!(members(ZRangeBy::Score(Included(-0.0), Included(-0.0)), false), "g");
+    assert_eq!(members(ZRangeBy::Score(Unbounded, Excluded(f64::INFINITY)), true), "dcbagf");
+
+    for (i, member) in ["f", "g", "a", "b", "c", "d", "e"].into_iter().enumerate() {
+      assert_eq!(zset.rank(member, false), Some(i));
+      assert_eq!(zset.rank(member, true), Some(6 - i));
+    }
+
+    let mut zset = SortedSet::default();
+    for member in ["a", "b", "c"] {
+      zset.insert(member.to_string(), 0.0);
+    }
+    let members = |min: Bound<&str>, max: Bound<&str>, rev: bool| -> String {
+      let by = ZRangeBy::Lex(min.map(str::to_string), max.map(str::to_string));
+      zset.range(&by, rev, None).into_iter().map(|(member, _)| member).collect()
+    };
+    assert_eq!(members(Excluded("a"), Unbounded, true), "cb");
+    assert_eq!(members(Excluded("a"), Excluded("a"), false), "");
+    assert_eq!(members(Included("c"), Included("a"), false), "");
+    assert_eq!(members(Unbounded, Included("b"), false), "ab");
+  }
+
+  #[test]
+  fn test_zadd_options() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    let opts = ZAddOptions::default();
+    assert_eq!(backend.zadd("z", pairs(&[(1.0, "a"), (2.0, "b")]), opts)?, (2, Some(2.0)));
+
+    let xx = ZAddOptions { xx: true, ch: true, ..opts };
+    assert_eq!(backend.zadd("z", pairs(&[(5.0, "a"), (1.0, "c")]), xx)?, (1, None));
+    assert_eq!(backend.zscore("z", "c")?, None);
+
+    let gt = ZAddOptions { gt: true, ch: true, ..opts };
+    assert_eq!(backend.zadd("z", pairs(&[(1.0, "a"), (3.0, "b")]), gt)?, (1, Some(3.0)));
+    assert_eq!(backend.zscore("z", "a")?, Some(5.0));
+
+    let incr = ZAddOptions { incr: true, ..opts };
+    assert_eq!(backend.zadd("z", pairs(&[(2.5, "a")]), incr)?, (0, Some(7.5)));
+    assert_eq!(
+      backend.zadd("z", pairs(&[(f64::INFINITY, "inf")]), incr)?,
+      (1, Some(f64::INFINITY))
+    );
+    let ret = backend.zadd("z", pairs(&[(f64::NEG_INFINITY, "inf")]), incr);
+    assert_eq!(ret, Err(CommandError::NotANumber));
+
+    // XX never creates the key
+    assert_eq!(backend.zadd("none", pairs(&[(1.0, "a")]), xx)?, (0, None));
+    assert_eq!(backend.type_of("none"), None);
+
+    backend.set("str".to_string(), RespFrame::Integer(1));
+    assert_eq!(backend.zadd("str", pairs(&[(1.0, "a")]), opts), Err(CommandError::WrongType));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zrange() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    let items = pairs(&[(1.0, "a"), (2.0, "b"), (2.0, "c"), (3.0, "d")]);
+    backend.zadd("z", items, ZAddOptions::default())?;
+
+    let by = ZRangeBy::Rank(0, -2);
+    assert_eq!(backend.zrange("z", &by, true, None)?, owned(&[("d", 3.0), ("c", 2.0), ("b", 2.0)]));
+
+    let by = ZRangeBy::Score(Bound::Excluded(1.0), Bound::Unbounded);
+    assert_eq!(backend.zrange("z", &by, false, Some((1, 5)))?, owned(&[("c", 2.0), ("d", 3.0)]));
+    assert_eq!(backend.zrange("z", &by, true, Some((0, 1)))?, owned(&[("d", 3.0)]));
+
+    let by = ZRangeBy::Lex(Bound::Included("b".to_string()), Bound::Excluded("d".to_string()));
+    assert_eq!(backend.zrange("z", &by, false, None)?, owned(&[("b", 2.0), ("c", 2.0)]));
+
+    assert_eq!(backend.zcount("z", Bound::Included(2.0), Bound::Included(3.0))?, 3);
+    assert_eq!(backend.zrank("z", "c", false)?, Some((2, 2.0)));
+    assert_eq!(backend.zrem("z", &["a".to_string(), "x".to_string()])?, 1);
+    assert_eq!(backend.zmscore("z", &["a".to_string(), "d".to_string()])?, vec![None, Some(3.0)]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zcombine_and_zstore() -> Result<(), CommandError> {
+    let backend = Backend::new();
+    backend.zadd("z1", pairs(&[(1.0, "a"), (2.0, "b")]), ZAddOptions::default())?;
+    backend.zadd("z2", pairs(&[(1.0, "a"), (2.0, "b"), (3.0, "c")]), ZAddOptions::default())?;
+    backend.sadd("s", "c")?;
+    let keys = ["z1", "z2", "s"].map(String::from);
+
+    let ret = backend.zcombine(ZSetOp::Union, &keys, &[2.0, 3.0, 1.0], Aggregate::Sum)?;
+    assert_eq!(ret, owned(&[("a", 5.0), ("b", 10.0), ("c", 10.0)]));
+    let ret = backend.zcombine(ZSetOp::Inter, &keys[..2], &[], Aggregate::Max)?;
+    assert_eq!(ret, owned(&[("a", 1.0), ("b", 2.0)]));
+    let ret =
+      backend.zcombine(ZSetOp::Diff, &["z2".to_string(), "z1".to_string()], &[], Aggregate::Sum)?;
+    assert_eq!(ret, owned(&[("c", 3.0)]));
+
+    assert_eq!(backend.zstore("dst", ret), 1);
+    assert_eq!(backend.zpop("dst", true, 5)?, Some(owned(&[("c", 3.0)])));
+    assert_eq!(backend.zpop("dst", true, 5)?, None);
+    assert_eq!(backend.zstore("dst", owned(&[("c", 3.0)])), 1);
+    assert_eq!(backend.zscore("dst", "c")?, Some(3.0));
+    assert_eq!(backend.zstore("dst", vec![]), 0);
+    assert_eq!(backend.type_of("dst"), None);
+
+    let by = ZRangeBy::Score(Bound::Included(2.0), Bound::Unbounded);
+    assert_eq!(backend.zremrange("z2", &by)?, 2);
+    assert_eq!(backend.zcard("z2")?, 1);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,72 @@
+use super::{extract_args, parse_bytes, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// APPEND key value
+///  APPEND mykey "Hello"
+/// (integer) 5
+///  APPEND mykey " World"
+/// (integer) 11
+///  GET mykey
+/// "Hello World"
+#[derive(Debug)]
+pub struct Append {
+  pub(crate) key: String,
+  pub(crate) value: Vec<u8>,
+}
+
+impl CommandExecutor for Append {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.append(&self.key, &self.value) {
+      Ok(len) => RespFrame::Integer(len as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for Append {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+      (Some(key), Some(value)) => {
+        Ok(Append { key: parse_string(key)?, value: parse_bytes(value)? })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key or value".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_append_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$6\r\nAPPEND\r\n$5\r\nmykey\r\n$5\r\nHello\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Append = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.value, b"Hello");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_append_execute() {
+    let backend = Backend::new();
+    let append = |value: &str| Append { key: "mykey".to_string(), value: value.into() };
+
+    assert_eq!(append("Hello").execute(&backend), RespFrame::Integer(5));
+    assert_eq!(append(" World").execute(&backend), RespFrame::Integer(11));
+    assert_eq!(backend.get("mykey"), Ok(Some(BulkString::from("Hello World").into())));
+
+    backend.sadd("myset", "a").unwrap();
+    let ret = Append { key: "myset".to_string(), value: b"x".to_vec() }.execute(&backend);
+    assert_eq!(ret, CommandError::WrongType.into());
+  }
+}
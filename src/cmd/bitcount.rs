@@ -0,0 +1,94 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BitUnit, RespArray, RespFrame};
+
+/// BITCOUNT key [start end [BYTE | BIT]]
+///  SET mykey "foobar"
+/// "OK"
+///  BITCOUNT mykey
+/// (integer) 26
+///  BITCOUNT mykey 1 1
+/// (integer) 6
+///  BITCOUNT mykey 5 30 BIT
+/// (integer) 17
+#[derive(Debug)]
+pub struct BitCount {
+  pub(crate) key: String,
+  pub(crate) range: Option<(i64, i64, BitUnit)>,
+}
+
+/// Parse the `BYTE | BIT` unit of a bitmap range.
+pub(crate) fn parse_bit_unit(frame: RespFrame) -> Result<BitUnit, CommandError> {
+  match parse_string(frame)?.to_ascii_lowercase().as_str() {
+    "byte" => Ok(BitUnit::Byte),
+    "bit" => Ok(BitUnit::Bit),
+    _ => Err(CommandError::SyntaxError),
+  }
+}
+
+impl CommandExecutor for BitCount {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.bitcount(&self.key, self.range) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for BitCount {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let range = match (args.next(), args.next(), args.next()) {
+      (None, _, _) => None,
+      (Some(start), Some(end), unit) => Some((
+        parse_integer(start)?,
+        parse_integer(end)?,
+        unit.map(parse_bit_unit).transpose()?.unwrap_or_default(),
+      )),
+      _ => return Err(CommandError::SyntaxError),
+    };
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(BitCount { key, range })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_bitcount_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*5\r\n$8\r\nBITCOUNT\r\n$5\r\nmykey\r\n$1\r\n5\r\n$2\r\n30\r\n$3\r\nBIT\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: BitCount = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.range, Some((5, 30, BitUnit::Bit)));
+
+    buf.extend_from_slice(b"*3\r\n$8\r\nbitcount\r\n$5\r\nmykey\r\n$1\r\n5\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(BitCount::try_from(frame), Err(CommandError::SyntaxError)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_bitcount_execute() {
+    let backend = Backend::new();
+    backend.set("mykey".to_string(), BulkString::from("foobar").into());
+    let bitcount = |range| BitCount { key: "mykey".to_string(), range };
+
+    assert_eq!(bitcount(None).execute(&backend), RespFrame::Integer(26));
+    assert_eq!(bitcount(Some((1, 1, BitUnit::Byte))).execute(&backend), RespFrame::Integer(6));
+    assert_eq!(bitcount(Some((5, 30, BitUnit::Bit))).execute(&backend), RespFrame::Integer(17));
+  }
+}
@@ -0,0 +1,181 @@
+use super::{
+  extract_args, extract_command_name, parse_integer, parse_string, CommandError, CommandExecutor,
+  RESP_NULL,
+};
+use crate::{Backend, BitFieldOp, BitFieldType, BitOverflow, RespArray, RespFrame, MAX_BIT_OFFSET};
+
+/// BITFIELD key [GET encoding offset | [OVERFLOW <WRAP | SAT | FAIL>]
+///   <SET encoding offset value | INCRBY encoding offset increment> ...]
+/// BITFIELD_RO key [GET encoding offset ...]
+///  BITFIELD mykey INCRBY i5 100 1 OVERFLOW FAIL INCRBY u2 102 4 GET u4 0
+/// 1) (integer) 1
+/// 2) (nil)
+/// 3) (integer) 0
+#[derive(Debug)]
+pub struct BitField {
+  pub(crate) key: String,
+  pub(crate) ops: Vec<BitFieldOp>,
+}
+
+fn parse_type(frame: RespFrame) -> Result<BitFieldType, CommandError> {
+  let ty = parse_string(frame)?.to_ascii_lowercase();
+  let (signed, bits) = match ty.split_at_checked(1) {
+    Some(("i", bits)) => (true, bits.parse().ok().filter(|bits| (1..=64).contains(bits))),
+    Some(("u", bits)) => (false, bits.parse().ok().filter(|bits| (1..=63).contains(bits))),
+    _ => (false, None),
+  };
+  match bits {
+    Some(bits) => Ok(BitFieldType { signed, bits }),
+    None => Err(CommandError::InvalidArgument(
+      "Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is."
+        .to_string(),
+    )),
+  }
+}
+
+/// Parse a bit offset, or with a `#` prefix an offset in multiples of the type width.
+fn parse_offset(frame: RespFrame, ty: BitFieldType) -> Result<u64, CommandError> {
+  let offset = parse_string(frame)?;
+  let offset = match offset.strip_prefix('#') {
+    Some(n) => n.parse::<u64>().ok().and_then(|n| n.checked_mul(ty.bits as u64)),
+    None => offset.parse().ok(),
+  };
+  offset
+    .filter(|offset| {
+      offset.checked_add(ty.bits as u64 - 1).is_some_and(|end| end <= MAX_BIT_OFFSET)
+    })
+    .ok_or(CommandError::InvalidBitOffset)
+}
+
+impl CommandExecutor for BitField {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.bitfield(&self.key, &self.ops) {
+      Ok(values) => {
+        let values = values
+          .into_iter()
+          .map(|value| value.map(RespFrame::Integer).unwrap_or(RESP_NULL.clone()))
+          .collect::<Vec<_>>();
+        RespArray::new(values).into()
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for BitField {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let read_only = extract_command_name(&value)? == "bitfield_ro";
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+
+    let mut ops = Vec::new();
+    let mut overflow = BitOverflow::default();
+    while let Some(arg) = args.next() {
+      let sub = parse_string(arg)?.to_ascii_lowercase();
+      if read_only && sub != "get" {
+        return Err(CommandError::InvalidArgument(
+          "BITFIELD_RO only supports the GET subcommand".to_string(),
+        ));
+      }
+      if sub == "overflow" {
+        overflow = match parse_string(args.next().ok_or(CommandError::SyntaxError)?)?
+          .to_ascii_lowercase()
+          .as_str()
+        {
+          "wrap" => BitOverflow::Wrap,
+          "sat" => BitOverflow::Sat,
+          "fail" => BitOverflow::Fail,
+          _ => {
+            return Err(CommandError::InvalidArgument(
+              "Invalid OVERFLOW type specified".to_string(),
+            ))
+          }
+        };
+        continue;
+      }
+
+      let ty = parse_type(args.next().ok_or(CommandError::SyntaxError)?)?;
+      let offset = parse_offset(args.next().ok_or(CommandError::SyntaxError)?, ty)?;
+      let op = match sub.as_str() {
+        "get" => BitFieldOp::Get { ty, offset },
+        "set" => {
+          let value = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+          BitFieldOp::Set { ty, offset, value, overflow }
+        }
+        "incrby" => {
+          let increment = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+          BitFieldOp::IncrBy { ty, offset, increment, overflow }
+        }
+        _ => return Err(CommandError::SyntaxError),
+      };
+      ops.push(op);
+    }
+    Ok(BitField { key, ops })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  const I5: BitFieldType = BitFieldType { signed: true, bits: 5 };
+  const U4: BitFieldType = BitFieldType { signed: false, bits: 4 };
+
+  #[test]
+  fn test_bitfield_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*12\r\n$8\r\nBITFIELD\r\n$5\r\nmykey\r\n$6\r\nINCRBY\r\n$2\r\ni5\r\n$3\r\n100\r\n$1\r\n1\r\n$8\r\nOVERFLOW\r\n$3\r\nSAT\r\n$3\r\nSET\r\n$2\r\nu4\r\n$2\r\n#2\r\n$2\r\n15\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: BitField = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(
+      ret.ops,
+      [
+        BitFieldOp::IncrBy { ty: I5, offset: 100, increment: 1, overflow: BitOverflow::Wrap },
+        BitFieldOp::Set { ty: U4, offset: 8, value: 15, overflow: BitOverflow::Sat },
+      ]
+    );
+
+    buf.extend_from_slice(
+      b"*5\r\n$11\r\nBITFIELD_RO\r\n$5\r\nmykey\r\n$3\r\nGET\r\n$3\r\nu64\r\n$1\r\n0\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(BitField::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    buf.extend_from_slice(
+      b"*6\r\n$11\r\nbitfield_ro\r\n$5\r\nmykey\r\n$3\r\nset\r\n$2\r\nu4\r\n$1\r\n0\r\n$1\r\n1\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(BitField::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    buf.extend_from_slice(
+      b"*5\r\n$8\r\nbitfield\r\n$5\r\nmykey\r\n$3\r\nget\r\n$2\r\nu4\r\n$2\r\n-1\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(BitField::try_from(frame), Err(CommandError::InvalidBitOffset)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_bitfield_execute() {
+    let backend = Backend::new();
+    let ops = vec![
+      BitFieldOp::IncrBy { ty: I5, offset: 100, increment: 1, overflow: BitOverflow::Wrap },
+      BitFieldOp::Get { ty: U4, offset: 0 },
+    ];
+    let ret = BitField { key: "mykey".to_string(), ops }.execute(&backend);
+    assert_eq!(ret, RespArray::new([RespFrame::Integer(1), RespFrame::Integer(0)]).into());
+
+    let u2 = BitFieldType { signed: false, bits: 2 };
+    let ops =
+      vec![BitFieldOp::IncrBy { ty: u2, offset: 102, increment: 4, overflow: BitOverflow::Fail }];
+    let ret = BitField { key: "mykey".to_string(), ops }.execute(&backend);
+    assert_eq!(ret, RespArray::new([RESP_NULL.clone()]).into());
+  }
+}
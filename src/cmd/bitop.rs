@@ -0,0 +1,96 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BitwiseOp, RespArray, RespFrame};
+
+/// BITOP <AND | OR | XOR | NOT> destkey key [key ...]
+///  SET key1 "foobar"
+/// "OK"
+///  SET key2 "abcdef"
+/// "OK"
+///  BITOP AND dest key1 key2
+/// (integer) 6
+///  GET dest
+/// "`bc`ab"
+#[derive(Debug)]
+pub struct BitOp {
+  pub(crate) op: BitwiseOp,
+  pub(crate) destination: String,
+  pub(crate) keys: Vec<String>,
+}
+
+impl CommandExecutor for BitOp {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.bitop(self.op, &self.destination, &self.keys) {
+      Ok(len) => RespFrame::Integer(len as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for BitOp {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let op = match parse_string(args.next().ok_or(CommandError::SyntaxError)?)?
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      "and" => BitwiseOp::And,
+      "or" => BitwiseOp::Or,
+      "xor" => BitwiseOp::Xor,
+      "not" => BitwiseOp::Not,
+      _ => return Err(CommandError::SyntaxError),
+    };
+    let destination = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let keys = args.map(parse_string).collect::<Result<Vec<String>, _>>()?;
+    if keys.is_empty() {
+      return Err(CommandError::WrongArity("bitop".to_string()));
+    }
+    Ok(BitOp { op, destination, keys })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_bitop_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*5\r\n$5\r\nBITOP\r\n$3\r\nAND\r\n$4\r\ndest\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: BitOp = frame.try_into()?;
+    assert_eq!(ret.op, BitwiseOp::And);
+    assert_eq!(ret.destination, "dest");
+    assert_eq!(ret.keys, vec!["key1".to_string(), "key2".to_string()]);
+
+    buf.extend_from_slice(b"*4\r\n$5\r\nbitop\r\n$4\r\nnand\r\n$4\r\ndest\r\n$4\r\nkey1\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(BitOp::try_from(frame), Err(CommandError::SyntaxError)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_bitop_execute() {
+    let backend = Backend::new();
+    backend.set("key1".to_string(), BulkString::from("foobar").into());
+    backend.set("key2".to_string(), BulkString::from("abcdef").into());
+    let bitop = |op, keys: &[&str]| BitOp {
+      op,
+      destination: "dest".to_string(),
+      keys: keys.iter().map(|k| k.to_string()).collect(),
+    };
+
+    assert_eq!(bitop(BitwiseOp::And, &["key1", "key2"]).execute(&backend), RespFrame::Integer(6));
+    assert_eq!(backend.get("dest"), Ok(Some(BulkString::from("`bc`ab").into())));
+
+    let ret = bitop(BitwiseOp::Not, &["key1", "key2"]).execute(&backend);
+    assert!(matches!(ret, RespFrame::Error(_)));
+  }
+}
@@ -0,0 +1,96 @@
+use super::{
+  bitcount::parse_bit_unit, extract_args, parse_integer, parse_string, CommandError,
+  CommandExecutor,
+};
+use crate::{Backend, BitUnit, RespArray, RespFrame};
+
+/// BITPOS key bit [start [end [BYTE | BIT]]]
+///  SET mykey "\xff\xf0\x00"
+/// "OK"
+///  BITPOS mykey 0
+/// (integer) 12
+///  BITPOS mykey 1 2
+/// (integer) -1
+///  BITPOS mykey 1 7 15 BIT
+/// (integer) 7
+#[derive(Debug)]
+pub struct BitPos {
+  pub(crate) key: String,
+  pub(crate) bit: bool,
+  pub(crate) start: i64,
+  pub(crate) end: Option<i64>,
+  pub(crate) unit: BitUnit,
+}
+
+impl CommandExecutor for BitPos {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.bitpos(&self.key, self.bit, self.start, self.end, self.unit) {
+      Ok(pos) => RespFrame::Integer(pos),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for BitPos {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, bit) = match (args.next(), args.next()) {
+      (Some(key), Some(bit)) => (parse_string(key)?, parse_integer(bit)?),
+      _ => return Err(CommandError::InvalidArgument("Invalid key or bit".to_string())),
+    };
+    let bit = match bit {
+      0 => false,
+      1 => true,
+      _ => {
+        return Err(CommandError::InvalidArgument("The bit argument must be 1 or 0.".to_string()))
+      }
+    };
+    let start = args.next().map(parse_integer).transpose()?.unwrap_or(0);
+    let end = args.next().map(parse_integer).transpose()?;
+    let unit = args.next().map(parse_bit_unit).transpose()?.unwrap_or_default();
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(BitPos { key, bit, start, end, unit })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_bitpos_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*6\r\n$6\r\nBITPOS\r\n$5\r\nmykey\r\n$1\r\n1\r\n$1\r\n7\r\n$2\r\n15\r\n$3\r\nbit\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: BitPos = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert!(ret.bit);
+    assert_eq!((ret.start, ret.end, ret.unit), (7, Some(15), BitUnit::Bit));
+
+    buf.extend_from_slice(b"*3\r\n$6\r\nbitpos\r\n$5\r\nmykey\r\n$1\r\n2\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(BitPos::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_bitpos_execute() {
+    let backend = Backend::new();
+    backend.set("mykey".to_string(), BulkString::new(vec![0xff, 0xf0, 0x00]).into());
+    let bitpos = |bit, start, end, unit| BitPos { key: "mykey".to_string(), bit, start, end, unit };
+
+    assert_eq!(bitpos(false, 0, None, BitUnit::Byte).execute(&backend), RespFrame::Integer(12));
+    assert_eq!(bitpos(true, 2, None, BitUnit::Byte).execute(&backend), RespFrame::Integer(-1));
+    assert_eq!(bitpos(true, 7, Some(15), BitUnit::Bit).execute(&backend), RespFrame::Integer(7));
+  }
+}
@@ -0,0 +1,119 @@
+use super::{
+  extract_args, lmove::parse_list_end, parse_string, parse_timeout, CommandError, CommandExecutor,
+  LMove, RESP_NULL,
+};
+use crate::{Backend, RespArray, RespFrame};
+use std::time::Duration;
+
+/// BLMOVE source destination <LEFT | RIGHT> <LEFT | RIGHT> timeout
+///  RPUSH mylist "one"
+/// (integer) 1
+///  BLMOVE mylist myotherlist RIGHT LEFT 0
+/// "one"
+///  BLMOVE mylist myotherlist RIGHT LEFT 0.1
+/// (nil)
+#[derive(Debug)]
+pub struct BLMove {
+  pub(crate) lmove: LMove,
+  pub(crate) timeout: Option<Duration>,
+}
+
+impl BLMove {
+  /// Move an element as `LMOVE` does, waiting for a push if the source is empty.
+  pub async fn run(self, backend: &Backend) -> RespFrame {
+    let LMove { source, destination, from, to } = self.lmove;
+    let serve = move |backend: &Backend, key: &str| match backend.lmove(key, &destination, from, to)
+    {
+      Ok(value) => value,
+      Err(e) => Some(e.into()),
+    };
+    backend.block_on(vec![source], self.timeout, true, serve).await.unwrap_or(RESP_NULL.clone())
+  }
+}
+
+impl CommandExecutor for BLMove {
+  /// Without a connection to park there is no waiting, it behaves as if the timeout elapsed.
+  fn execute(self, backend: &Backend) -> RespFrame {
+    self.lmove.execute(backend)
+  }
+}
+
+impl TryFrom<RespArray> for BLMove {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next(), args.next(), args.next()) {
+      (Some(source), Some(destination), Some(from), Some(to), Some(timeout)) => Ok(BLMove {
+        lmove: LMove {
+          source: parse_string(source)?,
+          destination: parse_string(destination)?,
+          from: parse_list_end(from)?,
+          to: parse_list_end(to)?,
+        },
+        timeout: parse_timeout(timeout)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid source or destination".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, ListEnd, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_blmove_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*6\r\n$6\r\nBLMOVE\r\n$6\r\nmylist\r\n$11\r\nmyotherlist\r\n$5\r\nRIGHT\r\n$4\r\nLEFT\r\n$1\r\n0\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: BLMove = frame.try_into()?;
+    assert_eq!(ret.lmove.source, "mylist");
+    assert_eq!(ret.lmove.destination, "myotherlist");
+    assert_eq!(ret.lmove.from, ListEnd::Right);
+    assert_eq!(ret.lmove.to, ListEnd::Left);
+    assert_eq!(ret.timeout, None);
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_blmove_run() -> Result<()> {
+    let backend = Backend::new();
+    let blmove = |timeout| BLMove {
+      lmove: LMove {
+        source: "mylist".to_string(),
+        destination: "myotherlist".to_string(),
+        from: ListEnd::Right,
+        to: ListEnd::Left,
+      },
+      timeout,
+    };
+
+    let ret = blmove(Some(Duration::from_millis(10))).run(&backend).await;
+    assert_eq!(ret, RESP_NULL.clone());
+
+    let handle = tokio::spawn({
+      let backend = backend.clone();
+      async move { blmove(None).run(&backend).await }
+    });
+    while !handle.is_finished() {
+      backend.push(
+        "mylist".to_string(),
+        vec![BulkString::from("one").into()],
+        ListEnd::Left,
+        false,
+      )?;
+      tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!(handle.await?, BulkString::from("one").into());
+    assert_eq!(backend.lrange("myotherlist", 0, -1)?, vec![BulkString::from("one").into()]);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,140 @@
+use super::{
+  extract_args, extract_command_name, parse_string, parse_timeout, CommandError, CommandExecutor,
+  RESP_NULL,
+};
+use crate::{Backend, BulkString, ListEnd, RespArray, RespFrame};
+use std::time::Duration;
+
+/// BLPOP key [key ...] timeout
+/// BRPOP key [key ...] timeout
+///  BRPOP empty 0.5
+/// (nil)
+///  RPUSH list1 a b c
+/// (integer) 3
+///  BLPOP list1 list2 0
+/// 1) "list1"
+/// 2) "a"
+#[derive(Debug)]
+pub struct BLPop {
+  pub(crate) keys: Vec<String>,
+  pub(crate) end: ListEnd,
+  pub(crate) timeout: Option<Duration>,
+}
+
+impl BLPop {
+  /// Pop from the first non-empty key, waiting for a push if they are all empty.
+  pub async fn run(self, backend: &Backend) -> RespFrame {
+    let end = self.end;
+    let serve = move |backend: &Backend, key: &str| pop(backend, key, end);
+    backend.block_on(self.keys, self.timeout, false, serve).await.unwrap_or(RESP_NULL.clone())
+  }
+}
+
+fn pop(backend: &Backend, key: &str, end: ListEnd) -> Option<RespFrame> {
+  match backend.pop(key, end, 1) {
+    Ok(Some(mut values)) => {
+      let value = values.pop()?;
+      Some(RespArray::new([BulkString::from(key).into(), value]).into())
+    }
+    Ok(None) => None,
+    Err(e) => Some(e.into()),
+  }
+}
+
+impl CommandExecutor for BLPop {
+  /// Without a connection to park there is no waiting, it behaves as if the timeout elapsed.
+  fn execute(self, backend: &Backend) -> RespFrame {
+    self.keys.iter().find_map(|key| pop(backend, key, self.end)).unwrap_or(RESP_NULL.clone())
+  }
+}
+
+impl TryFrom<RespArray> for BLPop {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let end = match extract_command_name(&value)?.as_str() {
+      "brpop" => ListEnd::Right,
+      _ => ListEnd::Left,
+    };
+
+    let mut args = extract_args(value, 1)?;
+    let timeout = parse_timeout(args.pop().ok_or(CommandError::SyntaxError)?)?;
+    let keys = args.into_iter().map(parse_string).collect::<Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+      return Err(CommandError::InvalidArgument("Invalid key".to_string()));
+    }
+    Ok(BLPop { keys, end, timeout })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_blpop_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$5\r\nBRPOP\r\n$5\r\nlist1\r\n$5\r\nlist2\r\n$3\r\n1.5\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: BLPop = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["list1", "list2"]);
+    assert_eq!(ret.end, ListEnd::Right);
+    assert_eq!(ret.timeout, Some(Duration::from_millis(1500)));
+
+    buf.extend_from_slice(b"*3\r\n$5\r\nblpop\r\n$5\r\nlist1\r\n$2\r\n-1\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(BLPop::try_from(frame), Err(CommandError::NegativeTimeout)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_blpop_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = vec![BulkString::from("a").into(), BulkString::from("b").into()];
+    backend.push("list2".to_string(), values, ListEnd::Right, false)?;
+    let blpop = || BLPop {
+      keys: vec!["list1".to_string(), "list2".to_string()],
+      end: ListEnd::Left,
+      timeout: None,
+    };
+
+    let ret = blpop().execute(&backend);
+    assert_eq!(
+      ret,
+      RespArray::new([BulkString::from("list2").into(), BulkString::from("a").into()]).into()
+    );
+
+    backend.set("list1".to_string(), BulkString::from("str").into());
+    assert_eq!(blpop().execute(&backend), CommandError::WrongType.into());
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_blpop_run() -> Result<()> {
+    let backend = Backend::new();
+    let blpop = |timeout| BLPop { keys: vec!["list".to_string()], end: ListEnd::Right, timeout };
+
+    let ret = blpop(Some(Duration::from_millis(10))).run(&backend).await;
+    assert_eq!(ret, RESP_NULL.clone());
+
+    let handle = tokio::spawn({
+      let backend = backend.clone();
+      async move { blpop(None).run(&backend).await }
+    });
+    while !handle.is_finished() {
+      backend.push("list".to_string(), vec![BulkString::from("a").into()], ListEnd::Left, false)?;
+      tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!(
+      handle.await?,
+      RespArray::new([BulkString::from("list").into(), BulkString::from("a").into()]).into()
+    );
+
+    Ok(())
+  }
+}
@@ -0,0 +1,121 @@
+use super::{
+  extract_args, extract_command_name, parse_string, parse_timeout, CommandError, CommandExecutor,
+  RESP_NULL,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+use std::time::Duration;
+
+/// BZPOPMIN key [key ...] timeout
+/// BZPOPMAX key [key ...] timeout
+///  ZADD zset1 0 a 1 b 2 c
+/// (integer) 3
+///  BZPOPMIN zset1 zset2 0
+/// 1) "zset1"
+/// 2) "a"
+/// 3) (double) 0
+#[derive(Debug)]
+pub struct BZPopMin {
+  pub(crate) keys: Vec<String>,
+  pub(crate) max: bool,
+  pub(crate) timeout: Option<Duration>,
+}
+
+impl BZPopMin {
+  /// Pop from the first non-empty key, waiting for a write if they are all empty.
+  pub async fn run(self, backend: &Backend) -> RespFrame {
+    let max = self.max;
+    let serve = move |backend: &Backend, key: &str| pop(backend, key, max);
+    backend.block_on(self.keys, self.timeout, false, serve).await.unwrap_or(RESP_NULL.clone())
+  }
+}
+
+fn pop(backend: &Backend, key: &str, max: bool) -> Option<RespFrame> {
+  match backend.zpop(key, max, 1) {
+    Ok(Some(mut items)) => {
+      let (member, score) = items.pop()?;
+      let frames = [BulkString::from(key).into(), BulkString::from(member).into(), score.into()];
+      Some(RespArray::new(frames).into())
+    }
+    Ok(None) => None,
+    Err(e) => Some(e.into()),
+  }
+}
+
+impl CommandExecutor for BZPopMin {
+  /// Without a connection to park there is no waiting, it behaves as if the timeout elapsed.
+  fn execute(self, backend: &Backend) -> RespFrame {
+    self.keys.iter().find_map(|key| pop(backend, key, self.max)).unwrap_or(RESP_NULL.clone())
+  }
+}
+
+impl TryFrom<RespArray> for BZPopMin {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let max = extract_command_name(&value)? == "bzpopmax";
+    let mut args = extract_args(value, 1)?;
+    let timeout = parse_timeout(args.pop().ok_or(CommandError::SyntaxError)?)?;
+    let keys = args.into_iter().map(parse_string).collect::<Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+      return Err(CommandError::InvalidArgument("Invalid key".to_string()));
+    }
+    Ok(BZPopMin { keys, max, timeout })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_bzpopmin_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$8\r\nBZPOPMAX\r\n$5\r\nzset1\r\n$5\r\nzset2\r\n$3\r\n0.5\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: BZPopMin = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["zset1", "zset2"]);
+    assert!(ret.max);
+    assert_eq!(ret.timeout, Some(Duration::from_millis(500)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_bzpopmin_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs = vec![(0.0, "a".to_string()), (1.0, "b".to_string())];
+    backend.zadd("zset2", pairs, ZAddOptions::default())?;
+
+    let keys = vec!["zset1".to_string(), "zset2".to_string()];
+    let ret = BZPopMin { keys, max: false, timeout: None }.execute(&backend);
+    let expected = [BulkString::from("zset2").into(), BulkString::from("a").into(), 0.0.into()];
+    assert_eq!(ret, RespArray::new(expected).into());
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_bzpopmin_run() -> Result<()> {
+    let backend = Backend::new();
+    let bzpopmin = |timeout| BZPopMin { keys: vec!["zset".to_string()], max: true, timeout };
+
+    let ret = bzpopmin(Some(Duration::from_millis(10))).run(&backend).await;
+    assert_eq!(ret, RESP_NULL.clone());
+
+    let handle = tokio::spawn({
+      let backend = backend.clone();
+      async move { bzpopmin(None).run(&backend).await }
+    });
+    while !handle.is_finished() {
+      backend.zadd("zset", vec![(1.0, "a".to_string())], ZAddOptions::default())?;
+      tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    let expected = [BulkString::from("zset").into(), BulkString::from("a").into(), 1.0.into()];
+    assert_eq!(handle.await?, RespArray::new(expected).into());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,93 @@
+use super::{extract_args, parse_integer, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// COPY source destination [DB destination-db] [REPLACE]
+///  SET dolly "sheep"
+/// "OK"
+///  COPY dolly clone
+/// (integer) 1
+///  GET clone
+/// "sheep"
+#[derive(Debug)]
+pub struct CopyKey {
+  pub(crate) source: String,
+  pub(crate) destination: String,
+  pub(crate) replace: bool,
+}
+
+impl CommandExecutor for CopyKey {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.copy(&self.source, &self.destination, self.replace) {
+      Ok(copied) => RespFrame::Integer(copied as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for CopyKey {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (source, destination) = match (args.next(), args.next()) {
+      (Some(RespFrame::BulkString(source)), Some(RespFrame::BulkString(destination))) => {
+        (source.into(), destination.into())
+      }
+      _ => return Err(CommandError::InvalidArgument("Invalid source or destination".to_string())),
+    };
+
+    let mut replace = false;
+    while let Some(arg) = args.next() {
+      let opt = match arg {
+        RespFrame::BulkString(opt) => String::from(opt).to_ascii_lowercase(),
+        _ => return Err(CommandError::SyntaxError),
+      };
+      match opt.as_str() {
+        "replace" => replace = true,
+        // there is a single database, so the only valid index is 0
+        "db" => match parse_integer(args.next().ok_or(CommandError::SyntaxError)?)? {
+          0 => {}
+          _ => return Err(CommandError::InvalidArgument("DB index is out of range".to_string())),
+        },
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+
+    Ok(CopyKey { source, destination, replace })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_copy_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*6\r\n$4\r\nCOPY\r\n$5\r\ndolly\r\n$5\r\nclone\r\n$2\r\nDB\r\n$1\r\n0\r\n$7\r\nREPLACE\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: CopyKey = frame.try_into()?;
+    assert_eq!(ret.source, "dolly");
+    assert_eq!(ret.destination, "clone");
+    assert!(ret.replace);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_copy_execute() {
+    let backend = Backend::new();
+    backend.set("dolly".to_string(), RespFrame::BulkString(b"sheep".into()));
+
+    let copy =
+      || CopyKey { source: "dolly".to_string(), destination: "clone".to_string(), replace: false };
+    assert_eq!(copy().execute(&backend), RespFrame::Integer(1));
+    assert_eq!(copy().execute(&backend), RespFrame::Integer(0));
+    assert_eq!(backend.get("clone"), Ok(Some(RespFrame::BulkString(b"sheep".into()))));
+  }
+}
@@ -0,0 +1,75 @@
+use super::{extract_args, extract_command_name, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// DEL key [key ...]
+/// UNLINK key [key ...]
+///  SET key1 "Hello"
+/// "OK"
+///  SET key2 "World"
+/// "OK"
+///  DEL key1 key2 key3
+/// (integer) 2
+#[derive(Debug)]
+pub struct Del {
+  pub(crate) keys: Vec<String>,
+  // UNLINK frees big values on a background task instead of in place
+  pub(crate) lazy: bool,
+}
+
+impl CommandExecutor for Del {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ret = self
+      .keys
+      .iter()
+      .filter(|key| if self.lazy { backend.unlink(key) } else { backend.remove(key) })
+      .count();
+    RespFrame::Integer(ret as i64)
+  }
+}
+
+impl TryFrom<RespArray> for Del {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let lazy = extract_command_name(&value)? == "unlink";
+    let keys =
+      extract_args(value, 1)?.into_iter().map(parse_string).collect::<Result<Vec<String>, _>>()?;
+    Ok(Del { keys, lazy })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_del_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$6\r\nUNLINK\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Del = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["key1".to_string(), "key2".to_string()]);
+    assert!(ret.lazy);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_del_execute() -> Result<()> {
+    let backend = Backend::new();
+    backend.set("key1".to_string(), RespFrame::BulkString(b"Hello".into()));
+    backend.sadd("key2", "World")?;
+
+    let keys = vec!["key1".to_string(), "key2".to_string(), "key3".to_string()];
+    let ret = Del { keys: keys.clone(), lazy: false }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+
+    let ret = Del { keys, lazy: true }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(0));
+
+    Ok(())
+  }
+}
@@ -1,6 +1,6 @@
 use crate::{Backend, BulkString, RespArray, RespFrame};
 
-use super::{extract_args, validate_command, CommandError, CommandExecutor};
+use super::{extract_args, CommandError, CommandExecutor};
 
 #[derive(Debug)]
 pub struct Echo {
@@ -16,8 +16,6 @@ impl CommandExecutor for Echo {
 impl TryFrom<RespArray> for Echo {
   type Error = CommandError;
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["echo"], 1)?;
-
     let mut args = extract_args(value, 1)?.into_iter();
     match args.next() {
       Some(RespFrame::BulkString(v)) => Ok(Echo { value: v.into() }),
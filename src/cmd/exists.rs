@@ -0,0 +1,63 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// EXISTS key [key ...]
+/// TOUCH key [key ...]
+///  SET key1 "Hello"
+/// "OK"
+///  EXISTS key1
+/// (integer) 1
+///  EXISTS nosuchkey
+/// (integer) 0
+///  EXISTS key1 key1 nosuchkey
+/// (integer) 2
+#[derive(Debug)]
+pub struct Exists {
+  pub(crate) keys: Vec<String>,
+}
+
+impl CommandExecutor for Exists {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ret = self.keys.iter().filter(|key| backend.exists(key)).count();
+    RespFrame::Integer(ret as i64)
+  }
+}
+
+impl TryFrom<RespArray> for Exists {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let keys =
+      extract_args(value, 1)?.into_iter().map(parse_string).collect::<Result<Vec<String>, _>>()?;
+    Ok(Exists { keys })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_exists_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$6\r\nexists\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Exists = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["key1".to_string(), "key2".to_string()]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_exists_execute() {
+    let backend = Backend::new();
+    backend.set("key1".to_string(), RespFrame::BulkString(b"Hello".into()));
+
+    let keys = vec!["key1".to_string(), "key1".to_string(), "nosuchkey".to_string()];
+    let ret = Exists { keys }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+  }
+}
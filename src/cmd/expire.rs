@@ -0,0 +1,146 @@
+use super::{extract_args, extract_command_name, parse_integer, CommandError, CommandExecutor};
+use crate::{now_ms, Backend, ExpireCondition, RespArray, RespFrame};
+
+/// EXPIRE key seconds [NX | XX | GT | LT]
+/// PEXPIRE key milliseconds [NX | XX | GT | LT]
+/// EXPIREAT key unix-time-seconds [NX | XX | GT | LT]
+/// PEXPIREAT key unix-time-milliseconds [NX | XX | GT | LT]
+///  SET mykey "Hello"
+/// "OK"
+///  EXPIRE mykey 10 XX
+/// (integer) 0
+///  EXPIRE mykey 10 NX
+/// (integer) 1
+///  TTL mykey
+/// (integer) 10
+#[derive(Debug)]
+pub struct Expire {
+  pub(crate) name: &'static str,
+  pub(crate) key: String,
+  pub(crate) when: i64,
+  // milliseconds per unit of `when`
+  pub(crate) unit: i64,
+  pub(crate) absolute: bool,
+  pub(crate) cond: ExpireCondition,
+}
+
+impl CommandExecutor for Expire {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let at = self.when.checked_mul(self.unit).and_then(|ms| match self.absolute {
+      true => Some(ms),
+      false => ms.checked_add(now_ms()),
+    });
+    match at {
+      Some(at) => RespFrame::Integer(backend.expire_at(&self.key, at, self.cond) as i64),
+      None => CommandError::InvalidExpireTime(self.name.to_string()).into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for Expire {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let (name, unit, absolute) = match extract_command_name(&value)?.as_str() {
+      "expire" => ("expire", 1000, false),
+      "pexpire" => ("pexpire", 1, false),
+      "expireat" => ("expireat", 1000, true),
+      "pexpireat" => ("pexpireat", 1, true),
+      name => return Err(CommandError::InvalidCommand(format!("unexpected command {}", name))),
+    };
+
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, when) = match (args.next(), args.next()) {
+      (Some(RespFrame::BulkString(key)), Some(when)) => (key.into(), parse_integer(when)?),
+      _ => return Err(CommandError::InvalidArgument("Invalid key or time".to_string())),
+    };
+
+    let mut cond = ExpireCondition::default();
+    for arg in args {
+      let opt = match arg {
+        RespFrame::BulkString(opt) => String::from(opt),
+        _ => return Err(CommandError::SyntaxError),
+      };
+      match opt.to_ascii_lowercase().as_str() {
+        "nx" => cond.nx = true,
+        "xx" => cond.xx = true,
+        "gt" => cond.gt = true,
+        "lt" => cond.lt = true,
+        _ => return Err(CommandError::InvalidArgument(format!("Unsupported option {}", opt))),
+      }
+    }
+    if cond.nx && (cond.xx || cond.gt || cond.lt) {
+      return Err(CommandError::InvalidArgument(
+        "NX and XX, GT or LT options at the same time are not compatible".to_string(),
+      ));
+    }
+    if cond.gt && cond.lt {
+      return Err(CommandError::InvalidArgument(
+        "GT and LT options at the same time are not compatible".to_string(),
+      ));
+    }
+
+    Ok(Expire { name, key, when, unit, absolute, cond })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_expire_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$7\r\nPEXPIRE\r\n$5\r\nmykey\r\n$3\r\n100\r\n$2\r\ngt\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Expire = frame.try_into()?;
+    assert_eq!(ret.name, "pexpire");
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.when, 100);
+    assert_eq!(ret.unit, 1);
+    assert!(!ret.absolute);
+    assert_eq!(ret.cond, ExpireCondition { gt: true, ..Default::default() });
+
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*5\r\n$6\r\nexpire\r\n$5\r\nmykey\r\n$1\r\n1\r\n$2\r\nNX\r\n$2\r\nXX\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(Expire::try_from(frame).is_err());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_expire_execute() {
+    let backend = Backend::new();
+    let cmd = |when, cond| Expire {
+      name: "expire",
+      key: "mykey".to_string(),
+      when,
+      unit: 1000,
+      absolute: false,
+      cond,
+    };
+
+    let ret = cmd(10, ExpireCondition::default()).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(0));
+
+    backend.set("mykey".to_string(), RespFrame::BulkString(b"hello".into()));
+    let ret = cmd(10, ExpireCondition { xx: true, ..Default::default() }).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(0));
+    let ret = cmd(10, ExpireCondition { nx: true, ..Default::default() }).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+    assert!(backend.expire_time("mykey").unwrap().is_some());
+
+    let ret = cmd(i64::MAX, ExpireCondition::default()).execute(&backend);
+    assert!(matches!(ret, RespFrame::Error(_)));
+
+    let ret = cmd(-1, ExpireCondition::default()).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+    assert_eq!(backend.get("mykey"), Ok(None));
+  }
+}
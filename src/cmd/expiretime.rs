@@ -0,0 +1,81 @@
+use super::{extract_args, extract_command_name, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// EXPIRETIME key
+/// PEXPIRETIME key
+///  SET mykey "Hello"
+/// "OK"
+///  EXPIREAT mykey 33177117420
+/// (integer) 1
+///  EXPIRETIME mykey
+/// (integer) 33177117420
+#[derive(Debug)]
+pub struct ExpireTime {
+  pub(crate) key: String,
+  // milliseconds per unit of the reply
+  pub(crate) unit: i64,
+}
+
+impl CommandExecutor for ExpireTime {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let at = match backend.expire_time(&self.key) {
+      None => -2,
+      Some(None) => -1,
+      Some(Some(at)) => at / self.unit,
+    };
+    RespFrame::Integer(at)
+  }
+}
+
+impl TryFrom<RespArray> for ExpireTime {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let unit = match extract_command_name(&value)?.as_str() {
+      "pexpiretime" => 1,
+      _ => 1000,
+    };
+
+    let mut args = extract_args(value, 1)?.into_iter();
+    match args.next() {
+      Some(RespFrame::BulkString(key)) => Ok(ExpireTime { key: key.into(), unit }),
+      _ => Err(CommandError::InvalidArgument("Invalid key".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{ExpireCondition, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_expiretime_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$10\r\nexpiretime\r\n$5\r\nmykey\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ExpireTime = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.unit, 1000);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_expiretime_execute() {
+    let backend = Backend::new();
+    backend.set("mykey".to_string(), RespFrame::BulkString(b"hello".into()));
+    backend.expire_at("mykey", 33177117420000, ExpireCondition::default());
+
+    let ret = ExpireTime { key: "mykey".to_string(), unit: 1000 }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(33177117420));
+
+    let ret = ExpireTime { key: "mykey".to_string(), unit: 1 }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(33177117420000));
+
+    let ret = ExpireTime { key: "missing".to_string(), unit: 1 }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(-2));
+  }
+}
@@ -1,4 +1,4 @@
-use super::{extract_args, validate_command, CommandError, CommandExecutor};
+use super::{extract_args, CommandError, CommandExecutor};
 
 use crate::{cmd::RESP_NULL, Backend, RespArray, RespFrame};
 #[derive(Debug)]
@@ -9,8 +9,9 @@ pub struct Get {
 impl CommandExecutor for Get {
   fn execute(self, backend: &Backend) -> RespFrame {
     match backend.get(&self.key) {
-      Some(value) => value,
-      None => RESP_NULL.clone(),
+      Ok(Some(value)) => value,
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
     }
   }
 }
@@ -18,8 +19,6 @@ impl CommandExecutor for Get {
 impl TryFrom<RespArray> for Get {
   type Error = CommandError;
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["get"], 1)?;
-
     let mut args = extract_args(value, 1)?.into_iter();
     match args.next() {
       Some(RespFrame::BulkString(key)) => Ok(Get { key: key.into() }),
@@ -48,4 +47,13 @@ mod tests {
 
     Ok(())
   }
+
+  #[test]
+  fn test_get_wrong_type() {
+    let backend = Backend::new();
+    backend.sadd("myset", "hello").unwrap();
+
+    let ret = Get { key: "myset".to_string() }.execute(&backend);
+    assert_eq!(ret, CommandError::WrongType.into());
+  }
 }
@@ -0,0 +1,71 @@
+use super::{extract_args, parse_string, setbit::parse_bit_offset, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// GETBIT key offset
+///  SETBIT mykey 7 1
+/// (integer) 0
+///  GETBIT mykey 0
+/// (integer) 0
+///  GETBIT mykey 7
+/// (integer) 1
+///  GETBIT mykey 100
+/// (integer) 0
+#[derive(Debug)]
+pub struct GetBit {
+  pub(crate) key: String,
+  pub(crate) offset: u64,
+}
+
+impl CommandExecutor for GetBit {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.getbit(&self.key, self.offset) {
+      Ok(bit) => RespFrame::Integer(bit as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for GetBit {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+      (Some(key), Some(offset)) => {
+        Ok(GetBit { key: parse_string(key)?, offset: parse_bit_offset(offset)? })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key or offset".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_getbit_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$6\r\nGETBIT\r\n$5\r\nmykey\r\n$3\r\n100\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: GetBit = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.offset, 100);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_getbit_execute() {
+    let backend = Backend::new();
+    backend.setbit("mykey", 7, true).unwrap();
+    let getbit = |offset| GetBit { key: "mykey".to_string(), offset };
+
+    assert_eq!(getbit(0).execute(&backend), RespFrame::Integer(0));
+    assert_eq!(getbit(7).execute(&backend), RespFrame::Integer(1));
+    assert_eq!(getbit(100).execute(&backend), RespFrame::Integer(0));
+  }
+}
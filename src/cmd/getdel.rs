@@ -0,0 +1,65 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, RespArray, RespFrame};
+
+/// GETDEL key
+///  SET mykey "Hello"
+/// "OK"
+///  GETDEL mykey
+/// "Hello"
+///  GET mykey
+/// (nil)
+#[derive(Debug)]
+pub struct GetDel {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for GetDel {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.getdel(&self.key) {
+      Ok(Some(value)) => value,
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for GetDel {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(GetDel { key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_getdel_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$6\r\nGETDEL\r\n$5\r\nmykey\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: GetDel = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_getdel_execute() {
+    let backend = Backend::new();
+    backend.set("mykey".to_string(), BulkString::from("Hello").into());
+
+    let ret = GetDel { key: "mykey".to_string() }.execute(&backend);
+    assert_eq!(ret, BulkString::from("Hello").into());
+    let ret = GetDel { key: "mykey".to_string() }.execute(&backend);
+    assert_eq!(ret, RESP_NULL.clone());
+    assert!(!backend.exists("mykey"));
+  }
+}
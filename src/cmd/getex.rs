@@ -0,0 +1,130 @@
+use super::{
+  extract_args, parse_integer, parse_string, set::SetExpire, CommandError, CommandExecutor,
+  RESP_NULL,
+};
+use crate::{Backend, RespArray, RespFrame, SetTtl};
+
+/// GETEX key [EX seconds | PX milliseconds | EXAT unix-time-seconds |
+///   PXAT unix-time-milliseconds | PERSIST]
+///  SET mykey "Hello"
+/// "OK"
+///  GETEX mykey
+/// "Hello"
+///  GETEX mykey EX 60
+/// "Hello"
+///  TTL mykey
+/// (integer) 60
+#[derive(Debug)]
+pub struct GetEx {
+  pub(crate) key: String,
+  pub(crate) expire: Option<SetExpire>,
+}
+
+impl CommandExecutor for GetEx {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ttl = match self.expire.map(SetExpire::ttl) {
+      Some(Some(ttl)) => ttl,
+      Some(None) => return CommandError::InvalidExpireTime("getex".to_string()).into(),
+      None => SetTtl::Keep,
+    };
+    match backend.getex(&self.key, ttl) {
+      Ok(Some(value)) => value,
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for GetEx {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+
+    let expire =
+      match args.next().map(parse_string).transpose()?.map(|opt| opt.to_ascii_lowercase()) {
+        None => None,
+        Some(opt) if opt == "persist" => Some(SetExpire::Persist),
+        Some(opt) => {
+          let unit: fn(i64) -> SetExpire = match opt.as_str() {
+            "ex" => SetExpire::Ex,
+            "px" => SetExpire::Px,
+            "exat" => SetExpire::ExAt,
+            "pxat" => SetExpire::PxAt,
+            _ => return Err(CommandError::SyntaxError),
+          };
+          let n = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+          if n <= 0 {
+            return Err(CommandError::InvalidExpireTime("getex".to_string()));
+          }
+          Some(unit(n))
+        }
+      };
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(GetEx { key, expire })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{now_ms, BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_getex_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$5\r\nGETEX\r\n$5\r\nmykey\r\n$2\r\nEX\r\n$2\r\n60\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: GetEx = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.expire, Some(SetExpire::Ex(60)));
+
+    buf.extend_from_slice(b"*3\r\n$5\r\ngetex\r\n$5\r\nmykey\r\n$7\r\npersist\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: GetEx = frame.try_into()?;
+    assert_eq!(ret.expire, Some(SetExpire::Persist));
+
+    buf.extend_from_slice(
+      b"*5\r\n$5\r\ngetex\r\n$5\r\nmykey\r\n$7\r\npersist\r\n$2\r\nex\r\n$1\r\n1\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(GetEx::try_from(frame), Err(CommandError::SyntaxError)));
+
+    buf.extend_from_slice(b"*4\r\n$5\r\ngetex\r\n$5\r\nmykey\r\n$2\r\npx\r\n$1\r\n0\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(GetEx::try_from(frame), Err(CommandError::InvalidExpireTime(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_getex_execute() {
+    let backend = Backend::new();
+    backend.set("mykey".to_string(), BulkString::from("Hello").into());
+    let getex = |expire| GetEx { key: "mykey".to_string(), expire };
+
+    assert_eq!(getex(None).execute(&backend), BulkString::from("Hello").into());
+    assert_eq!(backend.expire_time("mykey"), Some(None));
+
+    let at = now_ms() + 60_000;
+    assert_eq!(
+      getex(Some(SetExpire::PxAt(at))).execute(&backend),
+      BulkString::from("Hello").into()
+    );
+    assert_eq!(backend.expire_time("mykey"), Some(Some(at)));
+
+    getex(Some(SetExpire::Persist)).execute(&backend);
+    assert_eq!(backend.expire_time("mykey"), Some(None));
+
+    assert_eq!(
+      getex(Some(SetExpire::Ex(i64::MAX))).execute(&backend),
+      CommandError::InvalidExpireTime("getex".to_string()).into()
+    );
+    assert_eq!(getex(None).execute(&backend), BulkString::from("Hello").into());
+  }
+}
@@ -0,0 +1,77 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// GETRANGE key start end
+///  SET mykey "This is a string"
+/// "OK"
+///  GETRANGE mykey 0 3
+/// "This"
+///  GETRANGE mykey -3 -1
+/// "ing"
+///  GETRANGE mykey 10 100
+/// "string"
+#[derive(Debug)]
+pub struct GetRange {
+  pub(crate) key: String,
+  pub(crate) start: i64,
+  pub(crate) end: i64,
+}
+
+impl CommandExecutor for GetRange {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.getrange(&self.key, self.start, self.end) {
+      Ok(bytes) => BulkString::new(bytes).into(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for GetRange {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(start), Some(end)) => Ok(GetRange {
+        key: parse_string(key)?,
+        start: parse_integer(start)?,
+        end: parse_integer(end)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid key, start or end".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_getrange_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$8\r\nGETRANGE\r\n$5\r\nmykey\r\n$2\r\n-3\r\n$2\r\n-1\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: GetRange = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.start, -3);
+    assert_eq!(ret.end, -1);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_getrange_execute() {
+    let backend = Backend::new();
+    backend.set("mykey".to_string(), BulkString::from("This is a string").into());
+    let getrange = |start, end| GetRange { key: "mykey".to_string(), start, end };
+
+    assert_eq!(getrange(0, 3).execute(&backend), BulkString::from("This").into());
+    assert_eq!(getrange(-3, -1).execute(&backend), BulkString::from("ing").into());
+    assert_eq!(getrange(0, -1).execute(&backend), BulkString::from("This is a string").into());
+    assert_eq!(getrange(10, 100).execute(&backend), BulkString::from("string").into());
+    assert_eq!(getrange(5, 1).execute(&backend), BulkString::from("").into());
+  }
+}
@@ -0,0 +1,72 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, RespArray, RespFrame, SetCondition, SetTtl};
+
+/// GETSET key value
+///  SET mykey "Hello"
+/// "OK"
+///  GETSET mykey "World"
+/// "Hello"
+///  GET mykey
+/// "World"
+#[derive(Debug)]
+pub struct GetSet {
+  pub(crate) key: String,
+  pub(crate) value: RespFrame,
+}
+
+impl CommandExecutor for GetSet {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.set_if(self.key, self.value, SetCondition::Always, SetTtl::Discard, true) {
+      Ok((_, old)) => old.unwrap_or(RESP_NULL.clone()),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for GetSet {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+      (Some(key), Some(value)) => Ok(GetSet { key: parse_string(key)?, value }),
+      _ => Err(CommandError::InvalidArgument("Invalid key or value".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_getset_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$6\r\nGETSET\r\n$5\r\nmykey\r\n$5\r\nWorld\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: GetSet = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.value, BulkString::from("World").into());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_getset_execute() {
+    let backend = Backend::new();
+    let getset =
+      |value: &str| GetSet { key: "mykey".to_string(), value: BulkString::from(value).into() };
+
+    assert_eq!(getset("Hello").execute(&backend), RESP_NULL.clone());
+    assert_eq!(getset("World").execute(&backend), BulkString::from("Hello").into());
+    assert_eq!(backend.get("mykey"), Ok(Some(BulkString::from("World").into())));
+
+    backend.sadd("myset", "a").unwrap();
+    let ret =
+      GetSet { key: "myset".to_string(), value: BulkString::from("x").into() }.execute(&backend);
+    assert_eq!(ret, CommandError::WrongType.into());
+  }
+}
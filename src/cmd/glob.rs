@@ -0,0 +1,118 @@
+/// Redis-style glob matching as used by `KEYS` and the `MATCH` option of the scan family:
+/// `*` matches any sequence, `?` any single byte, `[abc]`, `[^abc]` and `[a-z]` match byte
+/// classes, and `\` escapes the next byte.
+/// Matching is iterative and only ever backtracks to the last `*` seen, so it takes
+/// O(pattern * s) time whatever the pattern.
+pub fn glob_match(pattern: &[u8], s: &[u8]) -> bool {
+  let (mut p, mut i) = (0, 0);
+  // the pattern after the last `*` and where in `s` it was last tried from
+  let mut star = None;
+  while i < s.len() {
+    if pattern.get(p) == Some(&b'*') {
+      p += 1;
+      star = Some((p, i));
+      continue;
+    }
+    if let Some(next) = match_one(pattern, p, s[i]) {
+      (p, i) = (next, i + 1);
+      continue;
+    }
+    // let the last `*` take one more byte, or give up if there is none
+    let Some((after_star, from)) = star else {
+      return false;
+    };
+    (p, i) = (after_star, from + 1);
+    star = Some((after_star, from + 1));
+  }
+  pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Match `c` against the pattern element at `p`, returning where the next one starts.
+fn match_one(pattern: &[u8], p: usize, c: u8) -> Option<usize> {
+  match &pattern[p..] {
+    [] => None,
+    [b'?', ..] => Some(p + 1),
+    [b'[', rest @ ..] => {
+      let (matched, rest) = match_class(rest, c);
+      matched.then_some(pattern.len() - rest.len())
+    }
+    [b'\\', escaped, ..] => (*escaped == c).then_some(p + 2),
+    [lit, ..] => (*lit == c).then_some(p + 1),
+  }
+}
+
+/// Match `c` against the class starting right after `[`, returning the pattern after `]`.
+fn match_class(mut pattern: &[u8], c: u8) -> (bool, &[u8]) {
+  let negate = pattern.first() == Some(&b'^');
+  if negate {
+    pattern = &pattern[1..];
+  }
+
+  let mut matched = false;
+  loop {
+    match pattern {
+      [] => break,
+      [b']', rest @ ..] => {
+        pattern = rest;
+        break;
+      }
+      [b'\\', escaped, rest @ ..] => {
+        matched |= *escaped == c;
+        pattern = rest;
+      }
+      [start, b'-', end, rest @ ..] if *end != b']' => {
+        let (lo, hi) = if start <= end { (*start, *end) } else { (*end, *start) };
+        matched |= (lo..=hi).contains(&c);
+        pattern = rest;
+      }
+      [p, rest @ ..] => {
+        matched |= *p == c;
+        pattern = rest;
+      }
+    }
+  }
+
+  (matched != negate, pattern)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::time::{Duration, Instant};
+
+  #[test]
+  fn test_glob_match() {
+    assert!(glob_match(b"*", b""));
+    assert!(glob_match(b"*", b"anything"));
+    assert!(glob_match(b"h?llo", b"hello"));
+    assert!(!glob_match(b"h?llo", b"hllo"));
+    assert!(glob_match(b"h*llo", b"heeeello"));
+    assert!(glob_match(b"h[ae]llo", b"hallo"));
+    assert!(!glob_match(b"h[ae]llo", b"hillo"));
+    assert!(glob_match(b"h[^e]llo", b"hallo"));
+    assert!(!glob_match(b"h[^e]llo", b"hello"));
+    assert!(glob_match(b"h[a-b]llo", b"hbllo"));
+    assert!(glob_match(b"h[b-a]llo", b"hallo"));
+    assert!(glob_match(b"user:*:name", b"user:42:name"));
+    assert!(!glob_match(b"user:*:name", b"user:42:age"));
+    assert!(glob_match(b"\\*", b"*"));
+    assert!(!glob_match(b"\\*", b"a"));
+    assert!(glob_match(b"[\\]]", b"]"));
+    assert!(!glob_match(b"abc", b"abcd"));
+    assert!(glob_match(b"a*b*c", b"aXbYbZc"));
+    assert!(!glob_match(b"a*b*c", b"aXbYbZ"));
+    assert!(glob_match(b"*?", b"x"));
+    assert!(glob_match(b"\\", b"\\"));
+  }
+
+  #[test]
+  fn test_glob_match_bounded() {
+    let start = Instant::now();
+    assert!(!glob_match(b"*a*a*a*a*a*a*a*b", &[b'a'; 40]));
+    assert!(!glob_match(&b"*a".repeat(1000), &[b'a'; 999]));
+    // long patterns and keys do not grow the stack
+    let long = vec![b'x'; 1_000_000];
+    assert!(glob_match(&vec![b'?'; 1_000_000], &long));
+    assert!(start.elapsed() < Duration::from_secs(1));
+  }
+}
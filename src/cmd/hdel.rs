@@ -0,0 +1,69 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// HDEL key field [field ...]
+///  HSET myhash field1 "foo"
+/// (integer) 1
+///  HDEL myhash field1
+/// (integer) 1
+///  HDEL myhash field2
+/// (integer) 0
+#[derive(Debug)]
+pub struct HDel {
+  pub(crate) key: String,
+  pub(crate) fields: Vec<String>,
+}
+
+impl CommandExecutor for HDel {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hdel(&self.key, &self.fields) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HDel {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let fields = args.map(parse_string).collect::<Result<Vec<String>, _>>()?;
+    Ok(HDel { key, fields })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hdel_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$4\r\nHDEL\r\n$6\r\nmyhash\r\n$6\r\nfield1\r\n$6\r\nfield2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HDel = frame.try_into()?;
+    assert_eq!(ret.key, "myhash");
+    assert_eq!(ret.fields, vec!["field1".to_string(), "field2".to_string()]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hdel_execute() {
+    let backend = Backend::new();
+    backend
+      .hset("myhash".to_string(), "field1".to_string(), BulkString::from("foo").into())
+      .unwrap();
+    let hdel = |field: &str| HDel { key: "myhash".to_string(), fields: vec![field.to_string()] };
+
+    assert_eq!(hdel("field2").execute(&backend), RespFrame::Integer(0));
+    assert_eq!(hdel("field1").execute(&backend), RespFrame::Integer(1));
+    // the hash goes away with its last field
+    assert!(!backend.exists("myhash"));
+  }
+}
@@ -0,0 +1,70 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// HEXISTS key field
+///  HSET myhash field1 "foo"
+/// (integer) 1
+///  HEXISTS myhash field1
+/// (integer) 1
+///  HEXISTS myhash field2
+/// (integer) 0
+#[derive(Debug)]
+pub struct HExists {
+  pub(crate) key: String,
+  pub(crate) field: String,
+}
+
+impl CommandExecutor for HExists {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hexists(&self.key, &self.field) {
+      Ok(exists) => RespFrame::Integer(exists as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HExists {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+      (Some(key), Some(field)) => {
+        Ok(HExists { key: parse_string(key)?, field: parse_string(field)? })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key or field".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hexists_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$7\r\nHEXISTS\r\n$6\r\nmyhash\r\n$6\r\nfield1\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HExists = frame.try_into()?;
+    assert_eq!(ret.key, "myhash");
+    assert_eq!(ret.field, "field1");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hexists_execute() {
+    let backend = Backend::new();
+    backend
+      .hset("myhash".to_string(), "field1".to_string(), BulkString::from("foo").into())
+      .unwrap();
+    let hexists = |field: &str| HExists { key: "myhash".to_string(), field: field.to_string() };
+
+    assert_eq!(hexists("field1").execute(&backend), RespFrame::Integer(1));
+    assert_eq!(hexists("field2").execute(&backend), RespFrame::Integer(0));
+  }
+}
@@ -1,4 +1,4 @@
-use super::{extract_args, validate_command, CommandError, CommandExecutor};
+use super::{extract_args, CommandError, CommandExecutor};
 use crate::{cmd::RESP_NULL, Backend, RespArray, RespFrame};
 
 #[derive(Debug)]
@@ -10,8 +10,9 @@ pub struct HGet {
 impl CommandExecutor for HGet {
   fn execute(self, backend: &Backend) -> RespFrame {
     match backend.hget(&self.key, &self.field) {
-      Some(value) => value,
-      None => RESP_NULL.clone(),
+      Ok(Some(value)) => value,
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
     }
   }
 }
@@ -19,8 +20,6 @@ impl CommandExecutor for HGet {
 impl TryFrom<RespArray> for HGet {
   type Error = CommandError;
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["hget"], 2)?;
-
     let mut args = extract_args(value, 1)?.into_iter();
     match (args.next(), args.next()) {
       (Some(RespFrame::BulkString(key)), Some(RespFrame::BulkString(field))) => {
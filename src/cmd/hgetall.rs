@@ -1,4 +1,4 @@
-use super::{extract_args, validate_command, CommandError, CommandExecutor};
+use super::{extract_args, CommandError, CommandExecutor};
 use crate::{Backend, BulkString, RespArray, RespFrame};
 use std::vec;
 
@@ -12,7 +12,7 @@ impl CommandExecutor for HGetAll {
   fn execute(self, backend: &Backend) -> RespFrame {
     let hmap = backend.hgetall(&self.key);
     match hmap {
-      Some(hmap) => {
+      Ok(Some(hmap)) => {
         let mut data = Vec::with_capacity(hmap.len());
         for v in hmap.iter() {
           let key = v.key().to_owned();
@@ -28,7 +28,8 @@ impl CommandExecutor for HGetAll {
 
         RespArray::new(ret).into()
       }
-      None => RespArray::new([]).into(),
+      Ok(None) => RespArray::new([]).into(),
+      Err(e) => e.into(),
     }
   }
 }
@@ -36,8 +37,6 @@ impl CommandExecutor for HGetAll {
 impl TryFrom<RespArray> for HGetAll {
   type Error = CommandError;
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["hgetall"], 1)?;
-
     let mut args = extract_args(value, 1)?.into_iter();
     match args.next() {
       Some(RespFrame::BulkString(key)) => Ok(HGetAll {
@@ -55,7 +54,7 @@ impl TryFrom<RespArray> for HGetAll {
 #[cfg(test)]
 mod test {
   use super::*;
-  use crate::{cmd::RESP_OK, HGet, HSet, RespDecode};
+  use crate::{HGet, HSet, RespDecode};
   use anyhow::Result;
   use bytes::BytesMut;
 
@@ -78,17 +77,17 @@ mod test {
     let backend = Backend::new();
     let cmd = HSet {
       key: "map".to_string(),
-      field: "hello".to_string(),
-      value: RespFrame::BulkString(b"world".into()),
+      pairs: vec![("hello".to_string(), RespFrame::BulkString(b"world".into()))],
+      reply_ok: false,
     };
     let ret = cmd.execute(&backend);
 
-    assert_eq!(ret, RESP_OK.clone());
+    assert_eq!(ret, RespFrame::Integer(1));
 
     let cmd = HSet {
       key: "map".to_string(),
-      field: "hello1".to_string(),
-      value: RespFrame::BulkString(b"world1".into()),
+      pairs: vec![("hello1".to_string(), RespFrame::BulkString(b"world1".into()))],
+      reply_ok: false,
     };
     cmd.execute(&backend);
 
@@ -0,0 +1,74 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// HINCRBY key field increment
+///  HSET myhash field 5
+/// (integer) 1
+///  HINCRBY myhash field 1
+/// (integer) 6
+///  HINCRBY myhash field -10
+/// (integer) -4
+#[derive(Debug)]
+pub struct HIncrBy {
+  pub(crate) key: String,
+  pub(crate) field: String,
+  pub(crate) increment: i64,
+}
+
+impl CommandExecutor for HIncrBy {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hincrby(&self.key, self.field, self.increment) {
+      Ok(n) => RespFrame::Integer(n),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HIncrBy {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(field), Some(increment)) => Ok(HIncrBy {
+        key: parse_string(key)?,
+        field: parse_string(field)?,
+        increment: parse_integer(increment)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid key, field or increment".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hincrby_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$7\r\nHINCRBY\r\n$6\r\nmyhash\r\n$5\r\nfield\r\n$3\r\n-10\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HIncrBy = frame.try_into()?;
+    assert_eq!(ret.key, "myhash");
+    assert_eq!(ret.field, "field");
+    assert_eq!(ret.increment, -10);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hincrby_execute() {
+    let backend = Backend::new();
+    let hincrby =
+      |increment| HIncrBy { key: "myhash".to_string(), field: "field".to_string(), increment };
+
+    assert_eq!(hincrby(5).execute(&backend), RespFrame::Integer(5));
+    assert_eq!(hincrby(1).execute(&backend), RespFrame::Integer(6));
+    assert_eq!(hincrby(-10).execute(&backend), RespFrame::Integer(-4));
+    assert!(matches!(hincrby(i64::MIN).execute(&backend), RespFrame::Error(_)));
+  }
+}
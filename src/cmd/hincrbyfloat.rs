@@ -0,0 +1,77 @@
+use super::{extract_args, parse_float, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// HINCRBYFLOAT key field increment
+///  HSET mykey field 10.50
+/// (integer) 1
+///  HINCRBYFLOAT mykey field 0.1
+/// "10.6"
+///  HINCRBYFLOAT mykey field -5
+/// "5.6"
+#[derive(Debug)]
+pub struct HIncrByFloat {
+  pub(crate) key: String,
+  pub(crate) field: String,
+  pub(crate) increment: f64,
+}
+
+impl CommandExecutor for HIncrByFloat {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hincrbyfloat(&self.key, self.field, self.increment) {
+      Ok(value) => BulkString::from(value).into(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HIncrByFloat {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(field), Some(increment)) => Ok(HIncrByFloat {
+        key: parse_string(key)?,
+        field: parse_string(field)?,
+        increment: parse_float(increment)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid key, field or increment".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hincrbyfloat_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*4\r\n$12\r\nHINCRBYFLOAT\r\n$5\r\nmykey\r\n$5\r\nfield\r\n$3\r\n0.1\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HIncrByFloat = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.field, "field");
+    assert_eq!(ret.increment, 0.1);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hincrbyfloat_execute() {
+    let backend = Backend::new();
+    backend
+      .hset("mykey".to_string(), "field".to_string(), BulkString::from("10.50").into())
+      .unwrap();
+    let hincrbyfloat =
+      |increment| HIncrByFloat { key: "mykey".to_string(), field: "field".to_string(), increment };
+
+    assert_eq!(hincrbyfloat(0.1).execute(&backend), BulkString::from("10.6").into());
+    assert_eq!(hincrbyfloat(-5.0).execute(&backend), BulkString::from("5.6").into());
+  }
+}
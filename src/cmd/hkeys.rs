@@ -0,0 +1,67 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// HKEYS key
+///  HSET myhash field1 "Hello" field2 "World"
+/// (integer) 2
+///  HKEYS myhash
+/// 1) "field1"
+/// 2) "field2"
+#[derive(Debug)]
+pub struct HKeys {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for HKeys {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hkeys(&self.key) {
+      Ok(fields) => {
+        RespArray::new(fields.into_iter().map(|f| BulkString::from(f).into()).collect::<Vec<_>>())
+          .into()
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HKeys {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(HKeys { key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hkeys_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$5\r\nHKEYS\r\n$6\r\nmyhash\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HKeys = frame.try_into()?;
+    assert_eq!(ret.key, "myhash");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hkeys_execute() {
+    let backend = Backend::new();
+    backend
+      .hset("myhash".to_string(), "field1".to_string(), BulkString::from("Hello").into())
+      .unwrap();
+
+    let ret = HKeys { key: "myhash".to_string() }.execute(&backend);
+    assert_eq!(ret, RespArray::new([BulkString::from("field1").into()]).into());
+    let ret = HKeys { key: "missing".to_string() }.execute(&backend);
+    assert_eq!(ret, RespArray::new([]).into());
+  }
+}
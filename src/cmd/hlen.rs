@@ -0,0 +1,60 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// HLEN key
+///  HSET myhash field1 "Hello" field2 "World"
+/// (integer) 2
+///  HLEN myhash
+/// (integer) 2
+#[derive(Debug)]
+pub struct HLen {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for HLen {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hlen(&self.key) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HLen {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(HLen { key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hlen_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$4\r\nHLEN\r\n$6\r\nmyhash\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HLen = frame.try_into()?;
+    assert_eq!(ret.key, "myhash");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hlen_execute() {
+    let backend = Backend::new();
+    let pairs = ["field1", "field2"].map(|f| (f.to_string(), BulkString::from("v").into()));
+    backend.hmset("myhash", pairs.to_vec()).unwrap();
+
+    assert_eq!(HLen { key: "myhash".to_string() }.execute(&backend), RespFrame::Integer(2));
+    assert_eq!(HLen { key: "missing".to_string() }.execute(&backend), RespFrame::Integer(0));
+  }
+}
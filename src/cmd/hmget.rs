@@ -1,4 +1,4 @@
-use super::{extract_args, validate_command, CommandError, CommandExecutor};
+use super::{extract_args, CommandError, CommandExecutor};
 use crate::{cmd::RESP_NULL, Backend, RespArray, RespFrame};
 
 #[derive(Debug)]
@@ -9,22 +9,21 @@ pub struct HMGet {
 
 impl CommandExecutor for HMGet {
   fn execute(self, backend: &Backend) -> RespFrame {
-    RespArray::new(
-      self
-        .fields
-        .iter()
-        .map(|field| backend.hget(&self.key, field).unwrap_or(RESP_NULL.clone()))
-        .collect::<Vec<RespFrame>>(),
-    )
-    .into()
+    let ret = self
+      .fields
+      .iter()
+      .map(|field| backend.hget(&self.key, field).map(|v| v.unwrap_or(RESP_NULL.clone())))
+      .collect::<Result<Vec<RespFrame>, CommandError>>();
+    match ret {
+      Ok(ret) => RespArray::new(ret).into(),
+      Err(e) => e.into(),
+    }
   }
 }
 
 impl TryFrom<RespArray> for HMGet {
   type Error = CommandError;
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["hmget"], 2)?;
-
     let mut args = extract_args(value, 1)?.into_iter();
     match (args.next(), args.next()) {
       (Some(RespFrame::BulkString(key)), Some(RespFrame::BulkString(field))) => {
@@ -42,7 +41,7 @@ impl TryFrom<RespArray> for HMGet {
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{cmd::RESP_OK, HSet, RespDecode};
+  use crate::{HSet, RespDecode};
   use anyhow::Result;
   use bytes::BytesMut;
 
@@ -62,12 +61,12 @@ mod tests {
     let backend = Backend::new();
     let cmd = HSet {
       key: "map".to_string(),
-      field: "hello".to_string(),
-      value: RespFrame::BulkString(b"world".into()),
+      pairs: vec![("hello".to_string(), RespFrame::BulkString(b"world".into()))],
+      reply_ok: false,
     };
     let ret = cmd.execute(&backend);
 
-    assert_eq!(ret, RESP_OK.clone());
+    assert_eq!(ret, RespFrame::Integer(1));
 
     let cmd =
       HMGet { key: "map".to_string(), fields: vec!["hello".to_string(), "field".to_string()] };
@@ -0,0 +1,120 @@
+use super::{
+  extract_args, parse_string, srandmember::parse_random_count, CommandError, CommandExecutor,
+  RESP_NULL,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// HRANDFIELD key [count [WITHVALUES]]
+///  HSET coin heads obverse tails reverse edge null
+/// (integer) 3
+///  HRANDFIELD coin
+/// "heads"
+///  HRANDFIELD coin -5 WITHVALUES
+/// 1) 1) "heads"
+///    2) "obverse"
+/// 2) 1) "edge"
+///    2) "null"
+/// 3) 1) "edge"
+///    2) "null"
+/// 4) 1) "tails"
+///    2) "reverse"
+/// 5) 1) "heads"
+///    2) "obverse"
+#[derive(Debug)]
+pub struct HRandField {
+  pub(crate) key: String,
+  // a negative count allows the same field more than once
+  pub(crate) count: Option<i64>,
+  pub(crate) with_values: bool,
+}
+
+impl CommandExecutor for HRandField {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let pairs = match backend.hrandfield(&self.key, self.count.unwrap_or(1)) {
+      Ok(pairs) => pairs,
+      Err(e) => return e.into(),
+    };
+    if self.count.is_none() {
+      return pairs
+        .into_iter()
+        .next()
+        .map_or(RESP_NULL.clone(), |(f, _)| BulkString::from(f).into());
+    }
+    let frames = pairs
+      .into_iter()
+      .map(|(field, value)| match self.with_values {
+        true => RespArray::new([BulkString::from(field).into(), value]).into(),
+        false => BulkString::from(field).into(),
+      })
+      .collect::<Vec<RespFrame>>();
+    RespArray::new(frames).into()
+  }
+}
+
+impl TryFrom<RespArray> for HRandField {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let count = args.next().map(parse_random_count).transpose()?;
+    let with_values = match args.next().map(parse_string).transpose()? {
+      Some(opt) if opt.eq_ignore_ascii_case("withvalues") => true,
+      Some(_) => return Err(CommandError::SyntaxError),
+      None => false,
+    };
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(HRandField { key, count, with_values })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hrandfield_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*4\r\n$10\r\nHRANDFIELD\r\n$4\r\ncoin\r\n$2\r\n-5\r\n$10\r\nWITHVALUES\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HRandField = frame.try_into()?;
+    assert_eq!(ret.key, "coin");
+    assert_eq!(ret.count, Some(-5));
+    assert!(ret.with_values);
+
+    buf.extend_from_slice(b"*3\r\n$10\r\nhrandfield\r\n$4\r\ncoin\r\n$10\r\nwithvalues\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(HRandField::try_from(frame), Err(CommandError::NotInteger)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hrandfield_execute() {
+    let backend = Backend::new();
+    backend
+      .hset("coin".to_string(), "heads".to_string(), BulkString::from("obverse").into())
+      .unwrap();
+    let hrandfield =
+      |count, with_values| HRandField { key: "coin".to_string(), count, with_values };
+
+    assert_eq!(hrandfield(None, false).execute(&backend), BulkString::from("heads").into());
+    let pair: RespFrame =
+      RespArray::new([BulkString::from("heads").into(), BulkString::from("obverse").into()]).into();
+    assert_eq!(
+      hrandfield(Some(-2), true).execute(&backend),
+      RespArray::new([pair.clone(), pair]).into()
+    );
+    assert_eq!(
+      hrandfield(Some(5), false).execute(&backend),
+      RespArray::new([BulkString::from("heads").into()]).into()
+    );
+  }
+}
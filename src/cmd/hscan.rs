@@ -0,0 +1,109 @@
+use super::{
+  extract_args, parse_string,
+  scan::{parse_cursor, scan_reply, ScanOptions},
+  CommandError, CommandExecutor,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// HSCAN key cursor [MATCH pattern] [COUNT count] [NOVALUES]
+///  HSET myhash name Jack
+/// (integer) 1
+///  HSCAN myhash 0 MATCH n*
+/// 1) "0"
+/// 2) 1) "name"
+///    2) "Jack"
+#[derive(Debug)]
+pub struct HScan {
+  pub(crate) key: String,
+  pub(crate) cursor: u64,
+  pub(crate) opts: ScanOptions,
+  pub(crate) no_values: bool,
+}
+
+impl CommandExecutor for HScan {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hscan(&self.key, self.cursor, self.opts.count) {
+      Ok((cursor, fields)) => {
+        let ret = fields
+          .into_iter()
+          .filter(|(field, _)| self.opts.matches(field))
+          .flat_map(|(field, value)| {
+            let field = BulkString::from(field).into();
+            if self.no_values {
+              vec![field]
+            } else {
+              vec![field, value]
+            }
+          })
+          .collect();
+        scan_reply(cursor, ret)
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HScan {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let cursor = parse_cursor(args.next().ok_or(CommandError::SyntaxError)?)?;
+
+    let mut scan = HScan { key, cursor, opts: ScanOptions::default(), no_values: false };
+    while let Some(arg) = args.next() {
+      let opt = parse_string(arg)?.to_ascii_lowercase();
+      if scan.opts.parse_option(&opt, &mut args)? {
+        continue;
+      }
+      match opt.as_str() {
+        "novalues" => scan.no_values = true,
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+
+    Ok(scan)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hscan_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*6\r\n$5\r\nhscan\r\n$6\r\nmyhash\r\n$1\r\n0\r\n$5\r\nmatch\r\n$2\r\nn*\r\n$8\r\nNOVALUES\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HScan = frame.try_into()?;
+    assert_eq!(ret.key, "myhash");
+    assert_eq!(ret.cursor, 0);
+    assert_eq!(ret.opts.pattern.as_deref(), Some("n*"));
+    assert!(ret.no_values);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hscan_execute() -> Result<()> {
+    let backend = Backend::new();
+    backend.hset("myhash".to_string(), "name".to_string(), BulkString::from("Jack").into())?;
+    backend.hset("myhash".to_string(), "age".to_string(), RespFrame::Integer(33))?;
+
+    let opts = ScanOptions { pattern: Some("n*".to_string()), count: 10 };
+    let ret =
+      HScan { key: "myhash".to_string(), cursor: 0, opts, no_values: false }.execute(&backend);
+    assert_eq!(
+      ret,
+      scan_reply(0, vec![BulkString::from("name").into(), BulkString::from("Jack").into()])
+    );
+
+    Ok(())
+  }
+}
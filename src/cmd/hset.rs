@@ -1,39 +1,59 @@
-use super::{extract_args, validate_command, CommandError, CommandExecutor, RESP_OK};
+use super::{
+  extract_args, extract_command_name, parse_string, CommandError, CommandExecutor, RESP_OK,
+};
 use crate::{Backend, RespArray, RespFrame};
 
+/// HSET key field value [field value ...]
+/// HMSET key field value [field value ...]
+///  HSET myhash field1 "Hello" field2 "World"
+/// (integer) 2
+///  HSET myhash field2 "Hi" field3 "there"
+/// (integer) 1
+///  HMSET myhash field1 "Hello"
+/// "OK"
 #[derive(Debug)]
 pub struct HSet {
   pub(crate) key: String,
-  pub(crate) field: String,
-  pub(crate) value: RespFrame,
+  pub(crate) pairs: Vec<(String, RespFrame)>,
+  // HMSET replies OK instead of the number of new fields
+  pub(crate) reply_ok: bool,
 }
 
 impl CommandExecutor for HSet {
   fn execute(self, backend: &Backend) -> RespFrame {
-    backend.hset(self.key, self.field, self.value);
-    RESP_OK.clone()
+    match backend.hmset(&self.key, self.pairs) {
+      Ok(_) if self.reply_ok => RESP_OK.clone(),
+      Ok(added) => RespFrame::Integer(added as i64),
+      Err(e) => e.into(),
+    }
   }
 }
 
 impl TryFrom<RespArray> for HSet {
   type Error = CommandError;
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["hset"], 3)?;
-
+    let name = extract_command_name(&value)?;
     let mut args = extract_args(value, 1)?.into_iter();
-    match (args.next(), args.next(), args.next()) {
-      (Some(RespFrame::BulkString(key)), Some(RespFrame::BulkString(field)), Some(value)) => {
-        Ok(HSet { key: key.into(), field: field.into(), value })
-      }
-      _ => Err(CommandError::InvalidArgument("Invalid key, field or value".to_string())),
+    let key = match args.next() {
+      Some(RespFrame::BulkString(key)) => key.into(),
+      _ => return Err(CommandError::InvalidArgument("Invalid key".to_string())),
+    };
+    if args.len() == 0 || !args.len().is_multiple_of(2) {
+      return Err(CommandError::WrongArity(name));
+    }
+
+    let mut pairs = Vec::with_capacity(args.len() / 2);
+    while let (Some(field), Some(value)) = (args.next(), args.next()) {
+      pairs.push((parse_string(field)?, value));
     }
+    Ok(HSet { key, pairs, reply_ok: name == "hmset" })
   }
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
-  use crate::{HSet, RespDecode};
+  use crate::{BulkString, HSet, RespDecode};
   use anyhow::Result;
   use bytes::BytesMut;
 
@@ -46,9 +66,38 @@ mod test {
 
     let ret: HSet = frame.try_into()?;
     assert_eq!(ret.key, "map");
-    assert_eq!(ret.field, "hello");
-    assert_eq!(ret.value, RespFrame::BulkString(b"world".into()));
+    assert_eq!(ret.pairs, [("hello".to_string(), RespFrame::BulkString(b"world".into()))]);
+    assert!(!ret.reply_ok);
+
+    buf.extend_from_slice(
+      b"*6\r\n$5\r\nHMSET\r\n$3\r\nmap\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HSet = frame.try_into()?;
+    assert_eq!(ret.pairs.len(), 2);
+    assert!(ret.reply_ok);
+
+    buf.extend_from_slice(b"*5\r\n$4\r\nhset\r\n$3\r\nmap\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(HSet::try_from(frame), Err(CommandError::WrongArity(name)) if name == "hset"));
 
     Ok(())
   }
+
+  #[test]
+  fn test_hset_execute() {
+    let backend = Backend::new();
+    let hset = |pairs: &[(&str, &str)], reply_ok| HSet {
+      key: "myhash".to_string(),
+      pairs: pairs.iter().map(|(f, v)| (f.to_string(), BulkString::from(*v).into())).collect(),
+      reply_ok,
+    };
+
+    let ret = hset(&[("field1", "Hello"), ("field2", "World")], false).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+    let ret = hset(&[("field2", "Hi"), ("field3", "there")], false).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+    assert_eq!(hset(&[("field1", "Hello")], true).execute(&backend), RESP_OK.clone());
+    assert_eq!(backend.hget("myhash", "field2"), Ok(Some(BulkString::from("Hi").into())));
+  }
 }
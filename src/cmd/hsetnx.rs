@@ -0,0 +1,74 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// HSETNX key field value
+///  HSETNX myhash field "Hello"
+/// (integer) 1
+///  HSETNX myhash field "World"
+/// (integer) 0
+///  HGET myhash field
+/// "Hello"
+#[derive(Debug)]
+pub struct HSetNx {
+  pub(crate) key: String,
+  pub(crate) field: String,
+  pub(crate) value: RespFrame,
+}
+
+impl CommandExecutor for HSetNx {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hsetnx(&self.key, self.field, self.value) {
+      Ok(set) => RespFrame::Integer(set as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HSetNx {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(field), Some(value)) => {
+        Ok(HSetNx { key: parse_string(key)?, field: parse_string(field)?, value })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key, field or value".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hsetnx_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$6\r\nHSETNX\r\n$6\r\nmyhash\r\n$5\r\nfield\r\n$5\r\nHello\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HSetNx = frame.try_into()?;
+    assert_eq!(ret.key, "myhash");
+    assert_eq!(ret.field, "field");
+    assert_eq!(ret.value, BulkString::from("Hello").into());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hsetnx_execute() {
+    let backend = Backend::new();
+    let hsetnx = |value: &str| HSetNx {
+      key: "myhash".to_string(),
+      field: "field".to_string(),
+      value: BulkString::from(value).into(),
+    };
+
+    assert_eq!(hsetnx("Hello").execute(&backend), RespFrame::Integer(1));
+    assert_eq!(hsetnx("World").execute(&backend), RespFrame::Integer(0));
+    assert_eq!(backend.hget("myhash", "field"), Ok(Some(BulkString::from("Hello").into())));
+  }
+}
@@ -0,0 +1,72 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// HSTRLEN key field
+///  HSET myhash f1 HelloWorld f2 99 f3 -256
+/// (integer) 3
+///  HSTRLEN myhash f1
+/// (integer) 10
+///  HSTRLEN myhash f3
+/// (integer) 4
+#[derive(Debug)]
+pub struct HStrLen {
+  pub(crate) key: String,
+  pub(crate) field: String,
+}
+
+impl CommandExecutor for HStrLen {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hstrlen(&self.key, &self.field) {
+      Ok(len) => RespFrame::Integer(len as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HStrLen {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+      (Some(key), Some(field)) => {
+        Ok(HStrLen { key: parse_string(key)?, field: parse_string(field)? })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key or field".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hstrlen_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$7\r\nHSTRLEN\r\n$6\r\nmyhash\r\n$2\r\nf1\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HStrLen = frame.try_into()?;
+    assert_eq!(ret.key, "myhash");
+    assert_eq!(ret.field, "f1");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hstrlen_execute() {
+    let backend = Backend::new();
+    backend
+      .hset("myhash".to_string(), "f1".to_string(), BulkString::from("HelloWorld").into())
+      .unwrap();
+    backend.hset("myhash".to_string(), "f3".to_string(), RespFrame::Integer(-256)).unwrap();
+    let hstrlen = |field: &str| HStrLen { key: "myhash".to_string(), field: field.to_string() };
+
+    assert_eq!(hstrlen("f1").execute(&backend), RespFrame::Integer(10));
+    assert_eq!(hstrlen("f3").execute(&backend), RespFrame::Integer(4));
+    assert_eq!(hstrlen("missing").execute(&backend), RespFrame::Integer(0));
+  }
+}
@@ -0,0 +1,62 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// HVALS key
+///  HSET myhash field1 "Hello" field2 "World"
+/// (integer) 2
+///  HVALS myhash
+/// 1) "Hello"
+/// 2) "World"
+#[derive(Debug)]
+pub struct HVals {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for HVals {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.hvals(&self.key) {
+      Ok(values) => RespArray::new(values).into(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for HVals {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(HVals { key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_hvals_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$5\r\nHVALS\r\n$6\r\nmyhash\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: HVals = frame.try_into()?;
+    assert_eq!(ret.key, "myhash");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_hvals_execute() {
+    let backend = Backend::new();
+    backend
+      .hset("myhash".to_string(), "field1".to_string(), BulkString::from("Hello").into())
+      .unwrap();
+
+    let ret = HVals { key: "myhash".to_string() }.execute(&backend);
+    assert_eq!(ret, RespArray::new([BulkString::from("Hello").into()]).into());
+  }
+}
@@ -0,0 +1,98 @@
+use super::{
+  extract_args, extract_command_name, parse_integer, parse_string, CommandError, CommandExecutor,
+};
+use crate::{Backend, RespArray, RespFrame};
+
+/// INCR key
+/// DECR key
+/// INCRBY key increment
+/// DECRBY key decrement
+///  SET mykey "10"
+/// "OK"
+///  INCR mykey
+/// (integer) 11
+///  DECRBY mykey 3
+/// (integer) 8
+///  SET mykey "234293482390480948029348230948"
+/// "OK"
+///  INCR mykey
+/// (error) ERR value is not an integer or out of range
+#[derive(Debug)]
+pub struct Incr {
+  pub(crate) key: String,
+  pub(crate) delta: i64,
+}
+
+impl CommandExecutor for Incr {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.incr_by(&self.key, self.delta) {
+      Ok(n) => RespFrame::Integer(n),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for Incr {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let name = extract_command_name(&value)?;
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let delta = match (name.as_str(), args.next()) {
+      ("incr", None) => 1,
+      ("decr", None) => -1,
+      ("incrby", Some(delta)) => parse_integer(delta)?,
+      ("decrby", Some(delta)) => {
+        parse_integer(delta)?.checked_neg().ok_or(CommandError::NotInteger)?
+      }
+      _ => return Err(CommandError::SyntaxError),
+    };
+    Ok(Incr { key, delta })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_incr_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$4\r\nDECR\r\n$5\r\nmykey\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Incr = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.delta, -1);
+
+    buf.extend_from_slice(b"*3\r\n$6\r\nincrby\r\n$5\r\nmykey\r\n$2\r\n-5\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Incr = frame.try_into()?;
+    assert_eq!(ret.delta, -5);
+
+    let min = i64::MIN.to_string();
+    buf.extend_from_slice(
+      format!("*3\r\n$6\r\ndecrby\r\n$5\r\nmykey\r\n${}\r\n{}\r\n", min.len(), min).as_bytes(),
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(Incr::try_from(frame), Err(CommandError::NotInteger)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_incr_execute() {
+    let backend = Backend::new();
+    let incr = |delta| Incr { key: "mykey".to_string(), delta };
+
+    backend.set("mykey".to_string(), BulkString::from("10").into());
+    assert_eq!(incr(1).execute(&backend), RespFrame::Integer(11));
+    assert_eq!(incr(-3).execute(&backend), RespFrame::Integer(8));
+    assert_eq!(backend.get("mykey"), Ok(Some(BulkString::from("8").into())));
+
+    backend.set("mykey".to_string(), BulkString::from("234293482390480948029348230948").into());
+    assert_eq!(incr(1).execute(&backend), CommandError::NotInteger.into());
+  }
+}
@@ -0,0 +1,82 @@
+use super::{extract_args, parse_float, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// INCRBYFLOAT key increment
+///  SET mykey 10.50
+/// "OK"
+///  INCRBYFLOAT mykey 0.1
+/// "10.6"
+///  INCRBYFLOAT mykey -5
+/// "5.6"
+///  SET mykey 5.0e3
+/// "OK"
+///  INCRBYFLOAT mykey 2.0e2
+/// "5200"
+#[derive(Debug)]
+pub struct IncrByFloat {
+  pub(crate) key: String,
+  pub(crate) increment: f64,
+}
+
+impl CommandExecutor for IncrByFloat {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.incr_by_float(&self.key, self.increment) {
+      Ok(value) => BulkString::from(value).into(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for IncrByFloat {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+      (Some(key), Some(increment)) => {
+        Ok(IncrByFloat { key: parse_string(key)?, increment: parse_float(increment)? })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key or increment".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_incrbyfloat_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$11\r\nINCRBYFLOAT\r\n$5\r\nmykey\r\n$5\r\n2.0e2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: IncrByFloat = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.increment, 200.0);
+
+    buf.extend_from_slice(b"*3\r\n$11\r\nincrbyfloat\r\n$5\r\nmykey\r\n$3\r\nabc\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(IncrByFloat::try_from(frame), Err(CommandError::NotFloat)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_incrbyfloat_execute() {
+    let backend = Backend::new();
+    let incrbyfloat = |increment| IncrByFloat { key: "mykey".to_string(), increment };
+
+    backend.set("mykey".to_string(), BulkString::from("10.50").into());
+    assert_eq!(incrbyfloat(0.1).execute(&backend), BulkString::from("10.6").into());
+    assert_eq!(incrbyfloat(-5.0).execute(&backend), BulkString::from("5.6").into());
+
+    backend.set("mykey".to_string(), BulkString::from("5.0e3").into());
+    assert_eq!(incrbyfloat(2.0e2).execute(&backend), BulkString::from("5200").into());
+
+    backend.set("mykey".to_string(), BulkString::from("abc").into());
+    assert_eq!(incrbyfloat(1.0).execute(&backend), CommandError::NotFloat.into());
+  }
+}
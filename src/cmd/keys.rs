@@ -0,0 +1,64 @@
+use super::{extract_args, glob::glob_match, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// KEYS pattern
+///  MSET firstname Jack lastname Stuntman age 35
+/// "OK"
+///  KEYS *name*
+/// 1) "firstname"
+/// 2) "lastname"
+#[derive(Debug)]
+pub struct Keys {
+  pub(crate) pattern: String,
+}
+
+impl CommandExecutor for Keys {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ret = backend
+      .keys()
+      .into_iter()
+      .filter(|key| glob_match(self.pattern.as_bytes(), key.as_bytes()))
+      .map(|key| BulkString::from(key).into())
+      .collect::<Vec<RespFrame>>();
+    RespArray::new(ret).into()
+  }
+}
+
+impl TryFrom<RespArray> for Keys {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let pattern = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(Keys { pattern })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_keys_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$4\r\nkeys\r\n$6\r\n*name*\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Keys = frame.try_into()?;
+    assert_eq!(ret.pattern, "*name*");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_keys_execute() {
+    let backend = Backend::new();
+    backend.set("firstname".to_string(), BulkString::from("Jack").into());
+    backend.set("age".to_string(), RespFrame::Integer(35));
+
+    let ret = Keys { pattern: "*name*".to_string() }.execute(&backend);
+    assert_eq!(ret, RespArray::new([BulkString::from("firstname").into()]).into());
+  }
+}
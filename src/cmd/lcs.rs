@@ -0,0 +1,167 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BulkString, LcsMatch, RespArray, RespFrame, RespMap};
+
+/// LCS key1 key2 [LEN] [IDX] [MINMATCHLEN min-match-len] [WITHMATCHLEN]
+///  MSET key1 ohmytext key2 mynewtext
+/// "OK"
+///  LCS key1 key2
+/// "mytext"
+///  LCS key1 key2 LEN
+/// (integer) 6
+///  LCS key1 key2 IDX MINMATCHLEN 4 WITHMATCHLEN
+/// 1# "len" => (integer) 6
+/// 2# "matches" =>
+///    1) 1) 1) (integer) 4
+///          2) (integer) 7
+///       2) 1) (integer) 5
+///          2) (integer) 8
+///       3) (integer) 4
+#[derive(Debug)]
+pub struct Lcs {
+  pub(crate) key1: String,
+  pub(crate) key2: String,
+  pub(crate) len: bool,
+  pub(crate) idx: bool,
+  pub(crate) min_match_len: usize,
+  pub(crate) with_match_len: bool,
+}
+
+fn range_reply((start, end): (usize, usize)) -> RespFrame {
+  RespArray::new([RespFrame::Integer(start as i64), RespFrame::Integer(end as i64)]).into()
+}
+
+impl Lcs {
+  fn match_reply(&self, m: &LcsMatch) -> RespFrame {
+    let mut reply = vec![range_reply(m.a), range_reply(m.b)];
+    if self.with_match_len {
+      reply.push(RespFrame::Integer(m.match_len() as i64));
+    }
+    RespArray::new(reply).into()
+  }
+}
+
+impl CommandExecutor for Lcs {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let (sequence, matches) = match backend.lcs(&self.key1, &self.key2) {
+      Ok(lcs) => lcs,
+      Err(e) => return e.into(),
+    };
+    if self.len {
+      return RespFrame::Integer(sequence.len() as i64);
+    }
+    if !self.idx {
+      return BulkString::new(sequence).into();
+    }
+
+    let matches = matches
+      .iter()
+      .filter(|m| m.match_len() >= self.min_match_len)
+      .map(|m| self.match_reply(m))
+      .collect::<Vec<_>>();
+    let mut reply = RespMap::new();
+    reply.insert("matches".to_string(), RespArray::new(matches).into());
+    reply.insert("len".to_string(), RespFrame::Integer(sequence.len() as i64));
+    reply.into()
+  }
+}
+
+impl TryFrom<RespArray> for Lcs {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key1, key2) = match (args.next(), args.next()) {
+      (Some(key1), Some(key2)) => (parse_string(key1)?, parse_string(key2)?),
+      _ => return Err(CommandError::InvalidArgument("Invalid keys".to_string())),
+    };
+
+    let mut lcs =
+      Lcs { key1, key2, len: false, idx: false, min_match_len: 0, with_match_len: false };
+    while let Some(arg) = args.next() {
+      match parse_string(arg)?.to_ascii_lowercase().as_str() {
+        "len" => lcs.len = true,
+        "idx" => lcs.idx = true,
+        "withmatchlen" => lcs.with_match_len = true,
+        "minmatchlen" => {
+          // a negative length is no minimum at all
+          let n = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+          lcs.min_match_len = n.max(0) as usize;
+        }
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+    if lcs.len && lcs.idx {
+      return Err(CommandError::InvalidArgument(
+        "If you want both the length and indexes, please just use IDX.".to_string(),
+      ));
+    }
+    Ok(lcs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  fn lcs(len: bool, idx: bool, min_match_len: usize, with_match_len: bool) -> Lcs {
+    Lcs {
+      key1: "key1".to_string(),
+      key2: "key2".to_string(),
+      len,
+      idx,
+      min_match_len,
+      with_match_len,
+    }
+  }
+
+  #[test]
+  fn test_lcs_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*7\r\n$3\r\nLCS\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n$3\r\nIDX\r\n$11\r\nMINMATCHLEN\r\n$1\r\n4\r\n$12\r\nWITHMATCHLEN\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Lcs = frame.try_into()?;
+    assert_eq!(ret.key1, "key1");
+    assert_eq!(ret.key2, "key2");
+    assert!(ret.idx && ret.with_match_len && !ret.len);
+    assert_eq!(ret.min_match_len, 4);
+
+    buf.extend_from_slice(
+      b"*5\r\n$3\r\nlcs\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n$3\r\nlen\r\n$3\r\nidx\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(Lcs::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lcs_execute() {
+    let backend = Backend::new();
+    backend.set("key1".to_string(), BulkString::from("ohmytext").into());
+    backend.set("key2".to_string(), BulkString::from("mynewtext").into());
+
+    assert_eq!(lcs(false, false, 0, false).execute(&backend), BulkString::from("mytext").into());
+    assert_eq!(lcs(true, false, 0, false).execute(&backend), RespFrame::Integer(6));
+
+    let mut expected = RespMap::new();
+    let first = RespArray::new([range_reply((4, 7)), range_reply((5, 8)), RespFrame::Integer(4)]);
+    expected.insert("matches".to_string(), RespArray::new([first.into()]).into());
+    expected.insert("len".to_string(), RespFrame::Integer(6));
+    assert_eq!(lcs(false, true, 4, true).execute(&backend), expected.into());
+
+    let ret = lcs(false, true, 0, false).execute(&backend);
+    let RespFrame::Map(map) = ret else { panic!("expected a map, got {:?}", ret) };
+    let second = RespArray::new([range_reply((2, 3)), range_reply((0, 1))]);
+    let first = RespArray::new([range_reply((4, 7)), range_reply((5, 8))]);
+    assert_eq!(map["matches"], RespArray::new([first.into(), second.into()]).into());
+
+    backend.sadd("myset", "a").unwrap();
+    let ret = Lcs { key2: "myset".to_string(), ..lcs(false, false, 0, false) }.execute(&backend);
+    assert_eq!(ret, CommandError::WrongType.into());
+  }
+}
@@ -0,0 +1,77 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, RespArray, RespFrame};
+
+/// LINDEX key index
+///  LPUSH mylist "World" "Hello"
+/// (integer) 2
+///  LINDEX mylist 0
+/// "Hello"
+///  LINDEX mylist -1
+/// "World"
+///  LINDEX mylist 3
+/// (nil)
+#[derive(Debug)]
+pub struct LIndex {
+  pub(crate) key: String,
+  pub(crate) index: i64,
+}
+
+impl CommandExecutor for LIndex {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.lindex(&self.key, self.index) {
+      Ok(Some(value)) => value,
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LIndex {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+      (Some(key), Some(index)) => {
+        Ok(LIndex { key: parse_string(key)?, index: parse_integer(index)? })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key or index".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, ListEnd, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_lindex_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$6\r\nLINDEX\r\n$6\r\nmylist\r\n$2\r\n-1\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LIndex = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+    assert_eq!(ret.index, -1);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lindex_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = vec![BulkString::from("World").into(), BulkString::from("Hello").into()];
+    backend.push("mylist".to_string(), values, ListEnd::Left, false)?;
+
+    let ret = LIndex { key: "mylist".to_string(), index: 0 }.execute(&backend);
+    assert_eq!(ret, BulkString::from("Hello").into());
+    let ret = LIndex { key: "mylist".to_string(), index: -1 }.execute(&backend);
+    assert_eq!(ret, BulkString::from("World").into());
+    let ret = LIndex { key: "mylist".to_string(), index: 3 }.execute(&backend);
+    assert_eq!(ret, RESP_NULL.clone());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,91 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// LINSERT key <BEFORE | AFTER> pivot element
+///  RPUSH mylist "Hello" "World"
+/// (integer) 2
+///  LINSERT mylist BEFORE "World" "There"
+/// (integer) 3
+///  LRANGE mylist 0 -1
+/// 1) "Hello"
+/// 2) "There"
+/// 3) "World"
+#[derive(Debug)]
+pub struct LInsert {
+  pub(crate) key: String,
+  pub(crate) before: bool,
+  pub(crate) pivot: RespFrame,
+  pub(crate) value: RespFrame,
+}
+
+impl CommandExecutor for LInsert {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.linsert(&self.key, self.before, &self.pivot, self.value) {
+      Ok(len) => RespFrame::Integer(len),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LInsert {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next(), args.next()) {
+      (Some(key), Some(position), Some(pivot), Some(value)) => {
+        let before = match parse_string(position)?.to_ascii_lowercase().as_str() {
+          "before" => true,
+          "after" => false,
+          _ => return Err(CommandError::SyntaxError),
+        };
+        Ok(LInsert { key: parse_string(key)?, before, pivot, value })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key, pivot or element".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, ListEnd, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_linsert_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*5\r\n$7\r\nLINSERT\r\n$6\r\nmylist\r\n$6\r\nBEFORE\r\n$5\r\nWorld\r\n$5\r\nThere\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LInsert = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+    assert!(ret.before);
+    assert_eq!(ret.pivot, BulkString::from("World").into());
+    assert_eq!(ret.value, BulkString::from("There").into());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_linsert_execute() -> Result<()> {
+    let backend = Backend::new();
+    let linsert = |pivot: &str| LInsert {
+      key: "mylist".to_string(),
+      before: false,
+      pivot: BulkString::from(pivot).into(),
+      value: BulkString::from("There").into(),
+    };
+    assert_eq!(linsert("Hello").execute(&backend), RespFrame::Integer(0));
+
+    let values = vec![BulkString::from("Hello").into(), BulkString::from("World").into()];
+    backend.push("mylist".to_string(), values, ListEnd::Right, false)?;
+    assert_eq!(linsert("Hello").execute(&backend), RespFrame::Integer(3));
+    assert_eq!(linsert("missing").execute(&backend), RespFrame::Integer(-1));
+    assert_eq!(backend.lindex("mylist", 1)?, Some(BulkString::from("There").into()));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,67 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// LLEN key
+///  LPUSH mylist "World"
+/// (integer) 1
+///  LPUSH mylist "Hello"
+/// (integer) 2
+///  LLEN mylist
+/// (integer) 2
+#[derive(Debug)]
+pub struct LLen {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for LLen {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.llen(&self.key) {
+      Ok(len) => RespFrame::Integer(len as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LLen {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(LLen { key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, ListEnd, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_llen_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$4\r\nllen\r\n$6\r\nmylist\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LLen = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_llen_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = vec![BulkString::from("World").into(), BulkString::from("Hello").into()];
+    backend.push("mylist".to_string(), values, ListEnd::Left, false)?;
+
+    let ret = LLen { key: "mylist".to_string() }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+
+    let ret = LLen { key: "missing".to_string() }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(0));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,100 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, ListEnd, RespArray, RespFrame};
+
+/// LMOVE source destination <LEFT | RIGHT> <LEFT | RIGHT>
+///  RPUSH mylist "one" "two" "three"
+/// (integer) 3
+///  LMOVE mylist myotherlist RIGHT LEFT
+/// "three"
+///  LMOVE mylist myotherlist LEFT RIGHT
+/// "one"
+#[derive(Debug)]
+pub struct LMove {
+  pub(crate) source: String,
+  pub(crate) destination: String,
+  pub(crate) from: ListEnd,
+  pub(crate) to: ListEnd,
+}
+
+pub(crate) fn parse_list_end(frame: RespFrame) -> Result<ListEnd, CommandError> {
+  match parse_string(frame)?.to_ascii_lowercase().as_str() {
+    "left" => Ok(ListEnd::Left),
+    "right" => Ok(ListEnd::Right),
+    _ => Err(CommandError::SyntaxError),
+  }
+}
+
+impl CommandExecutor for LMove {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.lmove(&self.source, &self.destination, self.from, self.to) {
+      Ok(Some(value)) => value,
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LMove {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next(), args.next()) {
+      (Some(source), Some(destination), Some(from), Some(to)) => Ok(LMove {
+        source: parse_string(source)?,
+        destination: parse_string(destination)?,
+        from: parse_list_end(from)?,
+        to: parse_list_end(to)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid source or destination".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_lmove_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*5\r\n$5\r\nLMOVE\r\n$6\r\nmylist\r\n$11\r\nmyotherlist\r\n$5\r\nRIGHT\r\n$4\r\nleft\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LMove = frame.try_into()?;
+    assert_eq!(ret.source, "mylist");
+    assert_eq!(ret.destination, "myotherlist");
+    assert_eq!(ret.from, ListEnd::Right);
+    assert_eq!(ret.to, ListEnd::Left);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lmove_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = ["one", "two", "three"].map(|v| BulkString::from(v).into()).to_vec();
+    backend.push("mylist".to_string(), values, ListEnd::Right, false)?;
+    let lmove = |from, to| LMove {
+      source: "mylist".to_string(),
+      destination: "myotherlist".to_string(),
+      from,
+      to,
+    };
+
+    let ret = lmove(ListEnd::Right, ListEnd::Left).execute(&backend);
+    assert_eq!(ret, BulkString::from("three").into());
+    let ret = lmove(ListEnd::Left, ListEnd::Right).execute(&backend);
+    assert_eq!(ret, BulkString::from("one").into());
+    assert_eq!(
+      backend.lrange("myotherlist", 0, -1)?,
+      vec![BulkString::from("three").into(), BulkString::from("one").into()]
+    );
+
+    Ok(())
+  }
+}
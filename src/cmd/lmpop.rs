@@ -0,0 +1,171 @@
+use super::{
+  extract_args, extract_command_name, lmove::parse_list_end, parse_integer, parse_string,
+  parse_timeout, CommandError, CommandExecutor, RESP_NULL,
+};
+use crate::{Backend, BulkString, ListEnd, RespArray, RespFrame};
+use std::time::Duration;
+
+/// LMPOP numkeys key [key ...] <LEFT | RIGHT> [COUNT count]
+/// BLMPOP timeout numkeys key [key ...] <LEFT | RIGHT> [COUNT count]
+///  LPUSH mylist "one" "two" "three" "four" "five"
+/// (integer) 5
+///  LMPOP 2 non1 mylist LEFT COUNT 2
+/// 1) "mylist"
+/// 2) 1) "five"
+///    2) "four"
+#[derive(Debug)]
+pub struct LMPop {
+  pub(crate) keys: Vec<String>,
+  pub(crate) end: ListEnd,
+  pub(crate) count: usize,
+  // only BLMPOP waits for a push, `timeout` is meaningless otherwise
+  pub(crate) blocking: bool,
+  pub(crate) timeout: Option<Duration>,
+}
+
+impl LMPop {
+  /// Pop from the first non-empty key, for BLMPOP waiting for a push if they are all empty.
+  pub async fn run(self, backend: &Backend) -> RespFrame {
+    if !self.blocking {
+      return backend.locked(false, || self.execute(backend));
+    }
+    let (end, count) = (self.end, self.count);
+    let serve = move |backend: &Backend, key: &str| pop(backend, key, end, count);
+    backend.block_on(self.keys, self.timeout, false, serve).await.unwrap_or(RESP_NULL.clone())
+  }
+}
+
+fn pop(backend: &Backend, key: &str, end: ListEnd, count: usize) -> Option<RespFrame> {
+  match backend.pop(key, end, count) {
+    Ok(Some(values)) => {
+      Some(RespArray::new([BulkString::from(key).into(), RespArray::new(values).into()]).into())
+    }
+    Ok(None) => None,
+    Err(e) => Some(e.into()),
+  }
+}
+
+impl CommandExecutor for LMPop {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ret = self.keys.iter().find_map(|key| pop(backend, key, self.end, self.count));
+    ret.unwrap_or(RESP_NULL.clone())
+  }
+}
+
+impl TryFrom<RespArray> for LMPop {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let blocking = extract_command_name(&value)? == "blmpop";
+    let mut args = extract_args(value, 1)?.into_iter();
+    let timeout = match blocking {
+      true => parse_timeout(args.next().ok_or(CommandError::SyntaxError)?)?,
+      false => None,
+    };
+
+    let numkeys = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+    if numkeys <= 0 {
+      return Err(CommandError::InvalidArgument("numkeys should be greater than 0".to_string()));
+    }
+    let keys =
+      args.by_ref().take(numkeys as usize).map(parse_string).collect::<Result<Vec<_>, _>>()?;
+    if keys.len() < numkeys as usize {
+      return Err(CommandError::SyntaxError);
+    }
+    let end = parse_list_end(args.next().ok_or(CommandError::SyntaxError)?)?;
+
+    let count = match (args.next(), args.next(), args.next()) {
+      (None, _, _) => 1,
+      (Some(opt), Some(count), None) => {
+        if !parse_string(opt)?.eq_ignore_ascii_case("count") {
+          return Err(CommandError::SyntaxError);
+        }
+        match parse_integer(count)? {
+          count if count > 0 => count as usize,
+          _ => {
+            return Err(CommandError::InvalidArgument("count should be greater than 0".to_string()))
+          }
+        }
+      }
+      _ => return Err(CommandError::SyntaxError),
+    };
+
+    Ok(LMPop { keys, end, count, blocking, timeout })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_lmpop_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*7\r\n$5\r\nLMPOP\r\n$1\r\n2\r\n$4\r\nnon1\r\n$6\r\nmylist\r\n$4\r\nLEFT\r\n$5\r\nCOUNT\r\n$1\r\n2\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LMPop = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["non1", "mylist"]);
+    assert_eq!(ret.end, ListEnd::Left);
+    assert_eq!(ret.count, 2);
+    assert!(!ret.blocking);
+
+    buf.extend_from_slice(
+      b"*5\r\n$6\r\nblmpop\r\n$3\r\n0.1\r\n$1\r\n1\r\n$6\r\nmylist\r\n$5\r\nright\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LMPop = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["mylist"]);
+    assert_eq!(ret.count, 1);
+    assert!(ret.blocking);
+    assert_eq!(ret.timeout, Some(Duration::from_millis(100)));
+
+    buf.extend_from_slice(b"*4\r\n$5\r\nlmpop\r\n$1\r\n2\r\n$6\r\nmylist\r\n$4\r\nLEFT\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(LMPop::try_from(frame), Err(CommandError::SyntaxError)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lmpop_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = ["one", "two", "three"].map(|v| BulkString::from(v).into()).to_vec();
+    backend.push("mylist".to_string(), values, ListEnd::Left, false)?;
+    let lmpop = || LMPop {
+      keys: vec!["non1".to_string(), "mylist".to_string()],
+      end: ListEnd::Left,
+      count: 2,
+      blocking: false,
+      timeout: None,
+    };
+
+    let ret = lmpop().execute(&backend);
+    let values = RespArray::new([BulkString::from("three").into(), BulkString::from("two").into()]);
+    assert_eq!(ret, RespArray::new([BulkString::from("mylist").into(), values.into()]).into());
+
+    lmpop().execute(&backend);
+    assert_eq!(lmpop().execute(&backend), RESP_NULL.clone());
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_lmpop_run_blocking() -> Result<()> {
+    let backend = Backend::new();
+    let lmpop = LMPop {
+      keys: vec!["mylist".to_string()],
+      end: ListEnd::Right,
+      count: 10,
+      blocking: true,
+      timeout: Some(Duration::from_millis(10)),
+    };
+    assert_eq!(lmpop.run(&backend).await, RESP_NULL.clone());
+
+    Ok(())
+  }
+}
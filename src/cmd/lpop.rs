@@ -0,0 +1,101 @@
+use super::{
+  extract_args, extract_command_name, parse_integer, parse_string, CommandError, CommandExecutor,
+  RESP_NULL,
+};
+use crate::{Backend, ListEnd, RespArray, RespFrame};
+
+/// LPOP key [count]
+/// RPOP key [count]
+///  RPUSH mylist "one" "two" "three" "four" "five"
+/// (integer) 5
+///  LPOP mylist
+/// "one"
+///  LPOP mylist 2
+/// 1) "two"
+/// 2) "three"
+#[derive(Debug)]
+pub struct LPop {
+  pub(crate) key: String,
+  pub(crate) end: ListEnd,
+  pub(crate) count: Option<usize>,
+}
+
+impl CommandExecutor for LPop {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.pop(&self.key, self.end, self.count.unwrap_or(1)) {
+      Ok(Some(values)) if self.count.is_some() => RespArray::new(values).into(),
+      Ok(Some(mut values)) => values.pop().unwrap_or(RESP_NULL.clone()),
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LPop {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let end = match extract_command_name(&value)?.as_str() {
+      "rpop" => ListEnd::Right,
+      _ => ListEnd::Left,
+    };
+
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let count = match args.next().map(parse_integer).transpose()? {
+      Some(count) if count < 0 => return Err(CommandError::NotPositive),
+      count => count.map(|count| count as usize),
+    };
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(LPop { key, end, count })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_lpop_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$4\r\nrpop\r\n$6\r\nmylist\r\n$1\r\n2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LPop = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+    assert_eq!(ret.end, ListEnd::Right);
+    assert_eq!(ret.count, Some(2));
+
+    buf.extend_from_slice(b"*3\r\n$4\r\nlpop\r\n$6\r\nmylist\r\n$2\r\n-1\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(LPop::try_from(frame), Err(CommandError::NotPositive)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lpop_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = ["one", "two", "three"].map(|v| BulkString::from(v).into()).to_vec();
+    backend.push("mylist".to_string(), values, ListEnd::Right, false)?;
+
+    let ret = LPop { key: "mylist".to_string(), end: ListEnd::Left, count: None }.execute(&backend);
+    assert_eq!(ret, BulkString::from("one").into());
+
+    let ret =
+      LPop { key: "mylist".to_string(), end: ListEnd::Right, count: Some(5) }.execute(&backend);
+    assert_eq!(
+      ret,
+      RespArray::new([BulkString::from("three").into(), BulkString::from("two").into()]).into()
+    );
+
+    let ret = LPop { key: "mylist".to_string(), end: ListEnd::Left, count: None }.execute(&backend);
+    assert_eq!(ret, RESP_NULL.clone());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,137 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, LPosOptions, RespArray, RespFrame};
+
+/// LPOS key element [RANK rank] [COUNT num-matches] [MAXLEN len]
+///  RPUSH mylist a b c d 1 2 3 4 3 3 3
+/// (integer) 11
+///  LPOS mylist 3
+/// (integer) 6
+///  LPOS mylist 3 COUNT 0 RANK 2
+/// 1) (integer) 8
+/// 2) (integer) 9
+/// 3) (integer) 10
+#[derive(Debug)]
+pub struct LPos {
+  pub(crate) key: String,
+  pub(crate) element: RespFrame,
+  pub(crate) opts: LPosOptions,
+  // with COUNT the reply is an array even for a single match
+  pub(crate) with_count: bool,
+}
+
+impl CommandExecutor for LPos {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.lpos(&self.key, &self.element, self.opts) {
+      Ok(positions) if self.with_count => RespArray::new(
+        positions.into_iter().map(|i| RespFrame::Integer(i as i64)).collect::<Vec<_>>(),
+      )
+      .into(),
+      Ok(positions) => match positions.first() {
+        Some(i) => RespFrame::Integer(*i as i64),
+        None => RESP_NULL.clone(),
+      },
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LPos {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, element) = match (args.next(), args.next()) {
+      (Some(key), Some(element)) => (parse_string(key)?, element),
+      _ => return Err(CommandError::InvalidArgument("Invalid key or element".to_string())),
+    };
+
+    let mut lpos = LPos { key, element, opts: LPosOptions::default(), with_count: false };
+    while let Some(arg) = args.next() {
+      let opt = parse_string(arg)?.to_ascii_lowercase();
+      let n = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+      match opt.as_str() {
+        "rank" if n == 0 || n == i64::MIN => {
+          return Err(CommandError::InvalidArgument(
+            "RANK can't be zero: use 1 to start from the first match, 2 from the second ... \
+             or use negative to start from the end of the list"
+              .to_string(),
+          ))
+        }
+        "rank" => lpos.opts.rank = n,
+        "count" if n < 0 => {
+          return Err(CommandError::InvalidArgument("COUNT can't be negative".to_string()))
+        }
+        "count" => {
+          lpos.opts.count = n as usize;
+          lpos.with_count = true;
+        }
+        "maxlen" if n < 0 => {
+          return Err(CommandError::InvalidArgument("MAXLEN can't be negative".to_string()))
+        }
+        "maxlen" => lpos.opts.max_len = n as usize,
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+
+    Ok(lpos)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, ListEnd, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_lpos_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*9\r\n$4\r\nLPOS\r\n$6\r\nmylist\r\n$1\r\n3\r\n$5\r\nCOUNT\r\n$1\r\n0\r\n$4\r\nRANK\r\n$2\r\n-2\r\n$6\r\nMAXLEN\r\n$2\r\n10\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LPos = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+    assert_eq!(ret.element, BulkString::from("3").into());
+    assert_eq!(ret.opts, LPosOptions { rank: -2, count: 0, max_len: 10 });
+    assert!(ret.with_count);
+
+    buf.extend_from_slice(
+      b"*5\r\n$4\r\nLPOS\r\n$6\r\nmylist\r\n$1\r\n3\r\n$4\r\nRANK\r\n$1\r\n0\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(LPos::try_from(frame).is_err());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lpos_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = ["a", "b", "c", "d", "1", "2", "3", "4", "3", "3", "3"]
+      .map(|v| BulkString::from(v).into())
+      .to_vec();
+    backend.push("mylist".to_string(), values, ListEnd::Right, false)?;
+    let lpos = |opts, with_count| LPos {
+      key: "mylist".to_string(),
+      element: BulkString::from("3").into(),
+      opts,
+      with_count,
+    };
+
+    let ret = lpos(LPosOptions::default(), false).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(6));
+
+    let ret = lpos(LPosOptions { rank: 2, count: 0, max_len: 0 }, true).execute(&backend);
+    assert_eq!(
+      ret,
+      RespArray::new([RespFrame::Integer(8), RespFrame::Integer(9), RespFrame::Integer(10)]).into()
+    );
+
+    let ret = lpos(LPosOptions { rank: 1, count: 1, max_len: 5 }, false).execute(&backend);
+    assert_eq!(ret, RESP_NULL.clone());
+
+    Ok(())
+  }
+}
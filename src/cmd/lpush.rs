@@ -0,0 +1,99 @@
+use super::{extract_args, extract_command_name, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, ListEnd, RespArray, RespFrame};
+
+/// LPUSH key element [element ...]
+/// RPUSH key element [element ...]
+/// LPUSHX key element [element ...]
+/// RPUSHX key element [element ...]
+///  LPUSH mylist "world"
+/// (integer) 1
+///  LPUSH mylist "hello"
+/// (integer) 2
+///  LRANGE mylist 0 -1
+/// 1) "hello"
+/// 2) "world"
+#[derive(Debug)]
+pub struct LPush {
+  pub(crate) key: String,
+  pub(crate) values: Vec<RespFrame>,
+  pub(crate) end: ListEnd,
+  // the X variants only push to lists that already exist
+  pub(crate) only_if_exists: bool,
+}
+
+impl CommandExecutor for LPush {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.push(self.key, self.values, self.end, self.only_if_exists) {
+      Ok(len) => RespFrame::Integer(len as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LPush {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let (end, only_if_exists) = match extract_command_name(&value)?.as_str() {
+      "lpush" => (ListEnd::Left, false),
+      "rpush" => (ListEnd::Right, false),
+      "lpushx" => (ListEnd::Left, true),
+      "rpushx" => (ListEnd::Right, true),
+      name => return Err(CommandError::InvalidCommand(format!("unexpected command {}", name))),
+    };
+
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let values: Vec<RespFrame> = args.collect();
+    if values.is_empty() {
+      return Err(CommandError::InvalidArgument("Invalid element".to_string()));
+    }
+    Ok(LPush { key, values, end, only_if_exists })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_lpush_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$6\r\nRPUSHX\r\n$6\r\nmylist\r\n$5\r\nhello\r\n$5\r\nworld\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LPush = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+    assert_eq!(
+      ret.values,
+      vec![BulkString::from("hello").into(), BulkString::from("world").into()]
+    );
+    assert_eq!(ret.end, ListEnd::Right);
+    assert!(ret.only_if_exists);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lpush_execute() -> Result<()> {
+    let backend = Backend::new();
+    let push = |value: &str, only_if_exists| LPush {
+      key: "mylist".to_string(),
+      values: vec![BulkString::from(value).into()],
+      end: ListEnd::Left,
+      only_if_exists,
+    };
+
+    assert_eq!(push("world", true).execute(&backend), RespFrame::Integer(0));
+    assert_eq!(push("world", false).execute(&backend), RespFrame::Integer(1));
+    assert_eq!(push("hello", true).execute(&backend), RespFrame::Integer(2));
+    assert_eq!(
+      backend.lrange("mylist", 0, -1)?,
+      vec![BulkString::from("hello").into(), BulkString::from("world").into()]
+    );
+
+    Ok(())
+  }
+}
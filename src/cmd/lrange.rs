@@ -0,0 +1,79 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// LRANGE key start stop
+///  RPUSH mylist "one" "two" "three"
+/// (integer) 3
+///  LRANGE mylist 5 10
+/// (empty array)
+///  LRANGE mylist -3 2
+/// 1) "one"
+/// 2) "two"
+/// 3) "three"
+#[derive(Debug)]
+pub struct LRange {
+  pub(crate) key: String,
+  pub(crate) start: i64,
+  pub(crate) stop: i64,
+}
+
+impl CommandExecutor for LRange {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.lrange(&self.key, self.start, self.stop) {
+      Ok(values) => RespArray::new(values).into(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LRange {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(start), Some(stop)) => Ok(LRange {
+        key: parse_string(key)?,
+        start: parse_integer(start)?,
+        stop: parse_integer(stop)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid key, start or stop".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, ListEnd, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_lrange_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$6\r\nlrange\r\n$6\r\nmylist\r\n$2\r\n-3\r\n$1\r\n2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LRange = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+    assert_eq!(ret.start, -3);
+    assert_eq!(ret.stop, 2);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lrange_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = ["one", "two", "three"].map(|v| BulkString::from(v).into()).to_vec();
+    backend.push("mylist".to_string(), values, ListEnd::Right, false)?;
+
+    let ret = LRange { key: "mylist".to_string(), start: 0, stop: 0 }.execute(&backend);
+    assert_eq!(ret, RespArray::new([BulkString::from("one").into()]).into());
+
+    let ret = LRange { key: "mylist".to_string(), start: 5, stop: 10 }.execute(&backend);
+    assert_eq!(ret, RespArray::new([]).into());
+
+    Ok(())
+  }
+}
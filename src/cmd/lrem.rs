@@ -0,0 +1,79 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// LREM key count element
+///  RPUSH mylist "hello" "hello" "foo" "hello"
+/// (integer) 4
+///  LREM mylist -2 "hello"
+/// (integer) 2
+///  LRANGE mylist 0 -1
+/// 1) "hello"
+/// 2) "foo"
+#[derive(Debug)]
+pub struct LRem {
+  pub(crate) key: String,
+  pub(crate) count: i64,
+  pub(crate) value: RespFrame,
+}
+
+impl CommandExecutor for LRem {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.lrem(&self.key, self.count, &self.value) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LRem {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(count), Some(value)) => {
+        Ok(LRem { key: parse_string(key)?, count: parse_integer(count)?, value })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key, count or element".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, ListEnd, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_lrem_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$4\r\nLREM\r\n$6\r\nmylist\r\n$2\r\n-2\r\n$5\r\nhello\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LRem = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+    assert_eq!(ret.count, -2);
+    assert_eq!(ret.value, BulkString::from("hello").into());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lrem_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = ["hello", "hello", "foo", "hello"].map(|v| BulkString::from(v).into()).to_vec();
+    backend.push("mylist".to_string(), values, ListEnd::Right, false)?;
+
+    let ret =
+      LRem { key: "mylist".to_string(), count: -2, value: BulkString::from("hello").into() }
+        .execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+    assert_eq!(
+      backend.lrange("mylist", 0, -1)?,
+      vec![BulkString::from("hello").into(), BulkString::from("foo").into()]
+    );
+
+    Ok(())
+  }
+}
@@ -0,0 +1,79 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor, RESP_OK};
+use crate::{Backend, RespArray, RespFrame};
+
+/// LSET key index element
+///  RPUSH mylist "one" "two" "three"
+/// (integer) 3
+///  LSET mylist 0 "four"
+/// "OK"
+///  LSET mylist -2 "five"
+/// "OK"
+#[derive(Debug)]
+pub struct LSet {
+  pub(crate) key: String,
+  pub(crate) index: i64,
+  pub(crate) value: RespFrame,
+}
+
+impl CommandExecutor for LSet {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.lset(&self.key, self.index, self.value) {
+      Ok(()) => RESP_OK.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LSet {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(index), Some(value)) => {
+        Ok(LSet { key: parse_string(key)?, index: parse_integer(index)?, value })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key, index or element".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, ListEnd, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_lset_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$4\r\nlset\r\n$6\r\nmylist\r\n$2\r\n-2\r\n$4\r\nfive\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LSet = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+    assert_eq!(ret.index, -2);
+    assert_eq!(ret.value, BulkString::from("five").into());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_lset_execute() -> Result<()> {
+    let backend = Backend::new();
+    let lset = |index| LSet { key: "mylist".to_string(), index, value: RespFrame::Integer(4) };
+
+    assert_eq!(lset(0).execute(&backend), CommandError::NoSuchKey.into());
+
+    let values = vec![RespFrame::Integer(1), RespFrame::Integer(2)];
+    backend.push("mylist".to_string(), values, ListEnd::Right, false)?;
+    assert_eq!(lset(-1).execute(&backend), RESP_OK.clone());
+    assert_eq!(lset(2).execute(&backend), CommandError::IndexOutOfRange.into());
+    assert_eq!(
+      backend.lrange("mylist", 0, -1)?,
+      vec![RespFrame::Integer(1), RespFrame::Integer(4)]
+    );
+
+    Ok(())
+  }
+}
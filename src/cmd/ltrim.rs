@@ -0,0 +1,79 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor, RESP_OK};
+use crate::{Backend, RespArray, RespFrame};
+
+/// LTRIM key start stop
+///  RPUSH mylist "one" "two" "three"
+/// (integer) 3
+///  LTRIM mylist 1 -1
+/// "OK"
+///  LRANGE mylist 0 -1
+/// 1) "two"
+/// 2) "three"
+#[derive(Debug)]
+pub struct LTrim {
+  pub(crate) key: String,
+  pub(crate) start: i64,
+  pub(crate) stop: i64,
+}
+
+impl CommandExecutor for LTrim {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.ltrim(&self.key, self.start, self.stop) {
+      Ok(()) => RESP_OK.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for LTrim {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(start), Some(stop)) => Ok(LTrim {
+        key: parse_string(key)?,
+        start: parse_integer(start)?,
+        stop: parse_integer(stop)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid key, start or stop".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, ListEnd, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_ltrim_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$5\r\nltrim\r\n$6\r\nmylist\r\n$1\r\n1\r\n$2\r\n-1\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: LTrim = frame.try_into()?;
+    assert_eq!(ret.key, "mylist");
+    assert_eq!(ret.start, 1);
+    assert_eq!(ret.stop, -1);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_ltrim_execute() -> Result<()> {
+    let backend = Backend::new();
+    let values = ["one", "two", "three"].map(|v| BulkString::from(v).into()).to_vec();
+    backend.push("mylist".to_string(), values, ListEnd::Right, false)?;
+
+    let ret = LTrim { key: "mylist".to_string(), start: 1, stop: -1 }.execute(&backend);
+    assert_eq!(ret, RESP_OK.clone());
+    assert_eq!(
+      backend.lrange("mylist", 0, -1)?,
+      vec![BulkString::from("two").into(), BulkString::from("three").into()]
+    );
+
+    Ok(())
+  }
+}
@@ -0,0 +1,75 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, RespArray, RespFrame};
+
+/// MGET key [key ...]
+///  SET key1 "Hello"
+/// "OK"
+///  SET key2 "World"
+/// "OK"
+///  MGET key1 key2 nonexisting
+/// 1) "Hello"
+/// 2) "World"
+/// 3) (nil)
+#[derive(Debug)]
+pub struct MGet {
+  pub(crate) keys: Vec<String>,
+}
+
+impl CommandExecutor for MGet {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    // keys holding another type read as missing instead of failing the whole command
+    let values = self
+      .keys
+      .iter()
+      .map(|key| backend.get(key).ok().flatten().unwrap_or(RESP_NULL.clone()))
+      .collect::<Vec<_>>();
+    RespArray::new(values).into()
+  }
+}
+
+impl TryFrom<RespArray> for MGet {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let keys =
+      extract_args(value, 1)?.into_iter().map(parse_string).collect::<Result<Vec<String>, _>>()?;
+    Ok(MGet { keys })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_mget_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$4\r\nMGET\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: MGet = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["key1".to_string(), "key2".to_string()]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_mget_execute() {
+    let backend = Backend::new();
+    backend.set("key1".to_string(), BulkString::from("Hello").into());
+    backend.set("key2".to_string(), BulkString::from("World").into());
+    backend.sadd("myset", "a").unwrap();
+
+    let keys = ["key1", "key2", "nonexisting", "myset"].map(String::from).to_vec();
+    let ret = MGet { keys }.execute(&backend);
+    let expected = RespArray::new([
+      BulkString::from("Hello").into(),
+      BulkString::from("World").into(),
+      RESP_NULL.clone(),
+      RESP_NULL.clone(),
+    ]);
+    assert_eq!(ret, expected.into());
+  }
+}
@@ -1,22 +1,140 @@
+mod append;
+mod bitcount;
+mod bitfield;
+mod bitop;
+mod bitpos;
+mod blmove;
+mod blpop;
+mod bzpopmin;
+mod copy;
+mod del;
 mod echo;
+mod exists;
+mod expire;
+mod expiretime;
 mod get;
+mod getbit;
+mod getdel;
+mod getex;
+mod getrange;
+mod getset;
+mod glob;
+mod hdel;
+mod hexists;
 mod hget;
 mod hgetall;
+mod hincrby;
+mod hincrbyfloat;
+mod hkeys;
+mod hlen;
 mod hmget;
+mod hrandfield;
+mod hscan;
 mod hset;
+mod hsetnx;
+mod hstrlen;
+mod hvals;
+mod incr;
+mod incrbyfloat;
+mod keys;
+mod lcs;
+mod lindex;
+mod linsert;
+mod llen;
+mod lmove;
+mod lmpop;
+mod lpop;
+mod lpos;
+mod lpush;
+mod lrange;
+mod lrem;
+mod lset;
+mod ltrim;
+mod mget;
+mod mset;
+mod persist;
+mod pfadd;
+mod pfcount;
+mod pfmerge;
+mod rename;
 mod sadd;
+mod scan;
+mod scard;
 mod set;
+mod setbit;
+mod setrange;
+mod sintercard;
 mod sismember;
 mod smembers;
+mod smismember;
+mod smove;
+mod spop;
+mod srandmember;
+mod srem;
+mod sscan;
+mod strlen;
+mod sunion;
+mod table;
+mod ttl;
+mod type_;
 mod unrecognized;
+mod xack;
+mod xadd;
+mod xautoclaim;
+mod xclaim;
+mod xdel;
+mod xgroup;
+mod xinfo;
+mod xlen;
+mod xpending;
+mod xrange;
+mod xread;
+mod xreadgroup;
+mod xtrim;
+mod zadd;
+mod zcard;
+mod zcount;
+mod zincrby;
+mod zintercard;
+mod zmpop;
+mod zmscore;
+mod zpopmin;
+mod zrange;
+mod zrangestore;
+mod zrank;
+mod zrem;
+mod zremrange;
+mod zscore;
+mod zunion;
 
 pub use self::{
-  echo::Echo, get::Get, hget::HGet, hgetall::HGetAll, hmget::HMGet, hset::HSet, sadd::SAdd,
-  set::Set, sismember::SIsMember, smembers::SMembers, unrecognized::Unrecognized,
+  append::Append, bitcount::BitCount, bitfield::BitField, bitop::BitOp, bitpos::BitPos,
+  blmove::BLMove, blpop::BLPop, bzpopmin::BZPopMin, copy::CopyKey, del::Del, echo::Echo,
+  exists::Exists, expire::Expire, expiretime::ExpireTime, get::Get, getbit::GetBit, getdel::GetDel,
+  getex::GetEx, getrange::GetRange, getset::GetSet, hdel::HDel, hexists::HExists, hget::HGet,
+  hgetall::HGetAll, hincrby::HIncrBy, hincrbyfloat::HIncrByFloat, hkeys::HKeys, hlen::HLen,
+  hmget::HMGet, hrandfield::HRandField, hscan::HScan, hset::HSet, hsetnx::HSetNx, hstrlen::HStrLen,
+  hvals::HVals, incr::Incr, incrbyfloat::IncrByFloat, keys::Keys, lcs::Lcs, lindex::LIndex,
+  linsert::LInsert, llen::LLen, lmove::LMove, lmpop::LMPop, lpop::LPop, lpos::LPos, lpush::LPush,
+  lrange::LRange, lrem::LRem, lset::LSet, ltrim::LTrim, mget::MGet, mset::MSet, persist::Persist,
+  pfadd::PFAdd, pfcount::PFCount, pfmerge::PFMerge, rename::Rename, sadd::SAdd, scan::Scan,
+  scard::SCard, set::Set, setbit::SetBit, setrange::SetRange, sintercard::SInterCard,
+  sismember::SIsMember, smembers::SMembers, smismember::SMIsMember, smove::SMove, spop::SPop,
+  srandmember::SRandMember, srem::SRem, sscan::SScan, strlen::StrLen, sunion::SUnion,
+  table::lookup_command, table::CommandSpec, ttl::Ttl, type_::Type, unrecognized::Unrecognized,
+  xack::XAck, xadd::XAdd, xautoclaim::XAutoClaim, xclaim::XClaim, xdel::XDel, xgroup::XGroup,
+  xinfo::XInfo, xlen::XLen, xpending::XPending, xrange::XRange, xread::XRead,
+  xreadgroup::XReadGroup, xtrim::XTrim, zadd::ZAdd, zcard::ZCard, zcount::ZCount, zincrby::ZIncrBy,
+  zintercard::ZInterCard, zmpop::ZMPop, zmscore::ZMScore, zpopmin::ZPopMin, zrange::ZRange,
+  zrangestore::ZRangeStore, zrank::ZRank, zrem::ZRem, zremrange::ZRemRange, zscore::ZScore,
+  zunion::ZUnion,
+};
+use crate::{
+  Backend, RespArray, RespError, RespFrame, RespNull, SimpleError, SimpleString, StreamId,
 };
-use crate::{Backend, RespArray, RespError, RespFrame, RespNull, SimpleString};
 use enum_dispatch::enum_dispatch;
 use lazy_static::lazy_static;
+use std::time::Duration;
 use thiserror::Error;
 
 lazy_static! {
@@ -24,16 +142,95 @@ lazy_static! {
   static ref RESP_NULL: RespFrame = RespFrame::Null(RespNull);
 }
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq)]
 pub enum CommandError {
   #[error("Invalid command: {0}")]
   InvalidCommand(String),
-  #[error("Invalid argument: {0}")]
+  #[error("{0}")]
   InvalidArgument(String),
   #[error("Resp error: {0}")]
   RespError(#[from] RespError),
   #[error("UTF-8 error: {0}")]
   Utf8Error(#[from] std::string::FromUtf8Error),
+  #[error("wrong number of arguments for '{0}' command")]
+  WrongArity(String),
+  #[error("syntax error")]
+  SyntaxError,
+  #[error("index out of range")]
+  IndexOutOfRange,
+  #[error("value is out of range, must be positive")]
+  NotPositive,
+  #[error("invalid cursor")]
+  InvalidCursor,
+  #[error("no such key")]
+  NoSuchKey,
+  #[error("value is not an integer or out of range")]
+  NotInteger,
+  #[error("timeout is not a float or out of range")]
+  InvalidTimeout,
+  #[error("timeout is negative")]
+  NegativeTimeout,
+  #[error("value is not a valid float")]
+  NotFloat,
+  #[error("resulting score is not a number (NaN)")]
+  NotANumber,
+  #[error("min or max is not a float")]
+  InvalidScoreRange,
+  #[error("min or max not valid string range item")]
+  InvalidLexRange,
+  #[error("bit offset is not an integer or out of range")]
+  InvalidBitOffset,
+  #[error("invalid expire time in '{0}' command")]
+  InvalidExpireTime(String),
+  #[error("Invalid stream ID specified as stream command argument")]
+  InvalidStreamId,
+  #[error("The ID specified in XADD is equal or smaller than the target stream top item")]
+  StreamIdTooSmall,
+  #[error("The ID specified in XADD must be greater than 0-0")]
+  StreamIdZero,
+  #[error("The stream has exhausted the last possible ID, unable to add more items")]
+  StreamExhausted,
+  #[error(
+    "The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use \
+     the MKSTREAM option to create an empty stream automatically."
+  )]
+  StreamKeyMissing,
+  #[error("Consumer Group name already exists")]
+  BusyGroup,
+  #[error("No such key '{0}' or consumer group '{1}'")]
+  NoGroup(String, String),
+  #[error("Key is not a valid HyperLogLog string value.")]
+  InvalidHll,
+  #[error("Corrupted HLL object detected")]
+  CorruptedHll,
+  #[error("Operation against a key holding the wrong kind of value")]
+  WrongType,
+}
+
+impl CommandError {
+  /// The error code a client sees in front of the message, e.g. `-ERR ...`.
+  pub fn code(&self) -> &'static str {
+    match self {
+      CommandError::WrongType | CommandError::InvalidHll => "WRONGTYPE",
+      CommandError::CorruptedHll => "INVALIDOBJ",
+      CommandError::BusyGroup => "BUSYGROUP",
+      CommandError::NoGroup(..) => "NOGROUP",
+      _ => "ERR",
+    }
+  }
+}
+
+/// An error reply, with line breaks turned into spaces since a simple error cannot hold them
+/// and a client argument echoed in the message would otherwise inject extra replies.
+pub(crate) fn error_reply(msg: impl AsRef<str>) -> RespFrame {
+  SimpleError::new(msg.as_ref().replace(['\r', '\n'], " ")).into()
+}
+
+impl From<CommandError> for RespFrame {
+  fn from(e: CommandError) -> Self {
+    // messages like NOGROUP's echo the key and group names the client sent
+    error_reply(format!("{} {}", e.code(), e))
+  }
 }
 
 #[enum_dispatch]
@@ -54,10 +251,146 @@ pub enum Command {
   SADD(SAdd),
   SMEMBERS(SMembers),
   SISMEMBER(SIsMember),
+  Expire(Expire),
+  Ttl(Ttl),
+  ExpireTime(ExpireTime),
+  Persist(Persist),
+  Type(Type),
+  Del(Del),
+  Exists(Exists),
+  Rename(Rename),
+  Copy(CopyKey),
+  Scan(Scan),
+  HScan(HScan),
+  SScan(SScan),
+  Keys(Keys),
+  LPush(LPush),
+  LPop(LPop),
+  LLen(LLen),
+  LRange(LRange),
+  LIndex(LIndex),
+  LSet(LSet),
+  LRem(LRem),
+  LTrim(LTrim),
+  LInsert(LInsert),
+  LPos(LPos),
+  LMove(LMove),
+  LMPop(LMPop),
+  BLPop(BLPop),
+  BLMove(BLMove),
+  ZAdd(ZAdd),
+  ZRange(ZRange),
+  ZRank(ZRank),
+  ZScore(ZScore),
+  ZMScore(ZMScore),
+  ZIncrBy(ZIncrBy),
+  ZRem(ZRem),
+  ZCard(ZCard),
+  ZCount(ZCount),
+  ZUnion(ZUnion),
+  ZInterCard(ZInterCard),
+  ZRangeStore(ZRangeStore),
+  ZRemRange(ZRemRange),
+  ZPopMin(ZPopMin),
+  ZMPop(ZMPop),
+  BZPopMin(BZPopMin),
+  Incr(Incr),
+  IncrByFloat(IncrByFloat),
+  Append(Append),
+  StrLen(StrLen),
+  GetRange(GetRange),
+  SetRange(SetRange),
+  GetDel(GetDel),
+  GetEx(GetEx),
+  GetSet(GetSet),
+  Lcs(Lcs),
+  MGet(MGet),
+  MSet(MSet),
+  SetBit(SetBit),
+  GetBit(GetBit),
+  BitCount(BitCount),
+  BitPos(BitPos),
+  BitOp(BitOp),
+  BitField(BitField),
+  HDel(HDel),
+  HExists(HExists),
+  HLen(HLen),
+  HKeys(HKeys),
+  HVals(HVals),
+  HIncrBy(HIncrBy),
+  HIncrByFloat(HIncrByFloat),
+  HSetNx(HSetNx),
+  HStrLen(HStrLen),
+  HRandField(HRandField),
+  SRandMember(SRandMember),
+  SPop(SPop),
+  SRem(SRem),
+  SCard(SCard),
+  SMIsMember(SMIsMember),
+  SMove(SMove),
+  SUnion(SUnion),
+  SInterCard(SInterCard),
+  XAdd(XAdd),
+  XRange(XRange),
+  XLen(XLen),
+  XDel(XDel),
+  XTrim(XTrim),
+  XGroup(XGroup),
+  XRead(XRead),
+  XReadGroup(XReadGroup),
+  XAck(XAck),
+  XPending(XPending),
+  XClaim(XClaim),
+  XAutoClaim(XAutoClaim),
+  XInfo(XInfo),
+  PFAdd(PFAdd),
+  PFCount(PFCount),
+  PFMerge(PFMerge),
 
   Unrecognized(Unrecognized),
 }
 
+impl Command {
+  /// Execute the command, parking the client first if it is a blocking one.
+  pub async fn run(self, backend: &Backend) -> RespFrame {
+    match self {
+      Command::BLPop(cmd) => cmd.run(backend).await,
+      Command::BLMove(cmd) => cmd.run(backend).await,
+      Command::LMPop(cmd) => cmd.run(backend).await,
+      Command::BZPopMin(cmd) => cmd.run(backend).await,
+      Command::ZMPop(cmd) => cmd.run(backend).await,
+      Command::XRead(cmd) if cmd.blocking => cmd.run(backend).await,
+      Command::XReadGroup(cmd) if cmd.blocking => cmd.run(backend).await,
+      cmd => backend.locked(cmd.is_multi_key(), || cmd.execute(backend)),
+    }
+  }
+
+  /// Whether the command reads or writes several keys, it then runs with every other command
+  /// shut out so it sees and updates all of them at once.
+  fn is_multi_key(&self) -> bool {
+    matches!(
+      self,
+      Command::Rename(_)
+        | Command::Copy(_)
+        | Command::LMove(_)
+        | Command::ZUnion(_)
+        | Command::ZInterCard(_)
+        | Command::ZRangeStore(_)
+        | Command::Lcs(_)
+        | Command::MGet(_)
+        | Command::MSet(_)
+        | Command::BitOp(_)
+        | Command::SMove(_)
+        | Command::SUnion(_)
+        | Command::SInterCard(_)
+        | Command::XRead(_)
+        | Command::XReadGroup(_)
+        | Command::PFCount(_)
+        | Command::PFMerge(_)
+    )
+  }
+}
+
 impl TryFrom<RespFrame> for Command {
   type Error = CommandError;
   fn try_from(v: RespFrame) -> Result<Self, Self::Error> {
@@ -71,61 +404,90 @@ impl TryFrom<RespFrame> for Command {
 impl TryFrom<RespArray> for Command {
   type Error = CommandError;
   fn try_from(v: RespArray) -> Result<Self, Self::Error> {
-    match &v.0 {
-      Some(frames) => match frames.first() {
-        Some(RespFrame::BulkString(ref cmd)) => match cmd.as_ref() {
-          b"get" => Ok(Get::try_from(v)?.into()),
-          b"set" => Ok(Set::try_from(v)?.into()),
-          b"hget" => Ok(HGet::try_from(v)?.into()),
-          b"hset" => Ok(HSet::try_from(v)?.into()),
-          b"hgetall" => Ok(HGetAll::try_from(v)?.into()),
-          b"echo" => Ok(Echo::try_from(v)?.into()),
-          b"hmget" => Ok(HMGet::try_from(v)?.into()),
-          b"sadd" => Ok(SAdd::try_from(v)?.into()),
-          b"smembers" => Ok(SMembers::try_from(v)?.into()),
-          b"sismember" => Ok(SIsMember::try_from(v)?.into()),
-          _ => Ok(Unrecognized.into()),
-        },
-        _ => Err(CommandError::InvalidCommand("command must be an RespFrame".to_string())),
+    let frames = match &v.0 {
+      Some(frames) => frames,
+      _ => return Err(CommandError::InvalidCommand("command must be an RespArray".to_string())),
+    };
+    let spec = match frames.first() {
+      Some(RespFrame::BulkString(ref cmd)) => match lookup_command(cmd.as_ref()) {
+        Some(spec) => spec,
+        None => return Ok(Unrecognized::try_from(v)?.into()),
       },
-      _ => Err(CommandError::InvalidCommand("command must be an RespArray".to_string())),
-    }
+      _ => return Err(CommandError::InvalidCommand("command must be an RespFrame".to_string())),
+    };
+    spec.check_arity(frames.len())?;
+    spec.parse(v)
   }
 }
-fn validate_command(
-  value: &RespArray,
-  names: &[&'static str],
-  n_arg: usize,
-) -> Result<(), CommandError> {
-  let frames = extract_resp_array(value.clone(), "Invalid command.")?;
-  if frames.len() < n_arg + names.len() {
-    return Err(CommandError::InvalidArgument(format!(
-      "expected at least {} arguments, got {}",
-      n_arg + names.len(),
-      frames.len()
-    )));
-  }
-
-  for (i, name) in names.iter().enumerate() {
-    match frames[i] {
-      RespFrame::BulkString(ref s) => {
-        let cmd: String = s.clone().into();
-        if cmd.as_bytes().to_ascii_lowercase() != name.as_bytes() {
-          return Err(CommandError::InvalidCommand(format!(
-            "expected command {}, got {}",
-            name, cmd
-          )));
-        }
-      }
-      _ => {
-        return Err(CommandError::InvalidCommand(
-          "command must have a BulkString as the first argument2".to_string(),
-        ))
-      }
+
+/// The lowercase name of the command carried by `value`.
+fn extract_command_name(value: &RespArray) -> Result<String, CommandError> {
+  match value.as_ref().and_then(|frames| frames.first()) {
+    Some(RespFrame::BulkString(name)) => {
+      Ok(String::from_utf8_lossy(name.as_ref()).to_ascii_lowercase())
     }
+    _ => Err(CommandError::InvalidCommand("command must have a name".to_string())),
+  }
+}
+
+fn parse_string(frame: RespFrame) -> Result<String, CommandError> {
+  match frame {
+    RespFrame::BulkString(s) => Ok(s.into()),
+    _ => Err(CommandError::InvalidArgument("expected a bulk string".to_string())),
+  }
+}
+
+/// Parse a value argument, keeping its raw bytes.
+fn parse_bytes(frame: RespFrame) -> Result<Vec<u8>, CommandError> {
+  match frame {
+    RespFrame::BulkString(s) => Ok(s.0.unwrap_or_default()),
+    _ => Err(CommandError::InvalidArgument("expected a bulk string".to_string())),
   }
+}
+
+fn parse_integer(frame: RespFrame) -> Result<i64, CommandError> {
+  match frame {
+    RespFrame::BulkString(s) => std::str::from_utf8(s.as_ref())
+      .ok()
+      .and_then(|s| s.parse().ok())
+      .ok_or(CommandError::NotInteger),
+    RespFrame::Integer(n) => Ok(n),
+    _ => Err(CommandError::NotInteger),
+  }
+}
+
+/// Parse a float argument such as a score, `inf` and `-inf` included but not NaN.
+fn parse_float(frame: RespFrame) -> Result<f64, CommandError> {
+  let n = match frame {
+    RespFrame::BulkString(s) => std::str::from_utf8(s.as_ref()).ok().and_then(|s| s.parse().ok()),
+    RespFrame::Integer(n) => Some(n as f64),
+    RespFrame::Double(n) => Some(n),
+    _ => None,
+  };
+  n.filter(|n: &f64| !n.is_nan()).ok_or(CommandError::NotFloat)
+}
+
+/// Parse a stream ID, a bare `<ms>` standing for `<ms>-0`.
+fn parse_stream_id(frame: RespFrame) -> Result<StreamId, CommandError> {
+  StreamId::parse(&parse_string(frame)?, 0)
+}
 
-  Ok(())
+/// Parse the timeout of a blocking command in seconds, `None` meaning block forever.
+fn parse_timeout(frame: RespFrame) -> Result<Option<Duration>, CommandError> {
+  let secs = match frame {
+    RespFrame::BulkString(s) => std::str::from_utf8(s.as_ref()).ok().and_then(|s| s.parse().ok()),
+    RespFrame::Integer(n) => Some(n as f64),
+    RespFrame::Double(n) => Some(n),
+    _ => None,
+  };
+  match secs.filter(|secs: &f64| secs.is_finite()) {
+    None => Err(CommandError::InvalidTimeout),
+    Some(secs) if secs < 0.0 => Err(CommandError::NegativeTimeout),
+    Some(0.0) => Ok(None),
+    Some(secs) => {
+      Duration::try_from_secs_f64(secs).map(Some).map_err(|_| CommandError::InvalidTimeout)
+    }
+  }
 }
 
 fn extract_args(value: RespArray, start: usize) -> Result<Vec<RespFrame>, CommandError> {
@@ -143,7 +505,7 @@ fn extract_resp_array(value: RespArray, err_msg: &str) -> Result<Vec<RespFrame>,
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::RespDecode;
+  use crate::{BulkString, RespDecode};
   use anyhow::Result;
   use bytes::BytesMut;
 
@@ -160,4 +522,76 @@ mod tests {
     assert_eq!(ret, RESP_NULL.clone());
     Ok(())
   }
+
+  #[test]
+  fn test_command_dispatch_ignores_case() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$3\r\nSET\r\n$5\r\nhello\r\n$5\r\nworld\r\n");
+    buf.extend_from_slice(b"*2\r\n$3\r\nGeT\r\n$5\r\nhello\r\n");
+
+    let backend = Backend::new();
+    let cmd: Command = RespArray::decode(&mut buf)?.try_into()?;
+    assert_eq!(cmd.execute(&backend), RESP_OK.clone());
+
+    let cmd: Command = RespArray::decode(&mut buf)?.try_into()?;
+    assert_eq!(cmd.execute(&backend), RespFrame::BulkString(b"world".into()));
+    Ok(())
+  }
+
+  #[test]
+  fn test_error_reply_strips_line_breaks() {
+    let e = CommandError::NoGroup("key\r\n".to_string(), "g\n+OK".to_string());
+    assert_eq!(
+      RespFrame::from(e),
+      SimpleError::new("NOGROUP No such key 'key  ' or consumer group 'g +OK'").into()
+    );
+  }
+
+  #[test]
+  fn test_command_unrecognized() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$3\r\nFOO\r\n$5\r\nhello\r\n");
+
+    let cmd: Command = RespArray::decode(&mut buf)?.try_into()?;
+    let ret = cmd.execute(&Backend::new());
+    assert_eq!(
+      ret,
+      SimpleError::new("ERR unknown command 'FOO', with args beginning with: 'hello' ").into()
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_command_wrong_arity() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*1\r\n$3\r\nGET\r\n");
+
+    let ret = Command::try_from(RespArray::decode(&mut buf)?);
+    assert!(matches!(ret, Err(CommandError::WrongArity(ref name)) if name == "get"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_parse_timeout() {
+    assert_eq!(parse_timeout(BulkString::from("0").into()), Ok(None));
+    assert_eq!(
+      parse_timeout(BulkString::from("0.25").into()),
+      Ok(Some(Duration::from_millis(250)))
+    );
+    assert_eq!(parse_timeout(BulkString::from("-1").into()), Err(CommandError::NegativeTimeout));
+    assert_eq!(parse_timeout(BulkString::from("inf").into()), Err(CommandError::InvalidTimeout));
+    assert_eq!(parse_timeout(BulkString::from("soon").into()), Err(CommandError::InvalidTimeout));
+  }
+
+  #[test]
+  fn test_command_error_to_frame() {
+    let frame: RespFrame = CommandError::InvalidArgument("Invalid key".to_string()).into();
+    assert_eq!(frame, SimpleError::new("ERR Invalid key").into());
+
+    let frame: RespFrame = CommandError::WrongType.into();
+    assert_eq!(
+      frame,
+      SimpleError::new("WRONGTYPE Operation against a key holding the wrong kind of value").into()
+    );
+  }
 }
@@ -0,0 +1,105 @@
+use super::{
+  extract_args, extract_command_name, parse_string, CommandError, CommandExecutor, RESP_OK,
+};
+use crate::{Backend, RespArray, RespFrame};
+
+/// MSET key value [key value ...]
+/// MSETNX key value [key value ...]
+///  MSET key1 "Hello" key2 "World"
+/// "OK"
+///  MSETNX key2 "there" key3 "world"
+/// (integer) 0
+///  MSETNX key3 "Hello" key4 "there"
+/// (integer) 1
+#[derive(Debug)]
+pub struct MSet {
+  pub(crate) pairs: Vec<(String, RespFrame)>,
+  // MSETNX sets none of the keys if any of them exists
+  pub(crate) nx: bool,
+}
+
+impl CommandExecutor for MSet {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    // runs with the command lock held exclusively, so the check and the writes are one step
+    if self.nx && self.pairs.iter().any(|(key, _)| backend.exists(key)) {
+      return RespFrame::Integer(0);
+    }
+    for (key, value) in self.pairs {
+      backend.set(key, value);
+    }
+    if self.nx {
+      RespFrame::Integer(1)
+    } else {
+      RESP_OK.clone()
+    }
+  }
+}
+
+impl TryFrom<RespArray> for MSet {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let name = extract_command_name(&value)?;
+    let args = extract_args(value, 1)?;
+    if args.is_empty() || !args.len().is_multiple_of(2) {
+      return Err(CommandError::WrongArity(name));
+    }
+
+    let mut pairs = Vec::with_capacity(args.len() / 2);
+    let mut args = args.into_iter();
+    while let (Some(key), Some(value)) = (args.next(), args.next()) {
+      pairs.push((parse_string(key)?, value));
+    }
+    Ok(MSet { pairs, nx: name == "msetnx" })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, RespFrame)> {
+    pairs.iter().map(|(k, v)| (k.to_string(), BulkString::from(*v).into())).collect()
+  }
+
+  #[test]
+  fn test_mset_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*5\r\n$6\r\nMSETNX\r\n$4\r\nkey1\r\n$5\r\nHello\r\n$4\r\nkey2\r\n$5\r\nWorld\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: MSet = frame.try_into()?;
+    assert_eq!(ret.pairs, pairs(&[("key1", "Hello"), ("key2", "World")]));
+    assert!(ret.nx);
+
+    buf.extend_from_slice(b"*4\r\n$4\r\nmset\r\n$4\r\nkey1\r\n$5\r\nHello\r\n$4\r\nkey2\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(MSet::try_from(frame), Err(CommandError::WrongArity(name)) if name == "mset"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_mset_execute() {
+    let backend = Backend::new();
+    let ret =
+      MSet { pairs: pairs(&[("key1", "Hello"), ("key2", "World")]), nx: false }.execute(&backend);
+    assert_eq!(ret, RESP_OK.clone());
+    assert_eq!(backend.get("key2"), Ok(Some(BulkString::from("World").into())));
+
+    let ret =
+      MSet { pairs: pairs(&[("key2", "there"), ("key3", "world")]), nx: true }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(0));
+    assert!(!backend.exists("key3"));
+    assert_eq!(backend.get("key2"), Ok(Some(BulkString::from("World").into())));
+
+    let ret =
+      MSet { pairs: pairs(&[("key3", "Hello"), ("key4", "there")]), nx: true }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+    assert_eq!(backend.get("key4"), Ok(Some(BulkString::from("there").into())));
+  }
+}
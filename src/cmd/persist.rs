@@ -0,0 +1,67 @@
+use super::{extract_args, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// PERSIST key
+///  SET mykey "Hello"
+/// "OK"
+///  EXPIRE mykey 10
+/// (integer) 1
+///  PERSIST mykey
+/// (integer) 1
+///  TTL mykey
+/// (integer) -1
+#[derive(Debug)]
+pub struct Persist {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for Persist {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    RespFrame::Integer(backend.persist(&self.key) as i64)
+  }
+}
+
+impl TryFrom<RespArray> for Persist {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match args.next() {
+      Some(RespFrame::BulkString(key)) => Ok(Persist { key: key.into() }),
+      _ => Err(CommandError::InvalidArgument("Invalid key".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{now_ms, ExpireCondition, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_persist_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$7\r\npersist\r\n$5\r\nmykey\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Persist = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_persist_execute() {
+    let backend = Backend::new();
+    backend.set("mykey".to_string(), RespFrame::BulkString(b"hello".into()));
+    backend.expire_at("mykey", now_ms() + 10_000, ExpireCondition::default());
+
+    let ret = Persist { key: "mykey".to_string() }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+
+    let ret = Persist { key: "mykey".to_string() }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(0));
+    assert_eq!(backend.expire_time("mykey"), Some(None));
+  }
+}
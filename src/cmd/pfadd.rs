@@ -0,0 +1,76 @@
+use super::{extract_args, parse_bytes, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// PFADD key [element [element ...]]
+///  PFADD hll a b c d e f g
+/// (integer) 1
+///  PFADD hll a b
+/// (integer) 0
+///  PFCOUNT hll
+/// (integer) 7
+#[derive(Debug)]
+pub struct PFAdd {
+  pub(crate) key: String,
+  pub(crate) elements: Vec<Vec<u8>>,
+}
+
+impl CommandExecutor for PFAdd {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.pfadd(&self.key, &self.elements) {
+      Ok(changed) => RespFrame::Integer(changed as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for PFAdd {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::WrongArity("pfadd".to_string()))?)?;
+    let elements = args.map(parse_bytes).collect::<Result<Vec<_>, _>>()?;
+    Ok(PFAdd { key, elements })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_pfadd_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$5\r\nPFADD\r\n$3\r\nhll\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: PFAdd = frame.try_into()?;
+    assert_eq!(ret.key, "hll");
+    assert_eq!(ret.elements, vec![b"a".to_vec(), b"b".to_vec()]);
+
+    buf.extend_from_slice(b"*2\r\n$5\r\npfadd\r\n$3\r\nhll\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: PFAdd = frame.try_into()?;
+    assert!(ret.elements.is_empty());
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_pfadd_execute() {
+    let backend = Backend::new();
+    let pfadd = |elements: &[&str]| PFAdd {
+      key: "hll".to_string(),
+      elements: elements.iter().map(|e| e.as_bytes().to_vec()).collect(),
+    };
+
+    assert_eq!(pfadd(&["a", "b", "c"]).execute(&backend), RespFrame::Integer(1));
+    assert_eq!(pfadd(&["a", "b"]).execute(&backend), RespFrame::Integer(0));
+    assert_eq!(pfadd(&[]).execute(&backend), RespFrame::Integer(0));
+
+    backend.set("hll".to_string(), BulkString::from("not a hll").into());
+    assert_eq!(pfadd(&["a"]).execute(&backend), CommandError::InvalidHll.into());
+  }
+}
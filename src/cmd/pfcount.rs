@@ -0,0 +1,69 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// PFCOUNT key [key ...]
+///  PFADD hll foo bar zap
+/// (integer) 1
+///  PFADD some-other-hll 1 2 3
+/// (integer) 1
+///  PFCOUNT hll some-other-hll
+/// (integer) 6
+#[derive(Debug)]
+pub struct PFCount {
+  pub(crate) keys: Vec<String>,
+}
+
+impl CommandExecutor for PFCount {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.pfcount(&self.keys) {
+      Ok(card) => RespFrame::Integer(card as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for PFCount {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let keys =
+      extract_args(value, 1)?.into_iter().map(parse_string).collect::<Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+      return Err(CommandError::WrongArity("pfcount".to_string()));
+    }
+    Ok(PFCount { keys })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_pfcount_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$7\r\nPFCOUNT\r\n$3\r\nhll\r\n$5\r\nother\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: PFCount = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["hll", "other"]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_pfcount_execute() -> Result<()> {
+    let backend = Backend::new();
+    backend.pfadd("hll", &[b"foo".to_vec(), b"bar".to_vec(), b"zap".to_vec()])?;
+    backend.pfadd("other", &[b"1".to_vec(), b"2".to_vec(), b"3".to_vec()])?;
+    let pfcount = |keys: &[&str]| PFCount { keys: keys.iter().map(|k| k.to_string()).collect() };
+
+    assert_eq!(pfcount(&["hll"]).execute(&backend), RespFrame::Integer(3));
+    assert_eq!(pfcount(&["hll", "other", "missing"]).execute(&backend), RespFrame::Integer(6));
+    assert_eq!(pfcount(&["missing"]).execute(&backend), RespFrame::Integer(0));
+
+    Ok(())
+  }
+}
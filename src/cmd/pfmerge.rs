@@ -0,0 +1,77 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor, RESP_OK};
+use crate::{Backend, RespArray, RespFrame};
+
+/// PFMERGE destkey [sourcekey [sourcekey ...]]
+///  PFADD hll1 foo bar zap a
+/// (integer) 1
+///  PFADD hll2 a b c foo
+/// (integer) 1
+///  PFMERGE hll3 hll1 hll2
+/// "OK"
+///  PFCOUNT hll3
+/// (integer) 6
+#[derive(Debug)]
+pub struct PFMerge {
+  pub(crate) destination: String,
+  pub(crate) keys: Vec<String>,
+}
+
+impl CommandExecutor for PFMerge {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.pfmerge(&self.destination, &self.keys) {
+      Ok(()) => RESP_OK.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for PFMerge {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let destination =
+      parse_string(args.next().ok_or(CommandError::WrongArity("pfmerge".to_string()))?)?;
+    let keys = args.map(parse_string).collect::<Result<Vec<_>, _>>()?;
+    Ok(PFMerge { destination, keys })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_pfmerge_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$7\r\nPFMERGE\r\n$4\r\nhll3\r\n$4\r\nhll1\r\n$4\r\nhll2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: PFMerge = frame.try_into()?;
+    assert_eq!(ret.destination, "hll3");
+    assert_eq!(ret.keys, vec!["hll1", "hll2"]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_pfmerge_execute() -> Result<()> {
+    let backend = Backend::new();
+    let elements = |elements: &[&str]| -> Vec<Vec<u8>> {
+      elements.iter().map(|e| e.as_bytes().to_vec()).collect()
+    };
+    backend.pfadd("hll1", &elements(&["foo", "bar", "zap", "a"]))?;
+    backend.pfadd("hll2", &elements(&["a", "b", "c", "foo"]))?;
+    let pfmerge = PFMerge {
+      destination: "hll3".to_string(),
+      keys: vec!["hll1".to_string(), "hll2".to_string()],
+    };
+
+    assert_eq!(pfmerge.execute(&backend), RESP_OK.clone());
+    assert_eq!(backend.pfcount(&["hll3".to_string()])?, 6);
+
+    Ok(())
+  }
+}
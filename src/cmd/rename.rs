@@ -0,0 +1,80 @@
+use super::{extract_args, extract_command_name, CommandError, CommandExecutor, RESP_OK};
+use crate::{Backend, RespArray, RespFrame};
+
+/// RENAME key newkey
+/// RENAMENX key newkey
+///  SET mykey "Hello"
+/// "OK"
+///  RENAME mykey myotherkey
+/// "OK"
+///  SET mykey "World"
+/// "OK"
+///  RENAMENX mykey myotherkey
+/// (integer) 0
+#[derive(Debug)]
+pub struct Rename {
+  pub(crate) key: String,
+  pub(crate) new_key: String,
+  pub(crate) nx: bool,
+}
+
+impl CommandExecutor for Rename {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.rename(&self.key, &self.new_key, self.nx) {
+      Ok(renamed) if self.nx => RespFrame::Integer(renamed as i64),
+      Ok(_) => RESP_OK.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for Rename {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let nx = extract_command_name(&value)? == "renamenx";
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+      (Some(RespFrame::BulkString(key)), Some(RespFrame::BulkString(new_key))) => {
+        Ok(Rename { key: key.into(), new_key: new_key.into(), nx })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key or newkey".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_rename_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$8\r\nRENAMENX\r\n$5\r\nmykey\r\n$10\r\nmyotherkey\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Rename = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.new_key, "myotherkey");
+    assert!(ret.nx);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_rename_execute() {
+    let backend = Backend::new();
+    let rename = |nx| Rename { key: "mykey".to_string(), new_key: "other".to_string(), nx };
+
+    assert_eq!(rename(false).execute(&backend), CommandError::NoSuchKey.into());
+
+    backend.set("mykey".to_string(), RespFrame::BulkString(b"Hello".into()));
+    assert_eq!(rename(false).execute(&backend), RESP_OK.clone());
+
+    backend.set("mykey".to_string(), RespFrame::BulkString(b"World".into()));
+    assert_eq!(rename(true).execute(&backend), RespFrame::Integer(0));
+    assert_eq!(backend.get("other"), Ok(Some(RespFrame::BulkString(b"Hello".into()))));
+  }
+}
@@ -1,5 +1,5 @@
 use super::CommandExecutor;
-use super::{extract_args, validate_command, CommandError};
+use super::{extract_args, CommandError};
 use crate::{Backend, RespArray, RespFrame};
 
 const INT_1: i64 = 1;
@@ -26,10 +26,13 @@ impl CommandExecutor for SAdd {
     let ret = self
       .members
       .iter()
-      .map(|member| if backend.sadd(&self.key, member) { INT_1 } else { INT_0 })
-      .sum::<i64>();
+      .map(|member| backend.sadd(&self.key, member).map(|added| if added { INT_1 } else { INT_0 }))
+      .sum::<Result<i64, CommandError>>();
 
-    RespFrame::Integer(ret)
+    match ret {
+      Ok(ret) => RespFrame::Integer(ret),
+      Err(e) => e.into(),
+    }
   }
 }
 
@@ -37,7 +40,6 @@ impl TryFrom<RespArray> for SAdd {
   type Error = CommandError;
 
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["sadd"], 2)?;
     let mut args = extract_args(value, 1)?.into_iter();
     match (args.next(), args.next()) {
       (Some(RespFrame::BulkString(key)), Some(RespFrame::BulkString(member))) => {
@@ -0,0 +1,153 @@
+use super::{
+  extract_args, glob::glob_match, parse_integer, parse_string, CommandError, CommandExecutor,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+const DEFAULT_COUNT: usize = 10;
+
+/// SCAN cursor [MATCH pattern] [COUNT count] [TYPE type]
+///  SCAN 0 MATCH user:* COUNT 100
+/// 1) "0"
+/// 2) 1) "user:1"
+///    2) "user:2"
+#[derive(Debug)]
+pub struct Scan {
+  pub(crate) cursor: u64,
+  pub(crate) opts: ScanOptions,
+  pub(crate) type_name: Option<String>,
+}
+
+/// The `MATCH` and `COUNT` options shared by `SCAN`, `HSCAN` and `SSCAN`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScanOptions {
+  pub(crate) pattern: Option<String>,
+  pub(crate) count: usize,
+}
+
+impl Default for ScanOptions {
+  fn default() -> Self {
+    Self { pattern: None, count: DEFAULT_COUNT }
+  }
+}
+
+impl ScanOptions {
+  /// Apply one option, returning false if `opt` is not `MATCH` or `COUNT`.
+  pub(crate) fn parse_option(
+    &mut self,
+    opt: &str,
+    args: &mut impl Iterator<Item = RespFrame>,
+  ) -> Result<bool, CommandError> {
+    match opt {
+      "match" => self.pattern = Some(parse_string(args.next().ok_or(CommandError::SyntaxError)?)?),
+      "count" => match parse_integer(args.next().ok_or(CommandError::SyntaxError)?)? {
+        count if count >= 1 => self.count = count as usize,
+        _ => return Err(CommandError::SyntaxError),
+      },
+      _ => return Ok(false),
+    }
+    Ok(true)
+  }
+
+  pub(crate) fn matches(&self, s: &str) -> bool {
+    self.pattern.as_ref().is_none_or(|p| glob_match(p.as_bytes(), s.as_bytes()))
+  }
+}
+
+/// The `[cursor, [elements...]]` reply of the scan family.
+pub(crate) fn scan_reply(cursor: u64, elements: Vec<RespFrame>) -> RespFrame {
+  RespArray::new([BulkString::from(cursor.to_string()).into(), RespArray::new(elements).into()])
+    .into()
+}
+
+pub(crate) fn parse_cursor(frame: RespFrame) -> Result<u64, CommandError> {
+  match frame {
+    RespFrame::BulkString(s) => std::str::from_utf8(s.as_ref())
+      .ok()
+      .and_then(|s| s.parse().ok())
+      .ok_or(CommandError::InvalidCursor),
+    _ => Err(CommandError::InvalidCursor),
+  }
+}
+
+impl CommandExecutor for Scan {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let (cursor, keys) = backend.scan(self.cursor, self.opts.count, self.type_name.as_deref());
+    let keys = keys
+      .into_iter()
+      .filter(|key| self.opts.matches(key))
+      .map(|key| BulkString::from(key).into())
+      .collect();
+    scan_reply(cursor, keys)
+  }
+}
+
+impl TryFrom<RespArray> for Scan {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let cursor = parse_cursor(args.next().ok_or(CommandError::SyntaxError)?)?;
+
+    let mut scan = Scan { cursor, opts: ScanOptions::default(), type_name: None };
+    while let Some(arg) = args.next() {
+      let opt = parse_string(arg)?.to_ascii_lowercase();
+      if scan.opts.parse_option(&opt, &mut args)? {
+        continue;
+      }
+      match opt.as_str() {
+        "type" => {
+          scan.type_name = Some(parse_string(args.next().ok_or(CommandError::SyntaxError)?)?)
+        }
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+
+    Ok(scan)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_scan_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*8\r\n$4\r\nSCAN\r\n$2\r\n17\r\n$5\r\nMATCH\r\n$6\r\nuser:*\r\n$5\r\nCOUNT\r\n$3\r\n100\r\n$4\r\nTYPE\r\n$4\r\nhash\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Scan = frame.try_into()?;
+    assert_eq!(ret.cursor, 17);
+    assert_eq!(ret.opts, ScanOptions { pattern: Some("user:*".to_string()), count: 100 });
+    assert_eq!(ret.type_name.as_deref(), Some("hash"));
+
+    buf.extend_from_slice(b"*2\r\n$4\r\nSCAN\r\n$2\r\n-1\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(Scan::try_from(frame), Err(CommandError::InvalidCursor)));
+
+    buf.extend_from_slice(b"*4\r\n$4\r\nSCAN\r\n$1\r\n0\r\n$5\r\nCOUNT\r\n$1\r\n0\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(Scan::try_from(frame), Err(CommandError::SyntaxError)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_scan_execute() {
+    let backend = Backend::new();
+    backend.set("user:1".to_string(), RespFrame::Integer(1));
+    backend.set("other".to_string(), RespFrame::Integer(2));
+
+    let opts = ScanOptions { pattern: Some("user:*".to_string()), count: 100 };
+    let ret = Scan { cursor: 0, opts, type_name: None }.execute(&backend);
+    assert_eq!(ret, scan_reply(0, vec![BulkString::from("user:1").into()]));
+
+    let ret = Scan { cursor: 0, opts: ScanOptions::default(), type_name: Some("set".to_string()) }
+      .execute(&backend);
+    assert_eq!(ret, scan_reply(0, vec![]));
+  }
+}
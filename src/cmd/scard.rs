@@ -0,0 +1,60 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// SCARD key
+///  SADD myset "Hello" "World"
+/// (integer) 2
+///  SCARD myset
+/// (integer) 2
+#[derive(Debug)]
+pub struct SCard {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for SCard {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.scard(&self.key) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SCard {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(SCard { key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_scard_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$5\r\nSCARD\r\n$5\r\nmyset\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SCard = frame.try_into()?;
+    assert_eq!(ret.key, "myset");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_scard_execute() {
+    let backend = Backend::new();
+    backend.sadd("myset", "Hello").unwrap();
+    backend.sadd("myset", "World").unwrap();
+
+    assert_eq!(SCard { key: "myset".to_string() }.execute(&backend), RespFrame::Integer(2));
+    assert_eq!(SCard { key: "missing".to_string() }.execute(&backend), RespFrame::Integer(0));
+  }
+}
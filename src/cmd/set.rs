@@ -1,39 +1,130 @@
-use super::{extract_args, validate_command, CommandError, CommandExecutor, RESP_OK};
-use crate::{Backend, RespArray, RespFrame};
+use super::{extract_args, parse_integer, CommandError, CommandExecutor, RESP_NULL, RESP_OK};
+use crate::{now_ms, Backend, RespArray, RespFrame, SetCondition, SetTtl};
 
+/// SET key value [NX | XX] [GET] [EX seconds | PX milliseconds |
+///   EXAT unix-time-seconds | PXAT unix-time-milliseconds | KEEPTTL]
+///  SET mykey "Hello"
+/// "OK"
+///  SET mykey "World" NX
+/// (nil)
+///  SET mykey "World" XX GET
+/// "Hello"
+///  SET lock "token" NX PX 30000
+/// "OK"
 #[derive(Debug)]
 pub struct Set {
   key: String,
   value: RespFrame,
+  cond: SetCondition,
+  expire: Option<SetExpire>,
+  get: bool,
+}
+
+/// The expire options shared by `SET` and `GETEX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SetExpire {
+  Ex(i64),
+  Px(i64),
+  ExAt(i64),
+  PxAt(i64),
+  KeepTtl,
+  // GETEX only
+  Persist,
+}
+
+impl SetExpire {
+  /// How the ttl of the key changes, `None` if the deadline overflows.
+  pub(crate) fn ttl(self) -> Option<SetTtl> {
+    let at = match self {
+      SetExpire::Ex(secs) => secs.checked_mul(1000)?.checked_add(now_ms())?,
+      SetExpire::Px(ms) => ms.checked_add(now_ms())?,
+      SetExpire::ExAt(secs) => secs.checked_mul(1000)?,
+      SetExpire::PxAt(ms) => ms,
+      SetExpire::KeepTtl => return Some(SetTtl::Keep),
+      SetExpire::Persist => return Some(SetTtl::Discard),
+    };
+    Some(SetTtl::At(at))
+  }
 }
 
 impl CommandExecutor for Set {
   fn execute(self, backend: &Backend) -> RespFrame {
-    backend.set(self.key, self.value);
-    RESP_OK.clone()
+    let ttl = match self.expire.map(SetExpire::ttl) {
+      Some(Some(ttl)) => ttl,
+      Some(None) => return CommandError::InvalidExpireTime("set".to_string()).into(),
+      None => SetTtl::Discard,
+    };
+    match backend.set_if(self.key, self.value, self.cond, ttl, self.get) {
+      Ok((_, old)) if self.get => old.unwrap_or(RESP_NULL.clone()),
+      Ok((true, _)) => RESP_OK.clone(),
+      Ok((false, _)) => RESP_NULL.clone(),
+      Err(e) => e.into(),
+    }
   }
 }
 
 impl TryFrom<RespArray> for Set {
   type Error = CommandError;
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["set"], 2)?;
-
     let mut args = extract_args(value, 1)?.into_iter();
-    match (args.next(), args.next()) {
-      (Some(RespFrame::BulkString(key)), Some(value)) => Ok(Set { key: key.into(), value }),
-      _ => Err(CommandError::InvalidArgument("Invalid key or value".to_string())),
+    let (key, value) = match (args.next(), args.next()) {
+      (Some(RespFrame::BulkString(key)), Some(value)) => (key.into(), value),
+      _ => return Err(CommandError::InvalidArgument("Invalid key or value".to_string())),
+    };
+
+    let mut set = Set { key, value, cond: SetCondition::Always, expire: None, get: false };
+    while let Some(arg) = args.next() {
+      let opt = match arg {
+        RespFrame::BulkString(opt) => String::from(opt).to_ascii_lowercase(),
+        _ => return Err(CommandError::SyntaxError),
+      };
+      let unit: fn(i64) -> SetExpire = match opt.as_str() {
+        "nx" | "xx" if set.cond != SetCondition::Always => return Err(CommandError::SyntaxError),
+        "nx" => {
+          set.cond = SetCondition::IfNotExists;
+          continue;
+        }
+        "xx" => {
+          set.cond = SetCondition::IfExists;
+          continue;
+        }
+        "get" => {
+          set.get = true;
+          continue;
+        }
+        _ if set.expire.is_some() => return Err(CommandError::SyntaxError),
+        "keepttl" => {
+          set.expire = Some(SetExpire::KeepTtl);
+          continue;
+        }
+        "ex" => SetExpire::Ex,
+        "px" => SetExpire::Px,
+        "exat" => SetExpire::ExAt,
+        "pxat" => SetExpire::PxAt,
+        _ => return Err(CommandError::SyntaxError),
+      };
+      let n = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+      if n <= 0 {
+        return Err(CommandError::InvalidExpireTime("set".to_string()));
+      }
+      set.expire = Some(unit(n));
     }
+
+    Ok(set)
   }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{Backend, Get, RespDecode};
+  use crate::{Backend, ExpireCondition, Get, RespDecode};
   use anyhow::Result;
   use bytes::BytesMut;
 
+  fn set(value: &str, cond: SetCondition, expire: Option<SetExpire>, get: bool) -> Set {
+    Set { key: "hello".to_string(), value: RespFrame::BulkString(value.into()), cond, expire, get }
+  }
+
   #[test]
   fn test_set_from_resp_array() -> Result<()> {
     let mut buf = BytesMut::new();
@@ -45,6 +136,46 @@ mod tests {
 
     assert_eq!(ret.key, "hello");
     assert_eq!(ret.value, RespFrame::BulkString(b"world".into()));
+    assert_eq!(ret.cond, SetCondition::Always);
+    assert_eq!(ret.expire, None);
+    assert!(!ret.get);
+
+    buf.extend_from_slice(
+      b"*6\r\n$3\r\nset\r\n$5\r\nhello\r\n$5\r\nworld\r\n$2\r\nNX\r\n$2\r\nPX\r\n$3\r\n100\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Set = frame.try_into()?;
+    assert_eq!(ret.cond, SetCondition::IfNotExists);
+    assert_eq!(ret.expire, Some(SetExpire::Px(100)));
+
+    buf.extend_from_slice(
+      b"*5\r\n$3\r\nset\r\n$5\r\nhello\r\n$5\r\nworld\r\n$7\r\nKEEPTTL\r\n$3\r\nget\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Set = frame.try_into()?;
+    assert_eq!(ret.expire, Some(SetExpire::KeepTtl));
+    assert!(ret.get);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_set_from_resp_array_rejects_conflicts() -> Result<()> {
+    let cases: [&[u8]; 4] = [
+      b"*5\r\n$3\r\nset\r\n$1\r\nk\r\n$1\r\nv\r\n$2\r\nnx\r\n$2\r\nxx\r\n",
+      b"*7\r\n$3\r\nset\r\n$1\r\nk\r\n$1\r\nv\r\n$2\r\nex\r\n$1\r\n1\r\n$2\r\npx\r\n$1\r\n1\r\n",
+      b"*6\r\n$3\r\nset\r\n$1\r\nk\r\n$1\r\nv\r\n$2\r\nex\r\n$1\r\n1\r\n$7\r\nkeepttl\r\n",
+      b"*4\r\n$3\r\nset\r\n$1\r\nk\r\n$1\r\nv\r\n$2\r\nex\r\n",
+    ];
+    for case in cases {
+      let frame = RespArray::decode(&mut BytesMut::from(case))?;
+      assert!(matches!(Set::try_from(frame), Err(CommandError::SyntaxError)));
+    }
+
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*5\r\n$3\r\nset\r\n$1\r\nk\r\n$1\r\nv\r\n$4\r\nexat\r\n$1\r\n0\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(Set::try_from(frame), Err(CommandError::InvalidExpireTime(_))));
 
     Ok(())
   }
@@ -52,16 +183,58 @@ mod tests {
   #[test]
   fn test_set_get_command() -> Result<()> {
     let backend = Backend::new();
-    let cmd = Set { key: "hello".to_string(), value: RespFrame::BulkString(b"world".into()) };
+    let cmd = set("world", SetCondition::Always, Some(SetExpire::Ex(10)), false);
     let ret = cmd.execute(&backend);
 
     assert_eq!(ret, RESP_OK.clone());
+    assert!(backend.expire_time("hello").unwrap().is_some());
 
     let cmd = Get { key: "hello".to_string() };
     let ret = cmd.execute(&backend);
 
     assert_eq!(ret, RespFrame::BulkString(b"world".into()));
 
+    // a plain SET discards the previous ttl
+    let cmd = set("again", SetCondition::Always, None, false);
+    cmd.execute(&backend);
+    assert_eq!(backend.expire_time("hello"), Some(None));
+
     Ok(())
   }
+
+  #[test]
+  fn test_set_conditions() {
+    let backend = Backend::new();
+
+    let ret = set("v1", SetCondition::IfExists, None, false).execute(&backend);
+    assert_eq!(ret, RESP_NULL.clone());
+    assert_eq!(backend.get("hello"), Ok(None));
+
+    let ret = set("v1", SetCondition::IfNotExists, None, false).execute(&backend);
+    assert_eq!(ret, RESP_OK.clone());
+
+    let ret = set("v2", SetCondition::IfNotExists, None, true).execute(&backend);
+    assert_eq!(ret, RespFrame::BulkString("v1".into()));
+    assert_eq!(backend.get("hello"), Ok(Some(RespFrame::BulkString("v1".into()))));
+
+    let ret = set("v2", SetCondition::IfExists, None, true).execute(&backend);
+    assert_eq!(ret, RespFrame::BulkString("v1".into()));
+    assert_eq!(backend.get("hello"), Ok(Some(RespFrame::BulkString("v2".into()))));
+  }
+
+  #[test]
+  fn test_set_keepttl_and_absolute_expire() {
+    let backend = Backend::new();
+    let at = now_ms() + 10_000;
+
+    set("v1", SetCondition::Always, Some(SetExpire::PxAt(at)), false).execute(&backend);
+    assert_eq!(backend.expire_time("hello"), Some(Some(at)));
+
+    set("v2", SetCondition::Always, Some(SetExpire::KeepTtl), false).execute(&backend);
+    assert_eq!(backend.expire_time("hello"), Some(Some(at)));
+
+    set("v3", SetCondition::Always, Some(SetExpire::ExAt(1)), false).execute(&backend);
+    assert_eq!(backend.get("hello"), Ok(None));
+    assert!(!backend.expire_at("hello", at, ExpireCondition::default()));
+  }
 }
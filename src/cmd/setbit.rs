@@ -0,0 +1,94 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame, MAX_BIT_OFFSET};
+
+/// SETBIT key offset value
+///  SETBIT mykey 7 1
+/// (integer) 0
+///  SETBIT mykey 7 0
+/// (integer) 1
+///  GET mykey
+/// "\x00"
+#[derive(Debug)]
+pub struct SetBit {
+  pub(crate) key: String,
+  pub(crate) offset: u64,
+  pub(crate) bit: bool,
+}
+
+/// Parse the offset of a single bit, which must fit in the largest string.
+pub(crate) fn parse_bit_offset(frame: RespFrame) -> Result<u64, CommandError> {
+  match parse_integer(frame) {
+    Ok(offset) if (0..=MAX_BIT_OFFSET as i64).contains(&offset) => Ok(offset as u64),
+    _ => Err(CommandError::InvalidBitOffset),
+  }
+}
+
+impl CommandExecutor for SetBit {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.setbit(&self.key, self.offset, self.bit) {
+      Ok(old) => RespFrame::Integer(old as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SetBit {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, offset, bit) = match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(offset), Some(bit)) => (parse_string(key)?, parse_bit_offset(offset)?, bit),
+      _ => return Err(CommandError::InvalidArgument("Invalid key, offset or value".to_string())),
+    };
+    let bit = match parse_integer(bit) {
+      Ok(0) => false,
+      Ok(1) => true,
+      _ => {
+        return Err(CommandError::InvalidArgument(
+          "bit is not an integer or out of range".to_string(),
+        ))
+      }
+    };
+    Ok(SetBit { key, offset, bit })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_setbit_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$6\r\nSETBIT\r\n$5\r\nmykey\r\n$1\r\n7\r\n$1\r\n1\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SetBit = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.offset, 7);
+    assert!(ret.bit);
+
+    buf.extend_from_slice(b"*4\r\n$6\r\nsetbit\r\n$5\r\nmykey\r\n$2\r\n-1\r\n$1\r\n1\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(SetBit::try_from(frame), Err(CommandError::InvalidBitOffset)));
+
+    buf.extend_from_slice(b"*4\r\n$6\r\nsetbit\r\n$5\r\nmykey\r\n$1\r\n7\r\n$1\r\n2\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(SetBit::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_setbit_execute() {
+    let backend = Backend::new();
+    let setbit = |bit| SetBit { key: "mykey".to_string(), offset: 7, bit };
+
+    assert_eq!(setbit(true).execute(&backend), RespFrame::Integer(0));
+    assert_eq!(setbit(false).execute(&backend), RespFrame::Integer(1));
+    assert_eq!(backend.get("mykey"), Ok(Some(BulkString::new(vec![0]).into())));
+  }
+}
@@ -0,0 +1,89 @@
+use super::{
+  extract_args, parse_bytes, parse_integer, parse_string, CommandError, CommandExecutor,
+};
+use crate::{Backend, RespArray, RespFrame};
+
+/// SETRANGE key offset value
+///  SET key1 "Hello World"
+/// "OK"
+///  SETRANGE key1 6 "Redis"
+/// (integer) 11
+///  SETRANGE key2 6 "Redis"
+/// (integer) 11
+///  GET key2
+/// "\x00\x00\x00\x00\x00\x00Redis"
+#[derive(Debug)]
+pub struct SetRange {
+  pub(crate) key: String,
+  pub(crate) offset: usize,
+  pub(crate) value: Vec<u8>,
+}
+
+impl CommandExecutor for SetRange {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.setrange(&self.key, self.offset, &self.value) {
+      Ok(len) => RespFrame::Integer(len as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SetRange {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, offset, value) = match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(offset), Some(value)) => {
+        (parse_string(key)?, parse_integer(offset)?, parse_bytes(value)?)
+      }
+      _ => return Err(CommandError::InvalidArgument("Invalid key, offset or value".to_string())),
+    };
+    if offset < 0 {
+      return Err(CommandError::InvalidArgument("offset is out of range".to_string()));
+    }
+    Ok(SetRange { key, offset: offset as usize, value })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_setrange_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$8\r\nSETRANGE\r\n$4\r\nkey1\r\n$1\r\n6\r\n$5\r\nRedis\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SetRange = frame.try_into()?;
+    assert_eq!(ret.key, "key1");
+    assert_eq!(ret.offset, 6);
+    assert_eq!(ret.value, b"Redis");
+
+    buf.extend_from_slice(b"*4\r\n$8\r\nSETRANGE\r\n$4\r\nkey1\r\n$2\r\n-1\r\n$5\r\nRedis\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(SetRange::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_setrange_execute() {
+    let backend = Backend::new();
+    backend.set("key1".to_string(), BulkString::from("Hello World").into());
+    let setrange =
+      |key: &str, offset| SetRange { key: key.to_string(), offset, value: b"Redis".to_vec() };
+
+    assert_eq!(setrange("key1", 6).execute(&backend), RespFrame::Integer(11));
+    assert_eq!(backend.get("key1"), Ok(Some(BulkString::from("Hello Redis").into())));
+
+    assert_eq!(setrange("key2", 6).execute(&backend), RespFrame::Integer(11));
+    assert_eq!(
+      backend.get("key2"),
+      Ok(Some(BulkString::new(b"\0\0\0\0\0\0Redis".to_vec()).into()))
+    );
+  }
+}
@@ -0,0 +1,96 @@
+use super::{
+  extract_args, parse_integer, parse_string, zunion::parse_keys, CommandError, CommandExecutor,
+};
+use crate::{Backend, RespArray, RespFrame, SetOp};
+
+/// SINTERCARD numkeys key [key ...] [LIMIT limit]
+///  SADD key1 "a" "b" "c" "d"
+/// (integer) 4
+///  SADD key2 "c" "d" "e"
+/// (integer) 3
+///  SINTERCARD 2 key1 key2
+/// (integer) 2
+///  SINTERCARD 2 key1 key2 LIMIT 1
+/// (integer) 1
+#[derive(Debug)]
+pub struct SInterCard {
+  pub(crate) keys: Vec<String>,
+  // 0 means no limit
+  pub(crate) limit: usize,
+}
+
+impl CommandExecutor for SInterCard {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.scombine(SetOp::Inter, &self.keys) {
+      Ok(members) if self.limit > 0 => RespFrame::Integer(members.len().min(self.limit) as i64),
+      Ok(members) => RespFrame::Integer(members.len() as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SInterCard {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let keys = parse_keys(&mut args, "sintercard")?;
+    let limit = match (args.next(), args.next(), args.next()) {
+      (None, _, _) => 0,
+      (Some(opt), Some(limit), None) => {
+        if !parse_string(opt)?.eq_ignore_ascii_case("limit") {
+          return Err(CommandError::SyntaxError);
+        }
+        match parse_integer(limit)? {
+          limit if limit < 0 => {
+            return Err(CommandError::InvalidArgument("LIMIT can't be negative".to_string()))
+          }
+          limit => limit as usize,
+        }
+      }
+      _ => return Err(CommandError::SyntaxError),
+    };
+    Ok(SInterCard { keys, limit })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_sintercard_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*6\r\n$10\r\nSINTERCARD\r\n$1\r\n2\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n$5\r\nLIMIT\r\n$1\r\n1\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SInterCard = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["key1", "key2"]);
+    assert_eq!(ret.limit, 1);
+
+    buf.extend_from_slice(b"*3\r\n$10\r\nsintercard\r\n$1\r\n0\r\n$4\r\nkey1\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(SInterCard::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_sintercard_execute() {
+    let backend = Backend::new();
+    for (key, members) in [("key1", &["a", "b", "c", "d"][..]), ("key2", &["c", "d", "e"][..])] {
+      for member in members {
+        backend.sadd(key, *member).unwrap();
+      }
+    }
+    let sintercard =
+      |limit| SInterCard { keys: vec!["key1".to_string(), "key2".to_string()], limit };
+
+    assert_eq!(sintercard(0).execute(&backend), RespFrame::Integer(2));
+    assert_eq!(sintercard(1).execute(&backend), RespFrame::Integer(1));
+  }
+}
@@ -1,4 +1,4 @@
-use super::{extract_args, validate_command, CommandError, CommandExecutor};
+use super::{extract_args, CommandError, CommandExecutor};
 use crate::{Backend, RespArray, RespFrame};
 
 ///  SADD myset "one"
@@ -15,11 +15,10 @@ pub struct SIsMember {
 
 impl CommandExecutor for SIsMember {
   fn execute(self, backend: &Backend) -> RespFrame {
-    let is_member = backend.sismember(&self.key, &self.member);
-    if is_member {
-      RespFrame::Integer(1)
-    } else {
-      RespFrame::Integer(0)
+    match backend.sismember(&self.key, &self.member) {
+      Ok(true) => RespFrame::Integer(1),
+      Ok(false) => RespFrame::Integer(0),
+      Err(e) => e.into(),
     }
   }
 }
@@ -28,8 +27,6 @@ impl TryFrom<RespArray> for SIsMember {
   type Error = CommandError;
 
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["sismember"], 2)?;
-
     let mut args = extract_args(value, 1)?.into_iter();
     match (args.next(), args.next()) {
       (Some(RespFrame::BulkString(key)), Some(RespFrame::BulkString(member))) => {
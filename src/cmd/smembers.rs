@@ -1,4 +1,4 @@
-use super::{extract_args, validate_command, CommandError, CommandExecutor};
+use super::{extract_args, CommandError, CommandExecutor};
 use crate::{Backend, RespArray, RespFrame};
 
 /// redis> SADD myset "Hello"
@@ -16,14 +16,15 @@ impl CommandExecutor for SMembers {
   fn execute(self, backend: &Backend) -> RespFrame {
     let members = backend.smembers(&self.key);
     match members {
-      Some(members) => {
+      Ok(Some(members)) => {
         let ret = members
           .into_iter()
           .map(|member| RespFrame::BulkString(member.into()))
           .collect::<Vec<RespFrame>>();
         RespArray::new(ret).into()
       }
-      None => RespArray::new([]).into(),
+      Ok(None) => RespArray::new([]).into(),
+      Err(e) => e.into(),
     }
   }
 }
@@ -31,8 +32,6 @@ impl CommandExecutor for SMembers {
 impl TryFrom<RespArray> for SMembers {
   type Error = crate::CommandError;
   fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-    validate_command(&value, &["smembers"], 1)?;
-
     let mut args = extract_args(value, 1)?.into_iter();
     match args.next() {
       Some(RespFrame::BulkString(key)) => Ok(SMembers { key: key.into() }),
@@ -70,13 +69,14 @@ mod tests {
     let cmd = SMembers { key: "mykey".to_string() };
     let ret = cmd.execute(&backend);
 
+    // set members come back in no particular order
+    let RespFrame::Array(RespArray(Some(mut members))) = ret else {
+      panic!("expected an array, got {:?}", ret);
+    };
+    members.sort_by(|a, b| a.partial_cmp(b).unwrap());
     assert_eq!(
-      ret,
-      RespArray::new(vec![
-        RespFrame::BulkString("hello".into()),
-        RespFrame::BulkString("world".into())
-      ])
-      .into()
+      members,
+      vec![RespFrame::BulkString("hello".into()), RespFrame::BulkString("world".into())]
     );
     Ok(())
   }
@@ -0,0 +1,70 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// SMISMEMBER key member [member ...]
+///  SADD myset "one"
+/// (integer) 1
+///  SMISMEMBER myset "one" "notamember"
+/// 1) (integer) 1
+/// 2) (integer) 0
+#[derive(Debug)]
+pub struct SMIsMember {
+  pub(crate) key: String,
+  pub(crate) members: Vec<String>,
+}
+
+impl CommandExecutor for SMIsMember {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.smismember(&self.key, &self.members) {
+      Ok(found) => {
+        let ret =
+          found.into_iter().map(|found| RespFrame::Integer(found as i64)).collect::<Vec<_>>();
+        RespArray::new(ret).into()
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SMIsMember {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let members = args.map(parse_string).collect::<Result<Vec<String>, _>>()?;
+    Ok(SMIsMember { key, members })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_smismember_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*4\r\n$10\r\nSMISMEMBER\r\n$5\r\nmyset\r\n$3\r\none\r\n$10\r\nnotamember\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SMIsMember = frame.try_into()?;
+    assert_eq!(ret.key, "myset");
+    assert_eq!(ret.members, vec!["one".to_string(), "notamember".to_string()]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_smismember_execute() {
+    let backend = Backend::new();
+    backend.sadd("myset", "one").unwrap();
+    let members = vec!["one".to_string(), "notamember".to_string()];
+
+    let ret = SMIsMember { key: "myset".to_string(), members }.execute(&backend);
+    assert_eq!(ret, RespArray::new([RespFrame::Integer(1), RespFrame::Integer(0)]).into());
+  }
+}
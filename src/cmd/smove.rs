@@ -0,0 +1,79 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// SMOVE source destination member
+///  SADD myset "one" "two"
+/// (integer) 2
+///  SADD myotherset "three"
+/// (integer) 1
+///  SMOVE myset myotherset "two"
+/// (integer) 1
+#[derive(Debug)]
+pub struct SMove {
+  pub(crate) source: String,
+  pub(crate) destination: String,
+  pub(crate) member: String,
+}
+
+impl CommandExecutor for SMove {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.smove(&self.source, &self.destination, &self.member) {
+      Ok(moved) => RespFrame::Integer(moved as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SMove {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(source), Some(destination), Some(member)) => Ok(SMove {
+        source: parse_string(source)?,
+        destination: parse_string(destination)?,
+        member: parse_string(member)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid source, destination or member".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_smove_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf
+      .extend_from_slice(b"*4\r\n$5\r\nSMOVE\r\n$5\r\nmyset\r\n$10\r\nmyotherset\r\n$3\r\ntwo\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SMove = frame.try_into()?;
+    assert_eq!(ret.source, "myset");
+    assert_eq!(ret.destination, "myotherset");
+    assert_eq!(ret.member, "two");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_smove_execute() {
+    let backend = Backend::new();
+    backend.sadd("myset", "two").unwrap();
+    let smove = || SMove {
+      source: "myset".to_string(),
+      destination: "myotherset".to_string(),
+      member: "two".to_string(),
+    };
+
+    assert_eq!(smove().execute(&backend), RespFrame::Integer(1));
+    assert_eq!(smove().execute(&backend), RespFrame::Integer(0));
+    assert!(backend.sismember("myotherset", "two").unwrap());
+    assert!(!backend.exists("myset"));
+  }
+}
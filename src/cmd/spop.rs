@@ -0,0 +1,95 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// SPOP key [count]
+///  SADD myset one two three
+/// (integer) 3
+///  SPOP myset
+/// "one"
+///  SPOP myset 3
+/// 1) "three"
+/// 2) "two"
+#[derive(Debug)]
+pub struct SPop {
+  pub(crate) key: String,
+  pub(crate) count: Option<usize>,
+}
+
+impl CommandExecutor for SPop {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.spop(&self.key, self.count.unwrap_or(1)) {
+      Ok(members) if self.count.is_some() => {
+        RespArray::new(members.into_iter().map(|m| BulkString::from(m).into()).collect::<Vec<_>>())
+          .into()
+      }
+      Ok(members) => {
+        members.into_iter().next().map_or(RESP_NULL.clone(), |m| BulkString::from(m).into())
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SPop {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let count = match args.next().map(parse_integer).transpose()? {
+      Some(count) if count < 0 => {
+        return Err(CommandError::InvalidArgument(
+          "value is out of range, must be positive".to_string(),
+        ))
+      }
+      count => count.map(|count| count as usize),
+    };
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(SPop { key, count })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_spop_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$4\r\nSPOP\r\n$5\r\nmyset\r\n$1\r\n3\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SPop = frame.try_into()?;
+    assert_eq!(ret.key, "myset");
+    assert_eq!(ret.count, Some(3));
+
+    buf.extend_from_slice(b"*3\r\n$4\r\nspop\r\n$5\r\nmyset\r\n$2\r\n-1\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(SPop::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_spop_execute() {
+    let backend = Backend::new();
+    backend.sadd("myset", "one").unwrap();
+    backend.sadd("myset", "two").unwrap();
+    let spop = |count| SPop { key: "myset".to_string(), count };
+
+    let RespFrame::BulkString(popped) = spop(None).execute(&backend) else {
+      panic!("expected a member");
+    };
+    assert!(!backend.sismember("myset", &String::from(popped)).unwrap());
+    let ret = spop(Some(3)).execute(&backend);
+    assert!(
+      matches!(ret, RespFrame::Array(ref members) if members.as_ref().map(Vec::len) == Some(1))
+    );
+    assert!(!backend.exists("myset"));
+    assert_eq!(spop(None).execute(&backend), RESP_NULL.clone());
+  }
+}
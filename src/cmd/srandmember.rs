@@ -0,0 +1,107 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// SRANDMEMBER key [count]
+///  SADD myset one two three
+/// (integer) 3
+///  SRANDMEMBER myset
+/// "three"
+///  SRANDMEMBER myset -5
+/// 1) "one"
+/// 2) "three"
+/// 3) "one"
+/// 4) "two"
+/// 5) "one"
+#[derive(Debug)]
+pub struct SRandMember {
+  pub(crate) key: String,
+  // a negative count allows the same member more than once
+  pub(crate) count: Option<i64>,
+}
+
+/// The most items a negative count may ask for, each repeat takes memory in the reply.
+const MAX_RANDOM_REPEATS: i64 = 1 << 20;
+
+/// Parse the count of `SRANDMEMBER` and `HRANDFIELD`, which may be negative.
+pub(crate) fn parse_random_count(frame: RespFrame) -> Result<i64, CommandError> {
+  match parse_integer(frame)? {
+    count if count < -MAX_RANDOM_REPEATS => {
+      Err(CommandError::InvalidArgument("value is out of range".to_string()))
+    }
+    count => Ok(count),
+  }
+}
+
+impl CommandExecutor for SRandMember {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.srandmember(&self.key, self.count.unwrap_or(1)) {
+      Ok(members) if self.count.is_some() => {
+        RespArray::new(members.into_iter().map(|m| BulkString::from(m).into()).collect::<Vec<_>>())
+          .into()
+      }
+      Ok(members) => {
+        members.into_iter().next().map_or(RESP_NULL.clone(), |m| BulkString::from(m).into())
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SRandMember {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let count = args.next().map(parse_random_count).transpose()?;
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(SRandMember { key, count })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_srandmember_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$11\r\nSRANDMEMBER\r\n$5\r\nmyset\r\n$2\r\n-5\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SRandMember = frame.try_into()?;
+    assert_eq!(ret.key, "myset");
+    assert_eq!(ret.count, Some(-5));
+
+    let count = format!("{}", -MAX_RANDOM_REPEATS - 1);
+    let frame = RespArray::new([
+      BulkString::from("SRANDMEMBER").into(),
+      BulkString::from("myset").into(),
+      BulkString::from(count).into(),
+    ]);
+    let ret = SRandMember::try_from(frame);
+    assert!(matches!(ret, Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_srandmember_execute() {
+    let backend = Backend::new();
+    backend.sadd("myset", "one").unwrap();
+    let srandmember = |count| SRandMember { key: "myset".to_string(), count };
+
+    assert_eq!(srandmember(None).execute(&backend), BulkString::from("one").into());
+    let ret = srandmember(Some(-2)).execute(&backend);
+    let one: RespFrame = BulkString::from("one").into();
+    assert_eq!(ret, RespArray::new([one.clone(), one]).into());
+    assert_eq!(srandmember(Some(0)).execute(&backend), RespArray::new([]).into());
+
+    let ret = SRandMember { key: "missing".to_string(), count: None }.execute(&backend);
+    assert_eq!(ret, RESP_NULL.clone());
+  }
+}
@@ -0,0 +1,64 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// SREM key member [member ...]
+///  SADD myset "one" "two" "three"
+/// (integer) 3
+///  SREM myset "one" "four"
+/// (integer) 1
+#[derive(Debug)]
+pub struct SRem {
+  pub(crate) key: String,
+  pub(crate) members: Vec<String>,
+}
+
+impl CommandExecutor for SRem {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.srem(&self.key, &self.members) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SRem {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let members = args.map(parse_string).collect::<Result<Vec<String>, _>>()?;
+    Ok(SRem { key, members })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_srem_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$4\r\nSREM\r\n$5\r\nmyset\r\n$3\r\none\r\n$4\r\nfour\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SRem = frame.try_into()?;
+    assert_eq!(ret.key, "myset");
+    assert_eq!(ret.members, vec!["one".to_string(), "four".to_string()]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_srem_execute() {
+    let backend = Backend::new();
+    backend.sadd("myset", "one").unwrap();
+    let members = vec!["one".to_string(), "four".to_string()];
+
+    let ret = SRem { key: "myset".to_string(), members }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+    assert!(!backend.exists("myset"));
+  }
+}
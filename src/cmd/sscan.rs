@@ -0,0 +1,93 @@
+use super::{
+  extract_args, parse_string,
+  scan::{parse_cursor, scan_reply, ScanOptions},
+  CommandError, CommandExecutor,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// SSCAN key cursor [MATCH pattern] [COUNT count]
+///  SADD myset apple banana avocado
+/// (integer) 3
+///  SSCAN myset 0 MATCH a*
+/// 1) "0"
+/// 2) 1) "apple"
+///    2) "avocado"
+#[derive(Debug)]
+pub struct SScan {
+  pub(crate) key: String,
+  pub(crate) cursor: u64,
+  pub(crate) opts: ScanOptions,
+}
+
+impl CommandExecutor for SScan {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.sscan(&self.key, self.cursor, self.opts.count) {
+      Ok((cursor, members)) => {
+        let ret = members
+          .into_iter()
+          .filter(|member| self.opts.matches(member))
+          .map(|member| BulkString::from(member).into())
+          .collect();
+        scan_reply(cursor, ret)
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SScan {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let cursor = parse_cursor(args.next().ok_or(CommandError::SyntaxError)?)?;
+
+    let mut opts = ScanOptions::default();
+    while let Some(arg) = args.next() {
+      let opt = parse_string(arg)?.to_ascii_lowercase();
+      if !opts.parse_option(&opt, &mut args)? {
+        return Err(CommandError::SyntaxError);
+      }
+    }
+
+    Ok(SScan { key, cursor, opts })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_sscan_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*5\r\n$5\r\nsscan\r\n$5\r\nmyset\r\n$2\r\n42\r\n$5\r\nCOUNT\r\n$1\r\n5\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SScan = frame.try_into()?;
+    assert_eq!(ret.key, "myset");
+    assert_eq!(ret.cursor, 42);
+    assert_eq!(ret.opts, ScanOptions { pattern: None, count: 5 });
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_sscan_execute() -> Result<()> {
+    let backend = Backend::new();
+    for member in ["apple", "banana"] {
+      backend.sadd("myset", member)?;
+    }
+
+    let opts = ScanOptions { pattern: Some("a*".to_string()), count: 10 };
+    let ret = SScan { key: "myset".to_string(), cursor: 0, opts }.execute(&backend);
+    assert_eq!(ret, scan_reply(0, vec![BulkString::from("apple").into()]));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,63 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// STRLEN key
+///  SET mykey "Hello world"
+/// "OK"
+///  STRLEN mykey
+/// (integer) 11
+///  STRLEN nonexisting
+/// (integer) 0
+#[derive(Debug)]
+pub struct StrLen {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for StrLen {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.strlen(&self.key) {
+      Ok(len) => RespFrame::Integer(len as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for StrLen {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(StrLen { key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_strlen_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$6\r\nSTRLEN\r\n$5\r\nmykey\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: StrLen = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_strlen_execute() {
+    let backend = Backend::new();
+    backend.set("mykey".to_string(), BulkString::from("Hello world").into());
+
+    let ret = StrLen { key: "mykey".to_string() }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(11));
+    let ret = StrLen { key: "nonexisting".to_string() }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(0));
+  }
+}
@@ -0,0 +1,126 @@
+use super::{extract_args, extract_command_name, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BulkString, RespArray, RespFrame, SetOp};
+
+/// SUNION key [key ...]
+/// SINTER key [key ...]
+/// SDIFF key [key ...]
+/// SUNIONSTORE destination key [key ...]
+/// SINTERSTORE destination key [key ...]
+/// SDIFFSTORE destination key [key ...]
+///  SADD key1 "a" "b" "c"
+/// (integer) 3
+///  SADD key2 "c" "d" "e"
+/// (integer) 3
+///  SINTERSTORE key key1 key2
+/// (integer) 1
+///  SDIFF key1 key2
+/// 1) "a"
+/// 2) "b"
+#[derive(Debug)]
+pub struct SUnion {
+  pub(crate) op: SetOp,
+  // the *STORE forms write the result here and reply with its size
+  pub(crate) destination: Option<String>,
+  pub(crate) keys: Vec<String>,
+}
+
+impl CommandExecutor for SUnion {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let members = match backend.scombine(self.op, &self.keys) {
+      Ok(members) => members,
+      Err(e) => return e.into(),
+    };
+    match self.destination {
+      Some(destination) => RespFrame::Integer(backend.sstore(&destination, members) as i64),
+      None => {
+        RespArray::new(members.into_iter().map(|m| BulkString::from(m).into()).collect::<Vec<_>>())
+          .into()
+      }
+    }
+  }
+}
+
+impl TryFrom<RespArray> for SUnion {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let name = extract_command_name(&value)?;
+    let (op, store) = match name.as_str() {
+      "sunion" => (SetOp::Union, false),
+      "sinter" => (SetOp::Inter, false),
+      "sdiff" => (SetOp::Diff, false),
+      "sunionstore" => (SetOp::Union, true),
+      "sinterstore" => (SetOp::Inter, true),
+      "sdiffstore" => (SetOp::Diff, true),
+      _ => return Err(CommandError::InvalidCommand(name)),
+    };
+
+    let mut args = extract_args(value, 1)?.into_iter();
+    let destination = match store {
+      true => Some(parse_string(args.next().ok_or(CommandError::SyntaxError)?)?),
+      false => None,
+    };
+    let keys = args.map(parse_string).collect::<Result<Vec<String>, _>>()?;
+    if keys.is_empty() {
+      return Err(CommandError::WrongArity(name));
+    }
+    Ok(SUnion { op, destination, keys })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  fn sunion(op: SetOp, destination: Option<&str>) -> SUnion {
+    SUnion {
+      op,
+      destination: destination.map(String::from),
+      keys: vec!["key1".to_string(), "key2".to_string()],
+    }
+  }
+
+  #[test]
+  fn test_sunion_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$11\r\nSINTERSTORE\r\n$3\r\nkey\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SUnion = frame.try_into()?;
+    assert_eq!(ret.op, SetOp::Inter);
+    assert_eq!(ret.destination, Some("key".to_string()));
+    assert_eq!(ret.keys, vec!["key1".to_string(), "key2".to_string()]);
+
+    buf.extend_from_slice(b"*3\r\n$5\r\nsdiff\r\n$4\r\nkey1\r\n$4\r\nkey2\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: SUnion = frame.try_into()?;
+    assert_eq!(ret.op, SetOp::Diff);
+    assert_eq!(ret.destination, None);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_sunion_execute() {
+    let backend = Backend::new();
+    for (key, members) in [("key1", ["a", "b", "c"]), ("key2", ["c", "d", "e"])] {
+      for member in members {
+        backend.sadd(key, member).unwrap();
+      }
+    }
+
+    let ret = sunion(SetOp::Inter, None).execute(&backend);
+    assert_eq!(ret, RespArray::new([BulkString::from("c").into()]).into());
+    assert_eq!(sunion(SetOp::Union, Some("key")).execute(&backend), RespFrame::Integer(5));
+    assert_eq!(backend.scard("key"), Ok(5));
+    assert_eq!(sunion(SetOp::Diff, Some("key")).execute(&backend), RespFrame::Integer(2));
+    assert!(backend.sismember("key", "a").unwrap());
+
+    backend.sadd("key2", "a").unwrap();
+    backend.sadd("key2", "b").unwrap();
+    assert_eq!(sunion(SetOp::Diff, Some("key")).execute(&backend), RespFrame::Integer(0));
+    assert!(!backend.exists("key"));
+  }
+}
@@ -0,0 +1,282 @@
+use super::*;
+use crate::RespArray;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+type Parser = fn(RespArray) -> Result<Command, CommandError>;
+
+/// One entry of the command table, modeled after the output of redis `COMMAND INFO`.
+///
+/// `arity` counts the command name itself: a positive value is the exact number of
+/// frames, a negative value `-N` means at least `N` frames. Key positions are indexes
+/// into the request array, `last_key` may be negative to count from the end.
+#[derive(Debug)]
+pub struct CommandSpec {
+  pub name: &'static str,
+  pub arity: i64,
+  pub flags: &'static [&'static str],
+  pub first_key: i64,
+  pub last_key: i64,
+  pub step: i64,
+  parse: Parser,
+}
+
+impl CommandSpec {
+  const fn new(
+    name: &'static str,
+    arity: i64,
+    flags: &'static [&'static str],
+    (first_key, last_key, step): (i64, i64, i64),
+    parse: Parser,
+  ) -> Self {
+    Self { name, arity, flags, first_key, last_key, step, parse }
+  }
+
+  pub fn check_arity(&self, n_frames: usize) -> Result<(), CommandError> {
+    let n_frames = n_frames as i64;
+    let ok = if self.arity >= 0 { n_frames == self.arity } else { n_frames >= -self.arity };
+    if ok {
+      Ok(())
+    } else {
+      Err(CommandError::WrongArity(self.name.to_string()))
+    }
+  }
+
+  pub fn has_flag(&self, flag: &str) -> bool {
+    self.flags.contains(&flag)
+  }
+
+  pub(crate) fn parse(&self, value: RespArray) -> Result<Command, CommandError> {
+    (self.parse)(value)
+  }
+}
+
+fn parse<T>(value: RespArray) -> Result<Command, CommandError>
+where
+  T: TryFrom<RespArray, Error = CommandError> + Into<Command>,
+{
+  Ok(T::try_from(value)?.into())
+}
+
+const COMMANDS: &[CommandSpec] = &[
+  CommandSpec::new("echo", 2, &["fast"], (0, 0, 0), parse::<Echo>),
+  CommandSpec::new("get", 2, &["readonly", "fast"], (1, 1, 1), parse::<Get>),
+  CommandSpec::new("set", -3, &["write", "denyoom"], (1, 1, 1), parse::<Set>),
+  CommandSpec::new("incr", 2, &["write", "denyoom", "fast"], (1, 1, 1), parse::<Incr>),
+  CommandSpec::new("decr", 2, &["write", "denyoom", "fast"], (1, 1, 1), parse::<Incr>),
+  CommandSpec::new("incrby", 3, &["write", "denyoom", "fast"], (1, 1, 1), parse::<Incr>),
+  CommandSpec::new("decrby", 3, &["write", "denyoom", "fast"], (1, 1, 1), parse::<Incr>),
+  CommandSpec::new(
+    "incrbyfloat",
+    3,
+    &["write", "denyoom", "fast"],
+    (1, 1, 1),
+    parse::<IncrByFloat>,
+  ),
+  CommandSpec::new("append", 3, &["write", "denyoom", "fast"], (1, 1, 1), parse::<Append>),
+  CommandSpec::new("strlen", 2, &["readonly", "fast"], (1, 1, 1), parse::<StrLen>),
+  CommandSpec::new("getrange", 4, &["readonly"], (1, 1, 1), parse::<GetRange>),
+  CommandSpec::new("substr", 4, &["readonly"], (1, 1, 1), parse::<GetRange>),
+  CommandSpec::new("setrange", 4, &["write", "denyoom"], (1, 1, 1), parse::<SetRange>),
+  CommandSpec::new("getdel", 2, &["write", "fast"], (1, 1, 1), parse::<GetDel>),
+  CommandSpec::new("getex", -2, &["write", "fast"], (1, 1, 1), parse::<GetEx>),
+  CommandSpec::new("getset", 3, &["write", "denyoom", "fast"], (1, 1, 1), parse::<GetSet>),
+  CommandSpec::new("mget", -2, &["readonly", "fast"], (1, -1, 1), parse::<MGet>),
+  CommandSpec::new("mset", -3, &["write", "denyoom"], (1, -1, 2), parse::<MSet>),
+  CommandSpec::new("msetnx", -3, &["write", "denyoom"], (1, -1, 2), parse::<MSet>),
+  CommandSpec::new("setbit", 4, &["write", "denyoom"], (1, 1, 1), parse::<SetBit>),
+  CommandSpec::new("getbit", 3, &["readonly", "fast"], (1, 1, 1), parse::<GetBit>),
+  CommandSpec::new("bitcount", -2, &["readonly"], (1, 1, 1), parse::<BitCount>),
+  CommandSpec::new("bitpos", -3, &["readonly"], (1, 1, 1), parse::<BitPos>),
+  CommandSpec::new("bitop", -4, &["write", "denyoom"], (2, -1, 1), parse::<BitOp>),
+  CommandSpec::new("bitfield", -2, &["write", "denyoom"], (1, 1, 1), parse::<BitField>),
+  CommandSpec::new("bitfield_ro", -2, &["readonly", "fast"], (1, 1, 1), parse::<BitField>),
+  CommandSpec::new("lcs", -3, &["readonly"], (1, 2, 1), parse::<Lcs>),
+  CommandSpec::new("hget", 3, &["readonly", "fast"], (1, 1, 1), parse::<HGet>),
+  CommandSpec::new("hset", -4, &["write", "denyoom", "fast"], (1, 1, 1), parse::<HSet>),
+  CommandSpec::new("hmset", -4, &["write", "denyoom", "fast"], (1, 1, 1), parse::<HSet>),
+  CommandSpec::new("hsetnx", 4, &["write", "denyoom", "fast"], (1, 1, 1), parse::<HSetNx>),
+  CommandSpec::new("hdel", -3, &["write", "fast"], (1, 1, 1), parse::<HDel>),
+  CommandSpec::new("hexists", 3, &["readonly", "fast"], (1, 1, 1), parse::<HExists>),
+  CommandSpec::new("hlen", 2, &["readonly", "fast"], (1, 1, 1), parse::<HLen>),
+  CommandSpec::new("hkeys", 2, &["readonly"], (1, 1, 1), parse::<HKeys>),
+  CommandSpec::new("hvals", 2, &["readonly"], (1, 1, 1), parse::<HVals>),
+  CommandSpec::new("hstrlen", 3, &["readonly", "fast"], (1, 1, 1), parse::<HStrLen>),
+  CommandSpec::new("hincrby", 4, &["write", "denyoom", "fast"], (1, 1, 1), parse::<HIncrBy>),
+  CommandSpec::new(
+    "hincrbyfloat",
+    4,
+    &["write", "denyoom", "fast"],
+    (1, 1, 1),
+    parse::<HIncrByFloat>,
+  ),
+  CommandSpec::new("hrandfield", -2, &["readonly"], (1, 1, 1), parse::<HRandField>),
+  CommandSpec::new("hgetall", 2, &["readonly"], (1, 1, 1), parse::<HGetAll>),
+  CommandSpec::new("hmget", -3, &["readonly", "fast"], (1, 1, 1), parse::<HMGet>),
+  CommandSpec::new("sadd", -3, &["write", "denyoom", "fast"], (1, 1, 1), parse::<SAdd>),
+  CommandSpec::new("smembers", 2, &["readonly"], (1, 1, 1), parse::<SMembers>),
+  CommandSpec::new("srandmember", -2, &["readonly"], (1, 1, 1), parse::<SRandMember>),
+  CommandSpec::new("spop", -2, &["write", "fast"], (1, 1, 1), parse::<SPop>),
+  CommandSpec::new("srem", -3, &["write", "fast"], (1, 1, 1), parse::<SRem>),
+  CommandSpec::new("scard", 2, &["readonly", "fast"], (1, 1, 1), parse::<SCard>),
+  CommandSpec::new("smismember", -3, &["readonly", "fast"], (1, 1, 1), parse::<SMIsMember>),
+  CommandSpec::new("smove", 4, &["write", "fast"], (1, 2, 1), parse::<SMove>),
+  CommandSpec::new("sunion", -2, &["readonly"], (1, -1, 1), parse::<SUnion>),
+  CommandSpec::new("sinter", -2, &["readonly"], (1, -1, 1), parse::<SUnion>),
+  CommandSpec::new("sdiff", -2, &["readonly"], (1, -1, 1), parse::<SUnion>),
+  CommandSpec::new("sunionstore", -3, &["write", "denyoom"], (1, -1, 1), parse::<SUnion>),
+  CommandSpec::new("sinterstore", -3, &["write", "denyoom"], (1, -1, 1), parse::<SUnion>),
+  CommandSpec::new("sdiffstore", -3, &["write", "denyoom"], (1, -1, 1), parse::<SUnion>),
+  CommandSpec::new("sintercard", -3, &["readonly", "movablekeys"], (0, 0, 0), parse::<SInterCard>),
+  CommandSpec::new("sismember", 3, &["readonly", "fast"], (1, 1, 1), parse::<SIsMember>),
+  CommandSpec::new("expire", -3, &["write", "fast"], (1, 1, 1), parse::<Expire>),
+  CommandSpec::new("pexpire", -3, &["write", "fast"], (1, 1, 1), parse::<Expire>),
+  CommandSpec::new("expireat", -3, &["write", "fast"], (1, 1, 1), parse::<Expire>),
+  CommandSpec::new("pexpireat", -3, &["write", "fast"], (1, 1, 1), parse::<Expire>),
+  CommandSpec::new("ttl", 2, &["readonly", "fast"], (1, 1, 1), parse::<Ttl>),
+  CommandSpec::new("pttl", 2, &["readonly", "fast"], (1, 1, 1), parse::<Ttl>),
+  CommandSpec::new("expiretime", 2, &["readonly", "fast"], (1, 1, 1), parse::<ExpireTime>),
+  CommandSpec::new("pexpiretime", 2, &["readonly", "fast"], (1, 1, 1), parse::<ExpireTime>),
+  CommandSpec::new("persist", 2, &["write", "fast"], (1, 1, 1), parse::<Persist>),
+  CommandSpec::new("type", 2, &["readonly", "fast"], (1, 1, 1), parse::<Type>),
+  CommandSpec::new("del", -2, &["write"], (1, -1, 1), parse::<Del>),
+  CommandSpec::new("unlink", -2, &["write", "fast"], (1, -1, 1), parse::<Del>),
+  CommandSpec::new("exists", -2, &["readonly", "fast"], (1, -1, 1), parse::<Exists>),
+  CommandSpec::new("touch", -2, &["readonly", "fast"], (1, -1, 1), parse::<Exists>),
+  CommandSpec::new("rename", 3, &["write"], (1, 2, 1), parse::<Rename>),
+  CommandSpec::new("renamenx", 3, &["write", "fast"], (1, 2, 1), parse::<Rename>),
+  CommandSpec::new("copy", -3, &["write"], (1, 2, 1), parse::<CopyKey>),
+  CommandSpec::new("scan", -2, &["readonly"], (0, 0, 0), parse::<Scan>),
+  CommandSpec::new("hscan", -3, &["readonly"], (1, 1, 1), parse::<HScan>),
+  CommandSpec::new("sscan", -3, &["readonly"], (1, 1, 1), parse::<SScan>),
+  CommandSpec::new("keys", 2, &["readonly"], (0, 0, 0), parse::<Keys>),
+  CommandSpec::new("lpush", -3, &["write", "denyoom", "fast"], (1, 1, 1), parse::<LPush>),
+  CommandSpec::new("rpush", -3, &["write", "denyoom", "fast"], (1, 1, 1), parse::<LPush>),
+  CommandSpec::new("lpushx", -3, &["write", "denyoom", "fast"], (1, 1, 1), parse::<LPush>),
+  CommandSpec::new("rpushx", -3, &["write", "denyoom", "fast"], (1, 1, 1), parse::<LPush>),
+  CommandSpec::new("lpop", -2, &["write", "fast"], (1, 1, 1), parse::<LPop>),
+  CommandSpec::new("rpop", -2, &["write", "fast"], (1, 1, 1), parse::<LPop>),
+  CommandSpec::new("llen", 2, &["readonly", "fast"], (1, 1, 1), parse::<LLen>),
+  CommandSpec::new("lrange", 4, &["readonly"], (1, 1, 1), parse::<LRange>),
+  CommandSpec::new("lindex", 3, &["readonly"], (1, 1, 1), parse::<LIndex>),
+  CommandSpec::new("lset", 4, &["write", "denyoom"], (1, 1, 1), parse::<LSet>),
+  CommandSpec::new("lrem", 4, &["write"], (1, 1, 1), parse::<LRem>),
+  CommandSpec::new("ltrim", 4, &["write"], (1, 1, 1), parse::<LTrim>),
+  CommandSpec::new("linsert", 5, &["write", "denyoom"], (1, 1, 1), parse::<LInsert>),
+  CommandSpec::new("lpos", -3, &["readonly"], (1, 1, 1), parse::<LPos>),
+  CommandSpec::new("lmove", 5, &["write", "denyoom"], (1, 2, 1), parse::<LMove>),
+  CommandSpec::new("lmpop", -4, &["write", "movablekeys"], (0, 0, 0), parse::<LMPop>),
+  CommandSpec::new("blpop", -3, &["write", "blocking"], (1, -2, 1), parse::<BLPop>),
+  CommandSpec::new("brpop", -3, &["write", "blocking"], (1, -2, 1), parse::<BLPop>),
+  CommandSpec::new("blmove", 6, &["write", "denyoom", "blocking"], (1, 2, 1), parse::<BLMove>),
+  CommandSpec::new("zadd", -4, &["write", "denyoom", "fast"], (1, 1, 1), parse::<ZAdd>),
+  CommandSpec::new("zrange", -4, &["readonly"], (1, 1, 1), parse::<ZRange>),
+  CommandSpec::new("zrank", -3, &["readonly", "fast"], (1, 1, 1), parse::<ZRank>),
+  CommandSpec::new("zrevrank", -3, &["readonly", "fast"], (1, 1, 1), parse::<ZRank>),
+  CommandSpec::new("zscore", 3, &["readonly", "fast"], (1, 1, 1), parse::<ZScore>),
+  CommandSpec::new("zmscore", -3, &["readonly", "fast"], (1, 1, 1), parse::<ZMScore>),
+  CommandSpec::new("zincrby", 4, &["write", "denyoom", "fast"], (1, 1, 1), parse::<ZIncrBy>),
+  CommandSpec::new("zrem", -3, &["write", "fast"], (1, 1, 1), parse::<ZRem>),
+  CommandSpec::new("zcard", 2, &["readonly", "fast"], (1, 1, 1), parse::<ZCard>),
+  CommandSpec::new("zcount", 4, &["readonly", "fast"], (1, 1, 1), parse::<ZCount>),
+  CommandSpec::new("zunion", -3, &["readonly", "movablekeys"], (0, 0, 0), parse::<ZUnion>),
+  CommandSpec::new("zinter", -3, &["readonly", "movablekeys"], (0, 0, 0), parse::<ZUnion>),
+  CommandSpec::new("zdiff", -3, &["readonly", "movablekeys"], (0, 0, 0), parse::<ZUnion>),
+  CommandSpec::new(
+    "zunionstore",
+    -4,
+    &["write", "denyoom", "movablekeys"],
+    (1, 1, 1),
+    parse::<ZUnion>,
+  ),
+  CommandSpec::new(
+    "zinterstore",
+    -4,
+    &["write", "denyoom", "movablekeys"],
+    (1, 1, 1),
+    parse::<ZUnion>,
+  ),
+  CommandSpec::new(
+    "zdiffstore",
+    -4,
+    &["write", "denyoom", "movablekeys"],
+    (1, 1, 1),
+    parse::<ZUnion>,
+  ),
+  CommandSpec::new("zintercard", -3, &["readonly", "movablekeys"], (0, 0, 0), parse::<ZInterCard>),
+  CommandSpec::new("zrangestore", -5, &["write", "denyoom"], (1, 2, 1), parse::<ZRangeStore>),
+  CommandSpec::new("zremrangebyscore", 4, &["write"], (1, 1, 1), parse::<ZRemRange>),
+  CommandSpec::new("zremrangebyrank", 4, &["write"], (1, 1, 1), parse::<ZRemRange>),
+  CommandSpec::new("zremrangebylex", 4, &["write"], (1, 1, 1), parse::<ZRemRange>),
+  CommandSpec::new("zpopmin", -2, &["write", "fast"], (1, 1, 1), parse::<ZPopMin>),
+  CommandSpec::new("zpopmax", -2, &["write", "fast"], (1, 1, 1), parse::<ZPopMin>),
+  CommandSpec::new("zmpop", -4, &["write", "movablekeys"], (0, 0, 0), parse::<ZMPop>),
+  CommandSpec::new("bzpopmin", -3, &["write", "blocking", "fast"], (1, -2, 1), parse::<BZPopMin>),
+  CommandSpec::new("bzpopmax", -3, &["write", "blocking", "fast"], (1, -2, 1), parse::<BZPopMin>),
+  CommandSpec::new("bzmpop", -5, &["write", "blocking", "movablekeys"], (0, 0, 0), parse::<ZMPop>),
+  CommandSpec::new("blmpop", -5, &["write", "blocking", "movablekeys"], (0, 0, 0), parse::<LMPop>),
+  CommandSpec::new("xadd", -5, &["write", "denyoom", "fast"], (1, 1, 1), parse::<XAdd>),
+  CommandSpec::new("xrange", -4, &["readonly"], (1, 1, 1), parse::<XRange>),
+  CommandSpec::new("xrevrange", -4, &["readonly"], (1, 1, 1), parse::<XRange>),
+  CommandSpec::new("xlen", 2, &["readonly", "fast"], (1, 1, 1), parse::<XLen>),
+  CommandSpec::new("xdel", -3, &["write", "fast"], (1, 1, 1), parse::<XDel>),
+  CommandSpec::new("xtrim", -4, &["write"], (1, 1, 1), parse::<XTrim>),
+  CommandSpec::new("xgroup", -2, &["write"], (2, 2, 1), parse::<XGroup>),
+  CommandSpec::new(
+    "xread",
+    -4,
+    &["readonly", "blocking", "movablekeys"],
+    (0, 0, 0),
+    parse::<XRead>,
+  ),
+  CommandSpec::new(
+    "xreadgroup",
+    -7,
+    &["write", "blocking", "movablekeys"],
+    (0, 0, 0),
+    parse::<XReadGroup>,
+  ),
+  CommandSpec::new("xack", -4, &["write", "fast"], (1, 1, 1), parse::<XAck>),
+  CommandSpec::new("xpending", -3, &["readonly"], (1, 1, 1), parse::<XPending>),
+  CommandSpec::new("xclaim", -6, &["write", "fast"], (1, 1, 1), parse::<XClaim>),
+  CommandSpec::new("xautoclaim", -6, &["write", "fast"], (1, 1, 1), parse::<XAutoClaim>),
+  CommandSpec::new("xinfo", -2, &["readonly"], (2, 2, 1), parse::<XInfo>),
+  CommandSpec::new("pfadd", -2, &["write", "denyoom", "fast"], (1, 1, 1), parse::<PFAdd>),
+  CommandSpec::new("pfcount", -2, &["readonly"], (1, -1, 1), parse::<PFCount>),
+  CommandSpec::new("pfmerge", -2, &["write", "denyoom"], (1, -1, 1), parse::<PFMerge>),
+];
+
+lazy_static! {
+  static ref COMMAND_TABLE: HashMap<&'static str, &'static CommandSpec> =
+    COMMANDS.iter().map(|spec| (spec.name, spec)).collect();
+}
+
+/// Look up a command by name, ignoring ASCII case.
+pub fn lookup_command(name: &[u8]) -> Option<&'static CommandSpec> {
+  let name = String::from_utf8_lossy(name).to_ascii_lowercase();
+  COMMAND_TABLE.get(name.as_str()).copied()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_lookup_command_ignores_case() {
+    let spec = lookup_command(b"HGetAll").expect("hgetall should be registered");
+    assert_eq!(spec.name, "hgetall");
+    assert!(spec.has_flag("readonly"));
+    assert!(lookup_command(b"nope").is_none());
+  }
+
+  #[test]
+  fn test_check_arity() {
+    let get = lookup_command(b"get").unwrap();
+    assert!(get.check_arity(2).is_ok());
+    assert!(get.check_arity(3).is_err());
+
+    let sadd = lookup_command(b"sadd").unwrap();
+    assert!(sadd.check_arity(2).is_err());
+    assert!(sadd.check_arity(5).is_ok());
+  }
+}
@@ -0,0 +1,82 @@
+use super::{extract_args, extract_command_name, CommandError, CommandExecutor};
+use crate::{now_ms, Backend, RespArray, RespFrame};
+
+/// TTL key
+/// PTTL key
+///  SET mykey "Hello"
+/// "OK"
+///  EXPIRE mykey 10
+/// (integer) 1
+///  TTL mykey
+/// (integer) 10
+#[derive(Debug)]
+pub struct Ttl {
+  pub(crate) key: String,
+  // milliseconds per unit of the reply
+  pub(crate) unit: i64,
+}
+
+impl CommandExecutor for Ttl {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ttl = match backend.expire_time(&self.key) {
+      None => -2,
+      Some(None) => -1,
+      Some(Some(at)) => {
+        let remaining = (at - now_ms()).max(0);
+        (remaining + self.unit / 2) / self.unit
+      }
+    };
+    RespFrame::Integer(ttl)
+  }
+}
+
+impl TryFrom<RespArray> for Ttl {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let unit = match extract_command_name(&value)?.as_str() {
+      "pttl" => 1,
+      _ => 1000,
+    };
+
+    let mut args = extract_args(value, 1)?.into_iter();
+    match args.next() {
+      Some(RespFrame::BulkString(key)) => Ok(Ttl { key: key.into(), unit }),
+      _ => Err(CommandError::InvalidArgument("Invalid key".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{ExpireCondition, RespDecode};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_ttl_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$4\r\nPTTL\r\n$5\r\nmykey\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Ttl = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+    assert_eq!(ret.unit, 1);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_ttl_execute() {
+    let backend = Backend::new();
+    let ttl = || Ttl { key: "mykey".to_string(), unit: 1000 }.execute(&backend);
+
+    assert_eq!(ttl(), RespFrame::Integer(-2));
+
+    backend.set("mykey".to_string(), RespFrame::BulkString(b"hello".into()));
+    assert_eq!(ttl(), RespFrame::Integer(-1));
+
+    backend.expire_at("mykey", now_ms() + 10_000, ExpireCondition::default());
+    assert_eq!(ttl(), RespFrame::Integer(10));
+  }
+}
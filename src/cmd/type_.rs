@@ -0,0 +1,69 @@
+use super::{extract_args, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame, SimpleString};
+
+/// TYPE key
+///  SET key1 "value"
+/// "OK"
+///  SADD key2 "value"
+/// (integer) 1
+///  TYPE key1
+/// "string"
+///  TYPE key2
+/// "set"
+#[derive(Debug)]
+pub struct Type {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for Type {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    SimpleString::new(backend.type_of(&self.key).unwrap_or("none")).into()
+  }
+}
+
+impl TryFrom<RespArray> for Type {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match args.next() {
+      Some(RespFrame::BulkString(key)) => Ok(Type { key: key.into() }),
+      _ => Err(CommandError::InvalidArgument("Invalid key".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, SAdd};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_type_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$4\r\ntype\r\n$5\r\nmykey\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Type = frame.try_into()?;
+    assert_eq!(ret.key, "mykey");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_type_execute() {
+    let backend = Backend::new();
+    backend.set("key1".to_string(), RespFrame::BulkString(b"value".into()));
+    SAdd { key: "key2".to_string(), members: vec!["value".to_string()] }.execute(&backend);
+
+    let ret = Type { key: "key1".to_string() }.execute(&backend);
+    assert_eq!(ret, SimpleString::new("string").into());
+
+    let ret = Type { key: "key2".to_string() }.execute(&backend);
+    assert_eq!(ret, SimpleString::new("set").into());
+
+    let ret = Type { key: "key3".to_string() }.execute(&backend);
+    assert_eq!(ret, SimpleString::new("none").into());
+  }
+}
@@ -1,11 +1,92 @@
-use super::{CommandExecutor, RESP_OK};
-use crate::{Backend, RespFrame};
+use super::{error_reply, extract_args, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+use tracing::warn;
+
+// redis stops quoting arguments once the reply grows past this many bytes
+const MAX_ARGS_LEN: usize = 128;
 
 #[derive(Debug)]
-pub struct Unrecognized;
+pub struct Unrecognized {
+  pub(crate) name: String,
+  pub(crate) args: Vec<String>,
+}
 
 impl CommandExecutor for Unrecognized {
-  fn execute(self, _: &Backend) -> RespFrame {
-    RESP_OK.clone()
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let total = backend.count_unknown_command();
+    warn!("Unknown command #{}: {:?} with args {:?}", total, self.name, self.args);
+    let mut args = String::new();
+    for arg in &self.args {
+      if args.len() >= MAX_ARGS_LEN {
+        break;
+      }
+      let arg: String = arg.chars().take(MAX_ARGS_LEN - args.len()).collect();
+      args.push_str(&format!("'{}' ", arg));
+    }
+    let name: String = self.name.chars().take(MAX_ARGS_LEN).collect();
+    error_reply(format!("ERR unknown command '{}', with args beginning with: {}", name, args))
+  }
+}
+
+impl TryFrom<RespArray> for Unrecognized {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 0)?.into_iter().map(|frame| match frame {
+      RespFrame::BulkString(s) => String::from_utf8_lossy(s.as_ref()).into_owned(),
+      frame => format!("{:?}", frame),
+    });
+    match args.next() {
+      Some(name) => Ok(Unrecognized { name, args: args.collect() }),
+      None => Err(CommandError::InvalidCommand("command must not be empty".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, SimpleError};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_unrecognized_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$3\r\nfoo\r\n$3\r\nbar\r\n$3\r\nbaz\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: Unrecognized = frame.try_into()?;
+    assert_eq!(ret.name, "foo");
+    assert_eq!(ret.args, vec!["bar".to_string(), "baz".to_string()]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_unrecognized_execute() {
+    let backend = Backend::new();
+    let cmd = Unrecognized { name: "foo".to_string(), args: vec!["bar".to_string()] };
+    let ret = cmd.execute(&backend);
+
+    assert_eq!(
+      ret,
+      SimpleError::new("ERR unknown command 'foo', with args beginning with: 'bar' ").into()
+    );
+
+    let cmd = Unrecognized { name: "foo".to_string(), args: vec![] };
+    let ret = cmd.execute(&backend);
+
+    assert_eq!(
+      ret,
+      SimpleError::new("ERR unknown command 'foo', with args beginning with: ").into()
+    );
+    assert_eq!(backend.unknown_commands(), 2);
+
+    // a line break in an argument must not end the reply early
+    let cmd = Unrecognized { name: "foo".to_string(), args: vec!["a\r\n+OK".to_string()] };
+    assert_eq!(
+      cmd.execute(&backend),
+      SimpleError::new("ERR unknown command 'foo', with args beginning with: 'a  +OK' ").into()
+    );
   }
 }
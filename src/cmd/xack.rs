@@ -0,0 +1,77 @@
+use super::{extract_args, parse_stream_id, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame, StreamId};
+
+/// XACK key group id [id ...]
+///  XACK mystream mygroup 1526569495631-0
+/// (integer) 1
+#[derive(Debug)]
+pub struct XAck {
+  pub(crate) key: String,
+  pub(crate) group: String,
+  pub(crate) ids: Vec<StreamId>,
+}
+
+impl CommandExecutor for XAck {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.xack(&self.key, &self.group, &self.ids) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for XAck {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, group) = match (args.next(), args.next()) {
+      (Some(key), Some(group)) => (parse_string(key)?, parse_string(group)?),
+      _ => return Err(CommandError::WrongArity("xack".to_string())),
+    };
+    let ids = args.map(parse_stream_id).collect::<Result<Vec<StreamId>, _>>()?;
+    if ids.is_empty() {
+      return Err(CommandError::WrongArity("xack".to_string()));
+    }
+    Ok(XAck { key, group, ids })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{GroupRead, RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xack_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*4\r\n$4\r\nXACK\r\n$8\r\nmystream\r\n$7\r\nmygroup\r\n$15\r\n1526569495631-0\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XAck = frame.try_into()?;
+    assert_eq!(ret.key, "mystream");
+    assert_eq!(ret.group, "mygroup");
+    assert_eq!(ret.ids, vec![StreamId::new(1526569495631, 0)]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xack_execute() -> Result<()> {
+    let backend = Backend::new();
+    let id = StreamId::new(1526569495631, 0);
+    backend.xadd("mystream", StreamIdSpec::Explicit(id), vec![], false, None)?;
+    backend.xgroup_create("mystream", "mygroup", Some(StreamId::MIN), false, None)?;
+    let streams = [("mystream".to_string(), GroupRead::New)];
+    backend.xreadgroup("mygroup", "alice", &streams, None, false)?;
+
+    let xack = || XAck { key: "mystream".to_string(), group: "mygroup".to_string(), ids: vec![id] };
+    assert_eq!(xack().execute(&backend), RespFrame::Integer(1));
+    assert_eq!(xack().execute(&backend), RespFrame::Integer(0));
+
+    Ok(())
+  }
+}
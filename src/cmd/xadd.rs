@@ -0,0 +1,118 @@
+use super::{
+  extract_args, parse_string, xtrim::parse_trim, CommandError, CommandExecutor, RESP_NULL,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame, StreamId, StreamIdSpec, StreamTrim};
+
+/// XADD key [NOMKSTREAM] [<MAXLEN | MINID> [= | ~] threshold [LIMIT count]] <* | id> field value
+/// [field value ...]
+///  XADD mystream * name Sara surname OConnor
+/// "1609459200000-0"
+///  XADD mystream 1609459200000-* field1 value1
+/// "1609459200000-1"
+///  XADD mystream MAXLEN 1 5-0 field1 value1
+/// (error) ERR The ID specified in XADD is equal or smaller than the target stream top item
+#[derive(Debug)]
+pub struct XAdd {
+  pub(crate) key: String,
+  pub(crate) id: StreamIdSpec,
+  pub(crate) fields: Vec<RespFrame>,
+  pub(crate) nomkstream: bool,
+  pub(crate) trim: Option<StreamTrim>,
+}
+
+/// Parse the `<* | id>` argument of `XADD`, where the sequence of an ID may be `*` too.
+fn parse_id_spec(id: &str) -> Result<StreamIdSpec, CommandError> {
+  match id.split_once('-') {
+    _ if id == "*" => Ok(StreamIdSpec::Auto),
+    Some((ms, "*")) => Ok(StreamIdSpec::Partial(StreamId::parse(ms, 0)?.ms)),
+    _ => Ok(StreamIdSpec::Explicit(StreamId::parse(id, 0)?)),
+  }
+}
+
+impl CommandExecutor for XAdd {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.xadd(&self.key, self.id, self.fields, self.nomkstream, self.trim) {
+      Ok(Some(id)) => BulkString::from(id.to_string()).into(),
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for XAdd {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter().peekable();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+
+    let mut nomkstream = false;
+    let mut trim = None;
+    let id = loop {
+      let arg = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+      match arg.to_ascii_lowercase().as_str() {
+        "nomkstream" => nomkstream = true,
+        "maxlen" | "minid" => trim = Some(parse_trim(&arg, &mut args)?),
+        _ => break parse_id_spec(&arg)?,
+      }
+    };
+
+    let fields: Vec<RespFrame> = args.collect();
+    if fields.is_empty() || !fields.len().is_multiple_of(2) {
+      return Err(CommandError::WrongArity("xadd".to_string()));
+    }
+    Ok(XAdd { key, id, fields, nomkstream, trim })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, TrimStrategy};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xadd_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*9\r\n$4\r\nXADD\r\n$8\r\nmystream\r\n$10\r\nNOMKSTREAM\r\n$6\r\nMAXLEN\r\n$1\r\n~\r\n$4\r\n1000\r\n$3\r\n5-*\r\n$4\r\nname\r\n$4\r\nSara\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XAdd = frame.try_into()?;
+    assert_eq!(ret.key, "mystream");
+    assert_eq!(ret.id, StreamIdSpec::Partial(5));
+    assert_eq!(ret.fields.len(), 2);
+    assert!(ret.nomkstream);
+    let trim = StreamTrim { strategy: TrimStrategy::MaxLen(1000), approx: true, limit: None };
+    assert_eq!(ret.trim, Some(trim));
+
+    buf.extend_from_slice(b"*4\r\n$4\r\nxadd\r\n$8\r\nmystream\r\n$1\r\n*\r\n$4\r\nname\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(XAdd::try_from(frame), Err(CommandError::WrongArity(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xadd_execute() {
+    let backend = Backend::new();
+    let xadd = |id, nomkstream| XAdd {
+      key: "mystream".to_string(),
+      id,
+      fields: vec![BulkString::from("name").into(), BulkString::from("Sara").into()],
+      nomkstream,
+      trim: None,
+    };
+
+    assert_eq!(xadd(StreamIdSpec::Auto, true).execute(&backend), RESP_NULL.clone());
+    let ret = xadd(StreamIdSpec::Explicit(StreamId::new(5, 0)), false).execute(&backend);
+    assert_eq!(ret, BulkString::from("5-0").into());
+    assert_eq!(
+      xadd(StreamIdSpec::Partial(5), true).execute(&backend),
+      BulkString::from("5-1").into()
+    );
+    let ret = xadd(StreamIdSpec::Partial(4), false).execute(&backend);
+    assert_eq!(ret, CommandError::StreamIdTooSmall.into());
+  }
+}
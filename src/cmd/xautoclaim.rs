@@ -0,0 +1,144 @@
+use super::{
+  extract_args, parse_integer, parse_string, xrange::encode_entries, xrange::parse_bound,
+  CommandError, CommandExecutor,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame, StreamId};
+
+/// XAUTOCLAIM key group consumer min-idle-time start [COUNT count] [JUSTID]
+///  XAUTOCLAIM mystream mygroup Alice 3600000 0-0 COUNT 25
+/// 1) "0-0"
+/// 2) 1) 1) "1609338752495-0"
+///       2) 1) "field"
+///          2) "value"
+/// 3) (empty array)
+#[derive(Debug)]
+pub struct XAutoClaim {
+  pub(crate) key: String,
+  pub(crate) group: String,
+  pub(crate) consumer: String,
+  pub(crate) min_idle: i64,
+  pub(crate) start: StreamId,
+  pub(crate) count: usize,
+  pub(crate) just_id: bool,
+}
+
+fn encode_ids(ids: impl IntoIterator<Item = StreamId>) -> RespFrame {
+  let ids = ids.into_iter().map(|id| BulkString::from(id.to_string()).into());
+  RespArray::new(ids.collect::<Vec<RespFrame>>()).into()
+}
+
+impl CommandExecutor for XAutoClaim {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ret = backend.xautoclaim(
+      &self.key,
+      &self.group,
+      &self.consumer,
+      self.min_idle,
+      (self.start, self.count),
+      self.just_id,
+    );
+    match ret {
+      Ok((next, claimed, deleted)) => {
+        let claimed = match self.just_id {
+          true => encode_ids(claimed.into_iter().map(|(id, _)| id)),
+          false => encode_entries(claimed),
+        };
+        RespArray::new([BulkString::from(next.to_string()).into(), claimed, encode_ids(deleted)])
+          .into()
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for XAutoClaim {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, group, consumer, min_idle, start) =
+      match (args.next(), args.next(), args.next(), args.next(), args.next()) {
+        (Some(key), Some(group), Some(consumer), Some(min_idle), Some(start)) => (
+          parse_string(key)?,
+          parse_string(group)?,
+          parse_string(consumer)?,
+          parse_integer(min_idle)?.max(0),
+          parse_bound(start, true)?,
+        ),
+        _ => return Err(CommandError::WrongArity("xautoclaim".to_string())),
+      };
+
+    let (mut count, mut just_id) = (100, false);
+    while let Some(opt) = args.next() {
+      match parse_string(opt)?.to_ascii_lowercase().as_str() {
+        "count" => {
+          count = match parse_integer(args.next().ok_or(CommandError::SyntaxError)?)? {
+            n if n <= 0 || n > i64::MAX / 10 => {
+              return Err(CommandError::InvalidArgument("COUNT must be > 0".to_string()))
+            }
+            n => n as usize,
+          }
+        }
+        "justid" => just_id = true,
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+    Ok(XAutoClaim { key, group, consumer, min_idle, start, count, just_id })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{GroupRead, RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xautoclaim_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*9\r\n$10\r\nXAUTOCLAIM\r\n$8\r\nmystream\r\n$7\r\nmygroup\r\n$5\r\nAlice\r\n$7\r\n3600000\r\n$3\r\n0-0\r\n$5\r\nCOUNT\r\n$2\r\n25\r\n$6\r\nJUSTID\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XAutoClaim = frame.try_into()?;
+    assert_eq!((ret.key.as_str(), ret.group.as_str()), ("mystream", "mygroup"));
+    assert_eq!(ret.consumer, "Alice");
+    assert_eq!(ret.min_idle, 3600000);
+    assert_eq!(ret.start, StreamId::MIN);
+    assert_eq!(ret.count, 25);
+    assert!(ret.just_id);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xautoclaim_execute() -> Result<()> {
+    let backend = Backend::new();
+    let id = StreamId::new(1609338752495, 0);
+    let fields = vec![BulkString::from("field").into(), BulkString::from("value").into()];
+    backend.xadd("mystream", StreamIdSpec::Explicit(id), fields.clone(), false, None)?;
+    backend.xgroup_create("mystream", "mygroup", Some(StreamId::MIN), false, None)?;
+    let streams = [("mystream".to_string(), GroupRead::New)];
+    backend.xreadgroup("mygroup", "Bob", &streams, None, false)?;
+
+    let ret = XAutoClaim {
+      key: "mystream".to_string(),
+      group: "mygroup".to_string(),
+      consumer: "Alice".to_string(),
+      min_idle: 0,
+      start: StreamId::MIN,
+      count: 25,
+      just_id: false,
+    }
+    .execute(&backend);
+    let expected = RespArray::new([
+      BulkString::from("0-0").into(),
+      encode_entries(vec![(id, fields)]),
+      RespArray::new([]).into(),
+    ]);
+    assert_eq!(ret, expected.into());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,141 @@
+use super::{
+  extract_args, parse_integer, parse_stream_id, parse_string, xrange::encode_entries, CommandError,
+  CommandExecutor,
+};
+use crate::{now_ms, Backend, BulkString, ClaimOptions, RespArray, RespFrame, StreamId};
+
+/// XCLAIM key group consumer min-idle-time id [id ...] [IDLE ms] [TIME unix-time-milliseconds]
+/// [RETRYCOUNT count] [FORCE] [JUSTID] [LASTID lastid]
+///  XCLAIM mystream mygroup Alice 3600000 1526569498055-0
+/// 1) 1) "1526569498055-0"
+///    2) 1) "message"
+///       2) "orange"
+#[derive(Debug)]
+pub struct XClaim {
+  pub(crate) key: String,
+  pub(crate) group: String,
+  pub(crate) consumer: String,
+  pub(crate) min_idle: i64,
+  pub(crate) ids: Vec<StreamId>,
+  pub(crate) opts: ClaimOptions,
+}
+
+impl CommandExecutor for XClaim {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ret =
+      backend.xclaim(&self.key, &self.group, &self.consumer, self.min_idle, &self.ids, &self.opts);
+    match ret {
+      Ok(claimed) if self.opts.just_id => {
+        let ids = claimed.into_iter().map(|(id, _)| BulkString::from(id.to_string()).into());
+        RespArray::new(ids.collect::<Vec<RespFrame>>()).into()
+      }
+      Ok(claimed) => encode_entries(claimed),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for XClaim {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter().peekable();
+    let (key, group, consumer, min_idle) =
+      match (args.next(), args.next(), args.next(), args.next()) {
+        (Some(key), Some(group), Some(consumer), Some(min_idle)) => (
+          parse_string(key)?,
+          parse_string(group)?,
+          parse_string(consumer)?,
+          parse_integer(min_idle)?.max(0),
+        ),
+        _ => return Err(CommandError::WrongArity("xclaim".to_string())),
+      };
+
+    // IDs run until the first option
+    let mut ids = vec![];
+    while let Some(Ok(id)) = args.peek().cloned().map(parse_stream_id) {
+      ids.push(id);
+      args.next();
+    }
+    if ids.is_empty() {
+      return Err(CommandError::InvalidStreamId);
+    }
+
+    let mut opts = ClaimOptions::default();
+    while let Some(opt) = args.next() {
+      let mut value = || args.next().ok_or(CommandError::SyntaxError);
+      match parse_string(opt)?.to_ascii_lowercase().as_str() {
+        "idle" => opts.delivered_at = Some(now_ms() - parse_integer(value()?)?.max(0)),
+        "time" => opts.delivered_at = Some(parse_integer(value()?)?),
+        "retrycount" => {
+          opts.retry_count = match parse_integer(value()?)? {
+            n if n < 0 => return Err(CommandError::NotInteger),
+            n => Some(n as u64),
+          }
+        }
+        "force" => opts.force = true,
+        "justid" => opts.just_id = true,
+        "lastid" => opts.last_id = Some(parse_stream_id(value()?)?),
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+    Ok(XClaim { key, group, consumer, min_idle, ids, opts })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{GroupRead, RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xclaim_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*11\r\n$6\r\nXCLAIM\r\n$8\r\nmystream\r\n$7\r\nmygroup\r\n$5\r\nAlice\r\n$7\r\n3600000\r\n$15\r\n1526569498055-0\r\n$1\r\n7\r\n$10\r\nRETRYCOUNT\r\n$1\r\n2\r\n$5\r\nFORCE\r\n$6\r\nJUSTID\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XClaim = frame.try_into()?;
+    assert_eq!(ret.key, "mystream");
+    assert_eq!(ret.group, "mygroup");
+    assert_eq!(ret.consumer, "Alice");
+    assert_eq!(ret.min_idle, 3600000);
+    assert_eq!(ret.ids, vec![StreamId::new(1526569498055, 0), StreamId::new(7, 0)]);
+    let opts =
+      ClaimOptions { retry_count: Some(2), force: true, just_id: true, ..Default::default() };
+    assert_eq!(ret.opts, opts);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xclaim_execute() -> Result<()> {
+    let backend = Backend::new();
+    let id = StreamId::new(1526569498055, 0);
+    let fields = vec![BulkString::from("message").into(), BulkString::from("orange").into()];
+    backend.xadd("mystream", StreamIdSpec::Explicit(id), fields.clone(), false, None)?;
+    backend.xgroup_create("mystream", "mygroup", Some(StreamId::MIN), false, None)?;
+    let streams = [("mystream".to_string(), GroupRead::New)];
+    backend.xreadgroup("mygroup", "Bob", &streams, None, false)?;
+
+    let xclaim = |min_idle, opts| XClaim {
+      key: "mystream".to_string(),
+      group: "mygroup".to_string(),
+      consumer: "Alice".to_string(),
+      min_idle,
+      ids: vec![id],
+      opts,
+    };
+    let ret = xclaim(3600000, ClaimOptions::default()).execute(&backend);
+    assert_eq!(ret, RespArray::new([]).into());
+    let ret = xclaim(0, ClaimOptions::default()).execute(&backend);
+    assert_eq!(ret, encode_entries(vec![(id, fields)]));
+    let opts = ClaimOptions { just_id: true, ..Default::default() };
+    let ret = xclaim(0, opts).execute(&backend);
+    assert_eq!(ret, RespArray::new([BulkString::from("1526569498055-0").into()]).into());
+
+    Ok(())
+  }
+}
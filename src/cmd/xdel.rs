@@ -0,0 +1,71 @@
+use super::{extract_args, parse_stream_id, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame, StreamId};
+
+/// XDEL key id [id ...]
+///  XADD mystream 1538561700640-0 a 1
+/// "1538561700640-0"
+///  XDEL mystream 1538561700640-0 1538561700640-1
+/// (integer) 1
+#[derive(Debug)]
+pub struct XDel {
+  pub(crate) key: String,
+  pub(crate) ids: Vec<StreamId>,
+}
+
+impl CommandExecutor for XDel {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.xdel(&self.key, &self.ids) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for XDel {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let ids = args.map(parse_stream_id).collect::<Result<Vec<StreamId>, _>>()?;
+    Ok(XDel { key, ids })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xdel_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$4\r\nXDEL\r\n$8\r\nmystream\r\n$3\r\n1-5\r\n$1\r\n2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XDel = frame.try_into()?;
+    assert_eq!(ret.key, "mystream");
+    assert_eq!(ret.ids, vec![StreamId::new(1, 5), StreamId::new(2, 0)]);
+
+    buf.extend_from_slice(b"*3\r\n$4\r\nXDEL\r\n$8\r\nmystream\r\n$3\r\n1-x\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert_eq!(XDel::try_from(frame).unwrap_err(), CommandError::InvalidStreamId);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xdel_execute() -> Result<()> {
+    let backend = Backend::new();
+    let id = StreamId::new(1538561700640, 0);
+    backend.xadd("mystream", StreamIdSpec::Explicit(id), vec![], false, None)?;
+
+    let ids = vec![id, StreamId::new(1538561700640, 1)];
+    let ret = XDel { key: "mystream".to_string(), ids }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+    assert_eq!(backend.xlen("mystream")?, 0);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,193 @@
+use super::{
+  extract_args, parse_integer, parse_stream_id, parse_string, CommandError, CommandExecutor,
+  RESP_OK,
+};
+use crate::{Backend, RespArray, RespFrame, StreamId};
+
+/// XGROUP CREATE key group <id | $> [MKSTREAM] [ENTRIESREAD entries-read]
+/// XGROUP SETID key group <id | $> [ENTRIESREAD entries-read]
+/// XGROUP DESTROY key group
+/// XGROUP CREATECONSUMER key group consumer
+/// XGROUP DELCONSUMER key group consumer
+///  XGROUP CREATE mystream mygroup $ MKSTREAM
+/// OK
+///  XGROUP CREATECONSUMER mystream mygroup alice
+/// (integer) 1
+///  XGROUP DESTROY mystream mygroup
+/// (integer) 1
+#[derive(Debug)]
+pub struct XGroup {
+  pub(crate) key: String,
+  pub(crate) group: String,
+  pub(crate) subcommand: XGroupSubcommand,
+}
+
+/// An ID of `None` stands for `$`, the last ID of the stream.
+#[derive(Debug, PartialEq)]
+pub(crate) enum XGroupSubcommand {
+  Create { id: Option<StreamId>, mkstream: bool, entries_read: Option<u64> },
+  SetId { id: Option<StreamId>, entries_read: Option<u64> },
+  Destroy,
+  CreateConsumer(String),
+  DelConsumer(String),
+}
+
+fn parse_group_id(frame: RespFrame) -> Result<Option<StreamId>, CommandError> {
+  match frame {
+    RespFrame::BulkString(ref id) if id.as_ref() == b"$" => Ok(None),
+    frame => parse_stream_id(frame).map(Some),
+  }
+}
+
+/// Parse `[MKSTREAM] [ENTRIESREAD entries-read]`, returning the two options.
+fn parse_options(
+  mut args: impl Iterator<Item = RespFrame>,
+  allow_mkstream: bool,
+) -> Result<(bool, Option<u64>), CommandError> {
+  let (mut mkstream, mut entries_read) = (false, None);
+  while let Some(opt) = args.next() {
+    match parse_string(opt)?.to_ascii_lowercase().as_str() {
+      "mkstream" if allow_mkstream => mkstream = true,
+      "entriesread" => {
+        entries_read = match parse_integer(args.next().ok_or(CommandError::SyntaxError)?)? {
+          -1 => None,
+          n if n >= 0 => Some(n as u64),
+          _ => {
+            return Err(CommandError::InvalidArgument(
+              "value for ENTRIESREAD must be positive or -1".to_string(),
+            ))
+          }
+        }
+      }
+      _ => return Err(CommandError::SyntaxError),
+    }
+  }
+  Ok((mkstream, entries_read))
+}
+
+impl CommandExecutor for XGroup {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let (key, group) = (&self.key, &self.group);
+    let ret = match self.subcommand {
+      XGroupSubcommand::Create { id, mkstream, entries_read } => {
+        backend.xgroup_create(key, group, id, mkstream, entries_read).map(|_| RESP_OK.clone())
+      }
+      XGroupSubcommand::SetId { id, entries_read } => {
+        backend.xgroup_setid(key, group, id, entries_read).map(|_| RESP_OK.clone())
+      }
+      XGroupSubcommand::Destroy => {
+        backend.xgroup_destroy(key, group).map(|destroyed| RespFrame::Integer(destroyed as i64))
+      }
+      XGroupSubcommand::CreateConsumer(consumer) => backend
+        .xgroup_createconsumer(key, group, &consumer)
+        .map(|created| RespFrame::Integer(created as i64)),
+      XGroupSubcommand::DelConsumer(consumer) => backend
+        .xgroup_delconsumer(key, group, &consumer)
+        .map(|pending| RespFrame::Integer(pending as i64)),
+    };
+    ret.unwrap_or_else(RespFrame::from)
+  }
+}
+
+impl TryFrom<RespArray> for XGroup {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let name = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?.to_ascii_lowercase();
+    let arity = || CommandError::WrongArity(format!("xgroup|{}", name));
+    let (key, group) = match (args.next(), args.next()) {
+      (Some(key), Some(group)) => (parse_string(key)?, parse_string(group)?),
+      _ => return Err(arity()),
+    };
+
+    let subcommand = match name.as_str() {
+      "create" => {
+        let id = parse_group_id(args.next().ok_or_else(arity)?)?;
+        let (mkstream, entries_read) = parse_options(args, true)?;
+        XGroupSubcommand::Create { id, mkstream, entries_read }
+      }
+      "setid" => {
+        let id = parse_group_id(args.next().ok_or_else(arity)?)?;
+        let (_, entries_read) = parse_options(args, false)?;
+        XGroupSubcommand::SetId { id, entries_read }
+      }
+      "destroy" if args.len() == 0 => XGroupSubcommand::Destroy,
+      "createconsumer" | "delconsumer" if args.len() == 1 => {
+        let consumer = parse_string(args.next().ok_or_else(arity)?)?;
+        match name.as_str() {
+          "createconsumer" => XGroupSubcommand::CreateConsumer(consumer),
+          _ => XGroupSubcommand::DelConsumer(consumer),
+        }
+      }
+      "destroy" | "createconsumer" | "delconsumer" => return Err(arity()),
+      _ => {
+        return Err(CommandError::InvalidArgument(format!(
+          "unknown subcommand '{}'. Try XGROUP HELP.",
+          name
+        )))
+      }
+    };
+    Ok(XGroup { key, group, subcommand })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, SimpleError};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xgroup_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*8\r\n$6\r\nXGROUP\r\n$6\r\nCREATE\r\n$8\r\nmystream\r\n$7\r\nmygroup\r\n$1\r\n$\r\n$8\r\nMKSTREAM\r\n$11\r\nENTRIESREAD\r\n$1\r\n3\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XGroup = frame.try_into()?;
+    assert_eq!(ret.key, "mystream");
+    assert_eq!(ret.group, "mygroup");
+    let create = XGroupSubcommand::Create { id: None, mkstream: true, entries_read: Some(3) };
+    assert_eq!(ret.subcommand, create);
+
+    buf.extend_from_slice(
+      b"*5\r\n$6\r\nxgroup\r\n$5\r\nsetid\r\n$8\r\nmystream\r\n$7\r\nmygroup\r\n$3\r\n5-1\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XGroup = frame.try_into()?;
+    let setid = XGroupSubcommand::SetId { id: Some(StreamId::new(5, 1)), entries_read: None };
+    assert_eq!(ret.subcommand, setid);
+
+    buf.extend_from_slice(
+      b"*4\r\n$6\r\nxgroup\r\n$14\r\ncreateconsumer\r\n$8\r\nmystream\r\n$7\r\nmygroup\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    let err = CommandError::WrongArity("xgroup|createconsumer".to_string());
+    assert_eq!(XGroup::try_from(frame).unwrap_err(), err);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xgroup_execute() {
+    let backend = Backend::new();
+    let xgroup = |subcommand| {
+      XGroup { key: "mystream".to_string(), group: "mygroup".to_string(), subcommand }
+        .execute(&backend)
+    };
+    let create = |mkstream| XGroupSubcommand::Create { id: None, mkstream, entries_read: None };
+
+    assert_eq!(xgroup(create(false)), CommandError::StreamKeyMissing.into());
+    assert_eq!(xgroup(create(true)), RESP_OK.clone());
+    let ret = xgroup(create(true));
+    assert_eq!(ret, SimpleError::new("BUSYGROUP Consumer Group name already exists").into());
+    let ret = xgroup(XGroupSubcommand::CreateConsumer("alice".to_string()));
+    assert_eq!(ret, RespFrame::Integer(1));
+    let ret = xgroup(XGroupSubcommand::DelConsumer("alice".to_string()));
+    assert_eq!(ret, RespFrame::Integer(0));
+    assert_eq!(xgroup(XGroupSubcommand::Destroy), RespFrame::Integer(1));
+    assert_eq!(xgroup(XGroupSubcommand::Destroy), RespFrame::Integer(0));
+  }
+}
@@ -0,0 +1,194 @@
+use super::{
+  extract_args, parse_string, xrange::encode_entry, CommandError, CommandExecutor, RESP_NULL,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame, RespMap, StreamId};
+
+/// XINFO STREAM key
+/// XINFO GROUPS key
+/// XINFO CONSUMERS key group
+///  XINFO GROUPS mystream
+/// 1)  1) "consumers"
+///     2) (integer) 2
+///     3) "entries-read"
+///     4) (integer) 2
+///     5) "lag"
+///     6) (integer) 0
+///     7) "last-delivered-id"
+///     8) "1638126030001-0"
+///     9) "name"
+///    10) "mygroup"
+///    11) "pending"
+///    12) (integer) 2
+#[derive(Debug)]
+pub struct XInfo {
+  pub(crate) key: String,
+  pub(crate) kind: XInfoKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum XInfoKind {
+  Stream,
+  Groups,
+  Consumers(String),
+}
+
+fn id_frame(id: StreamId) -> RespFrame {
+  BulkString::from(id.to_string()).into()
+}
+
+fn integer_or_nil(n: Option<u64>) -> RespFrame {
+  n.map_or(RESP_NULL.clone(), |n| RespFrame::Integer(n as i64))
+}
+
+fn map(fields: impl IntoIterator<Item = (&'static str, RespFrame)>) -> RespFrame {
+  let mut map = RespMap::new();
+  for (name, value) in fields {
+    map.insert(name.to_string(), value);
+  }
+  map.into()
+}
+
+impl XInfo {
+  fn stream(&self, backend: &Backend) -> Result<RespFrame, CommandError> {
+    let info = backend.xinfo_stream(&self.key)?;
+    let entry = |entry: Option<(StreamId, Vec<RespFrame>)>| {
+      entry.map_or(RESP_NULL.clone(), |(id, fields)| encode_entry(id, Some(fields)))
+    };
+    Ok(map([
+      ("length", RespFrame::Integer(info.length as i64)),
+      ("last-generated-id", id_frame(info.last_generated_id)),
+      ("max-deleted-entry-id", id_frame(info.max_deleted_id)),
+      ("entries-added", RespFrame::Integer(info.entries_added as i64)),
+      ("recorded-first-entry-id", id_frame(info.recorded_first_id)),
+      ("groups", RespFrame::Integer(info.groups as i64)),
+      ("first-entry", entry(info.first_entry)),
+      ("last-entry", entry(info.last_entry)),
+    ]))
+  }
+
+  fn groups(&self, backend: &Backend) -> Result<RespFrame, CommandError> {
+    let groups = backend.xinfo_groups(&self.key)?.into_iter().map(|group| {
+      map([
+        ("name", BulkString::from(group.name).into()),
+        ("consumers", RespFrame::Integer(group.consumers as i64)),
+        ("pending", RespFrame::Integer(group.pending as i64)),
+        ("last-delivered-id", id_frame(group.last_delivered)),
+        ("entries-read", integer_or_nil(group.entries_read)),
+        ("lag", integer_or_nil(group.lag)),
+      ])
+    });
+    Ok(RespArray::new(groups.collect::<Vec<_>>()).into())
+  }
+
+  fn consumers(&self, backend: &Backend, group: &str) -> Result<RespFrame, CommandError> {
+    let consumers = backend.xinfo_consumers(&self.key, group)?.into_iter().map(|consumer| {
+      map([
+        ("name", BulkString::from(consumer.name).into()),
+        ("pending", RespFrame::Integer(consumer.pending as i64)),
+        ("idle", RespFrame::Integer(consumer.idle)),
+        ("inactive", RespFrame::Integer(consumer.inactive)),
+      ])
+    });
+    Ok(RespArray::new(consumers.collect::<Vec<_>>()).into())
+  }
+}
+
+impl CommandExecutor for XInfo {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ret = match &self.kind {
+      XInfoKind::Stream => self.stream(backend),
+      XInfoKind::Groups => self.groups(backend),
+      XInfoKind::Consumers(group) => self.consumers(backend, group),
+    };
+    ret.unwrap_or_else(RespFrame::from)
+  }
+}
+
+impl TryFrom<RespArray> for XInfo {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let name = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?.to_ascii_lowercase();
+    let arity = || CommandError::WrongArity(format!("xinfo|{}", name));
+    let key = parse_string(args.next().ok_or_else(arity)?)?;
+    let kind = match (name.as_str(), args.next(), args.next()) {
+      ("stream", None, _) => XInfoKind::Stream,
+      ("groups", None, _) => XInfoKind::Groups,
+      ("consumers", Some(group), None) => XInfoKind::Consumers(parse_string(group)?),
+      ("stream" | "groups" | "consumers", _, _) => return Err(arity()),
+      _ => {
+        return Err(CommandError::InvalidArgument(format!(
+          "unknown subcommand '{}'. Try XINFO HELP.",
+          name
+        )))
+      }
+    };
+    Ok(XInfo { key, kind })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{GroupRead, RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xinfo_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*4\r\n$5\r\nXINFO\r\n$9\r\nCONSUMERS\r\n$8\r\nmystream\r\n$7\r\nmygroup\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XInfo = frame.try_into()?;
+    assert_eq!(ret.key, "mystream");
+    assert_eq!(ret.kind, XInfoKind::Consumers("mygroup".to_string()));
+
+    buf.extend_from_slice(b"*4\r\n$5\r\nxinfo\r\n$6\r\nstream\r\n$8\r\nmystream\r\n$4\r\nFULL\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    let err = CommandError::WrongArity("xinfo|stream".to_string());
+    assert_eq!(XInfo::try_from(frame).unwrap_err(), err);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xinfo_execute() -> Result<()> {
+    let backend = Backend::new();
+    let xinfo = |kind| XInfo { key: "mystream".to_string(), kind }.execute(&backend);
+    assert_eq!(xinfo(XInfoKind::Stream), CommandError::NoSuchKey.into());
+
+    for ms in [1638125133432, 1638126030001] {
+      let id = StreamIdSpec::Explicit(StreamId::new(ms, 0));
+      backend.xadd("mystream", id, vec![], false, None)?;
+    }
+    backend.xgroup_create("mystream", "mygroup", Some(StreamId::MIN), false, None)?;
+    let streams = [("mystream".to_string(), GroupRead::New)];
+    backend.xreadgroup("mygroup", "Alice", &streams, Some(1), false)?;
+    backend.xreadgroup("mygroup", "Bob", &streams, Some(1), false)?;
+
+    let group = map([
+      ("name", BulkString::from("mygroup").into()),
+      ("consumers", RespFrame::Integer(2)),
+      ("pending", RespFrame::Integer(2)),
+      ("last-delivered-id", BulkString::from("1638126030001-0").into()),
+      ("entries-read", RespFrame::Integer(2)),
+      ("lag", RespFrame::Integer(0)),
+    ]);
+    assert_eq!(xinfo(XInfoKind::Groups), RespArray::new([group]).into());
+
+    let RespFrame::Map(info) = xinfo(XInfoKind::Stream) else {
+      panic!("expected a map");
+    };
+    assert_eq!(info.0.get("length"), Some(&RespFrame::Integer(2)));
+    assert_eq!(info.0.get("groups"), Some(&RespFrame::Integer(1)));
+    let RespFrame::Array(consumers) = xinfo(XInfoKind::Consumers("mygroup".to_string())) else {
+      panic!("expected an array");
+    };
+    assert_eq!(consumers.as_ref().map(Vec::len), Some(2));
+
+    Ok(())
+  }
+}
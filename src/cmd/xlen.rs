@@ -0,0 +1,63 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// XLEN key
+///  XADD mystream * item 1
+/// "1609459200000-0"
+///  XLEN mystream
+/// (integer) 1
+#[derive(Debug)]
+pub struct XLen {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for XLen {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.xlen(&self.key) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for XLen {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(XLen { key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xlen_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$4\r\nXLEN\r\n$8\r\nmystream\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XLen = frame.try_into()?;
+    assert_eq!(ret.key, "mystream");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xlen_execute() -> Result<()> {
+    let backend = Backend::new();
+    backend.xadd("mystream", StreamIdSpec::Auto, vec![], false, None)?;
+
+    assert_eq!(XLen { key: "mystream".to_string() }.execute(&backend), RespFrame::Integer(1));
+    backend.set("string".to_string(), RespFrame::Integer(1));
+    let ret = XLen { key: "string".to_string() }.execute(&backend);
+    assert_eq!(ret, CommandError::WrongType.into());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,209 @@
+use super::{
+  extract_args, parse_integer, parse_string, xrange::parse_bound, CommandError, CommandExecutor,
+  RESP_NULL,
+};
+use crate::{now_ms, Backend, BulkString, RespArray, RespFrame, StreamId};
+
+/// XPENDING key group [[IDLE min-idle-time] start end count [consumer]]
+///  XPENDING mystream group55
+/// 1) (integer) 1
+/// 2) "1526984818136-0"
+/// 3) "1526984818136-0"
+/// 4) 1) 1) "consumer-123"
+///       2) "1"
+#[derive(Debug)]
+pub struct XPending {
+  pub(crate) key: String,
+  pub(crate) group: String,
+  // the extended form, the summary is given without it
+  pub(crate) range: Option<PendingRange>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct PendingRange {
+  pub(crate) min_idle: i64,
+  pub(crate) start: StreamId,
+  pub(crate) end: StreamId,
+  pub(crate) count: usize,
+  pub(crate) consumer: Option<String>,
+}
+
+impl XPending {
+  fn summary(&self, backend: &Backend) -> RespFrame {
+    let summary = match backend.xpending_summary(&self.key, &self.group) {
+      Ok(summary) => summary,
+      Err(e) => return e.into(),
+    };
+    let Some((first, last)) = summary.range else {
+      let nil = RESP_NULL.clone();
+      return RespArray::new([RespFrame::Integer(0), nil.clone(), nil.clone(), nil]).into();
+    };
+    let consumers = summary
+      .consumers
+      .into_iter()
+      .map(|(name, count)| {
+        RespArray::new([BulkString::from(name).into(), BulkString::from(count.to_string()).into()])
+          .into()
+      })
+      .collect::<Vec<RespFrame>>();
+    RespArray::new([
+      RespFrame::Integer(summary.count as i64),
+      BulkString::from(first.to_string()).into(),
+      BulkString::from(last.to_string()).into(),
+      RespArray::new(consumers).into(),
+    ])
+    .into()
+  }
+}
+
+impl CommandExecutor for XPending {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let Some(range) = &self.range else {
+      return self.summary(backend);
+    };
+    let consumer = range.consumer.as_deref();
+    let ret = backend.xpending(
+      &self.key,
+      &self.group,
+      range.min_idle,
+      (range.start, range.end),
+      range.count,
+      consumer,
+    );
+    let now = now_ms();
+    match ret {
+      Ok(entries) => {
+        let entries = entries
+          .into_iter()
+          .map(|(id, pending)| {
+            RespArray::new([
+              BulkString::from(id.to_string()).into(),
+              BulkString::from(pending.consumer).into(),
+              RespFrame::Integer((now - pending.delivered_at).max(0)),
+              RespFrame::Integer(pending.delivery_count as i64),
+            ])
+            .into()
+          })
+          .collect::<Vec<RespFrame>>();
+        RespArray::new(entries).into()
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for XPending {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter().peekable();
+    let (key, group) = match (args.next(), args.next()) {
+      (Some(key), Some(group)) => (parse_string(key)?, parse_string(group)?),
+      _ => return Err(CommandError::WrongArity("xpending".to_string())),
+    };
+    if args.peek().is_none() {
+      return Ok(XPending { key, group, range: None });
+    }
+
+    let mut min_idle = 0;
+    if let Some(RespFrame::BulkString(opt)) = args.peek() {
+      if opt.as_ref().eq_ignore_ascii_case(b"idle") {
+        args.next();
+        min_idle = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+      }
+    }
+    let (start, end, count) = match (args.next(), args.next(), args.next()) {
+      (Some(start), Some(end), Some(count)) => {
+        (parse_bound(start, true)?, parse_bound(end, false)?, parse_integer(count)?.max(0) as usize)
+      }
+      _ => return Err(CommandError::SyntaxError),
+    };
+    let consumer = args.next().map(parse_string).transpose()?;
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    let range = PendingRange { min_idle, start, end, count, consumer };
+    Ok(XPending { key, group, range: Some(range) })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{GroupRead, RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xpending_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*9\r\n$8\r\nXPENDING\r\n$8\r\nmystream\r\n$7\r\ngroup55\r\n$4\r\nIDLE\r\n$4\r\n9000\r\n$1\r\n-\r\n$1\r\n+\r\n$2\r\n10\r\n$12\r\nconsumer-123\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XPending = frame.try_into()?;
+    assert_eq!(ret.key, "mystream");
+    assert_eq!(ret.group, "group55");
+    let range = PendingRange {
+      min_idle: 9000,
+      start: StreamId::MIN,
+      end: StreamId::MAX,
+      count: 10,
+      consumer: Some("consumer-123".to_string()),
+    };
+    assert_eq!(ret.range, Some(range));
+
+    buf.extend_from_slice(b"*3\r\n$8\r\nxpending\r\n$8\r\nmystream\r\n$7\r\ngroup55\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XPending = frame.try_into()?;
+    assert_eq!(ret.range, None);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xpending_execute() -> Result<()> {
+    let backend = Backend::new();
+    let xpending =
+      |range| XPending { key: "mystream".to_string(), group: "group55".to_string(), range };
+    let err = CommandError::NoGroup("mystream".to_string(), "group55".to_string());
+    assert_eq!(xpending(None).execute(&backend), err.into());
+
+    let id = StreamId::new(1526984818136, 0);
+    backend.xadd("mystream", StreamIdSpec::Explicit(id), vec![], false, None)?;
+    backend.xgroup_create("mystream", "group55", Some(StreamId::MIN), false, None)?;
+    let nil = RESP_NULL.clone();
+    let empty = RespArray::new([RespFrame::Integer(0), nil.clone(), nil.clone(), nil]);
+    assert_eq!(xpending(None).execute(&backend), empty.into());
+
+    let streams = [("mystream".to_string(), GroupRead::New)];
+    backend.xreadgroup("group55", "consumer-123", &streams, None, false)?;
+    let summary = RespArray::new([
+      RespFrame::Integer(1),
+      BulkString::from("1526984818136-0").into(),
+      BulkString::from("1526984818136-0").into(),
+      RespArray::new([RespArray::new([
+        BulkString::from("consumer-123").into(),
+        BulkString::from("1").into(),
+      ])
+      .into()])
+      .into(),
+    ]);
+    assert_eq!(xpending(None).execute(&backend), summary.into());
+
+    let range = |min_idle| PendingRange {
+      min_idle,
+      start: StreamId::MIN,
+      end: StreamId::MAX,
+      count: 10,
+      consumer: None,
+    };
+    let RespFrame::Array(entries) = xpending(Some(range(0))).execute(&backend) else {
+      panic!("expected an array");
+    };
+    assert_eq!(entries.as_ref().map(Vec::len), Some(1));
+    assert_eq!(xpending(Some(range(60_000))).execute(&backend), RespArray::new([]).into());
+
+    Ok(())
+  }
+}
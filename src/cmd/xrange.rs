@@ -0,0 +1,149 @@
+use super::{
+  extract_args, extract_command_name, parse_integer, parse_string, CommandError, CommandExecutor,
+  RESP_NULL,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame, StreamId};
+
+/// XRANGE key start end [COUNT count]
+/// XREVRANGE key end start [COUNT count]
+///  XADD writers 1-0 name Virginia surname Woolf
+/// "1-0"
+///  XADD writers 2-0 name Jane surname Austen
+/// "2-0"
+///  XRANGE writers (1-0 + COUNT 1
+/// 1) 1) "2-0"
+///    2) 1) "name"
+///       2) "Jane"
+///       3) "surname"
+///       4) "Austen"
+#[derive(Debug)]
+pub struct XRange {
+  pub(crate) key: String,
+  pub(crate) start: StreamId,
+  pub(crate) end: StreamId,
+  pub(crate) rev: bool,
+  pub(crate) count: Option<usize>,
+}
+
+/// Parse an interval bound: `-`, `+`, an ID, or an ID prefixed by `(` to exclude it. A bare
+/// `<ms>` covers the whole millisecond.
+pub(crate) fn parse_bound(frame: RespFrame, is_start: bool) -> Result<StreamId, CommandError> {
+  let bound = parse_string(frame)?;
+  let missing_seq = if is_start { 0 } else { u64::MAX };
+  match bound.as_str() {
+    "-" => Ok(StreamId::MIN),
+    "+" => Ok(StreamId::MAX),
+    _ => match bound.strip_prefix('(') {
+      Some(id) if is_start => StreamId::parse(id, missing_seq)?
+        .next()
+        .ok_or(CommandError::InvalidArgument("invalid start ID for the interval".to_string())),
+      Some(id) => StreamId::parse(id, missing_seq)?
+        .prev()
+        .ok_or(CommandError::InvalidArgument("invalid end ID for the interval".to_string())),
+      None => StreamId::parse(&bound, missing_seq),
+    },
+  }
+}
+
+/// Encode an entry as `[id, [field, value, ...]]`, the fields of a deleted one being nil.
+pub(crate) fn encode_entry(id: StreamId, fields: Option<Vec<RespFrame>>) -> RespFrame {
+  let fields = fields.map_or(RESP_NULL.clone(), |fields| RespArray::new(fields).into());
+  RespArray::new([BulkString::from(id.to_string()).into(), fields]).into()
+}
+
+pub(crate) fn encode_entries(entries: Vec<(StreamId, Vec<RespFrame>)>) -> RespFrame {
+  let entries = entries.into_iter().map(|(id, fields)| encode_entry(id, Some(fields)));
+  RespArray::new(entries.collect::<Vec<_>>()).into()
+}
+
+impl CommandExecutor for XRange {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.xrange(&self.key, self.start, self.end, self.rev, self.count) {
+      Ok(entries) => encode_entries(entries),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for XRange {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let rev = extract_command_name(&value)? == "xrevrange";
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, first, second) = match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(first), Some(second)) => (parse_string(key)?, first, second),
+      _ => return Err(CommandError::SyntaxError),
+    };
+    let (start, end) = match rev {
+      true => (parse_bound(second, true)?, parse_bound(first, false)?),
+      false => (parse_bound(first, true)?, parse_bound(second, false)?),
+    };
+
+    let count = match (args.next(), args.next(), args.next()) {
+      (None, _, _) => None,
+      (Some(opt), Some(count), None) => {
+        if !parse_string(opt)?.eq_ignore_ascii_case("count") {
+          return Err(CommandError::SyntaxError);
+        }
+        Some(parse_integer(count)?.max(0) as usize)
+      }
+      _ => return Err(CommandError::SyntaxError),
+    };
+    Ok(XRange { key, start, end, rev, count })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xrange_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*6\r\n$9\r\nXREVRANGE\r\n$7\r\nwriters\r\n$1\r\n5\r\n$4\r\n(1-0\r\n$5\r\nCOUNT\r\n$1\r\n1\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XRange = frame.try_into()?;
+    assert_eq!(ret.key, "writers");
+    assert_eq!(ret.start, StreamId::new(1, 1));
+    assert_eq!(ret.end, StreamId::new(5, u64::MAX));
+    assert!(ret.rev);
+    assert_eq!(ret.count, Some(1));
+
+    buf.extend_from_slice(b"*4\r\n$6\r\nxrange\r\n$7\r\nwriters\r\n$1\r\n-\r\n$4\r\n(0-0\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(XRange::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xrange_execute() -> Result<()> {
+    let backend = Backend::new();
+    for (ms, name) in [(1, "Virginia"), (2, "Jane")] {
+      let fields = vec![BulkString::from("name").into(), BulkString::from(name).into()];
+      backend.xadd("writers", StreamIdSpec::Explicit(StreamId::new(ms, 0)), fields, false, None)?;
+    }
+
+    let xrange = XRange {
+      key: "writers".to_string(),
+      start: StreamId::MIN,
+      end: StreamId::MAX,
+      rev: true,
+      count: Some(1),
+    };
+    let ret = xrange.execute(&backend);
+    let entry = RespArray::new([
+      BulkString::from("2-0").into(),
+      RespArray::new([BulkString::from("name").into(), BulkString::from("Jane").into()]).into(),
+    ]);
+    assert_eq!(ret, RespArray::new([entry.into()]).into());
+
+    Ok(())
+  }
+}
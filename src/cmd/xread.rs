@@ -0,0 +1,257 @@
+use super::{
+  extract_args, parse_integer, parse_string, xrange::encode_entry, CommandError, CommandExecutor,
+  RESP_NULL,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame, StreamId};
+use std::{collections::HashMap, time::Duration};
+
+/// XREAD [COUNT count] [BLOCK milliseconds] STREAMS key [key ...] id [id ...]
+///  XADD mystream 1-0 message apple
+/// "1-0"
+///  XREAD STREAMS mystream 0
+/// 1) 1) "mystream"
+///    2) 1) 1) "1-0"
+///          2) 1) "message"
+///             2) "apple"
+#[derive(Debug)]
+pub struct XRead {
+  pub(crate) count: Option<usize>,
+  // only set with BLOCK, `timeout` is meaningless otherwise
+  pub(crate) blocking: bool,
+  pub(crate) timeout: Option<Duration>,
+  // `None` is `$`: only entries added after the command is received
+  pub(crate) streams: Vec<(String, Option<StreamId>)>,
+}
+
+type Entries = Vec<(StreamId, Vec<RespFrame>)>;
+
+/// Encode the entries read per stream, a null when no stream had any.
+pub(crate) fn encode_streams<T>(streams: Vec<(String, Vec<(StreamId, T)>)>) -> RespFrame
+where
+  T: Into<Option<Vec<RespFrame>>>,
+{
+  if streams.is_empty() {
+    return RESP_NULL.clone();
+  }
+  let streams = streams
+    .into_iter()
+    .map(|(key, entries)| {
+      let entries = entries.into_iter().map(|(id, fields)| encode_entry(id, fields.into()));
+      let entries = RespArray::new(entries.collect::<Vec<_>>());
+      RespArray::new([BulkString::from(key).into(), entries.into()]).into()
+    })
+    .collect::<Vec<RespFrame>>();
+  RespArray::new(streams).into()
+}
+
+/// Read the entries after `id` from every stream, leaving out those with none.
+fn read(
+  backend: &Backend,
+  streams: &[(String, StreamId)],
+  count: Option<usize>,
+) -> Result<Vec<(String, Entries)>, CommandError> {
+  let mut ret = vec![];
+  for (key, id) in streams {
+    let entries = backend.xread(key, *id, count)?;
+    if !entries.is_empty() {
+      ret.push((key.clone(), entries));
+    }
+  }
+  Ok(ret)
+}
+
+/// Parse the milliseconds of `BLOCK`, the wait has no timeout for 0.
+pub(crate) fn parse_block(frame: RespFrame) -> Result<Option<Duration>, CommandError> {
+  match parse_integer(frame)? {
+    ms if ms < 0 => Err(CommandError::InvalidArgument("timeout is negative".to_string())),
+    0 => Ok(None),
+    ms => Ok(Some(Duration::from_millis(ms as u64))),
+  }
+}
+
+impl XRead {
+  /// Read the streams, with BLOCK waiting for an entry to be added to any of them if none has
+  /// one past its ID.
+  pub async fn run(self, backend: &Backend) -> RespFrame {
+    if !self.blocking {
+      return backend.locked(true, || self.execute(backend));
+    }
+    // `$` is resolved under the same lock as the first read, so nothing is added in between
+    let ret = backend.locked(true, || {
+      let mut streams = Vec::with_capacity(self.streams.len());
+      for (key, id) in self.streams {
+        let id = match id {
+          Some(id) => id,
+          None => backend.xlast_id(&key).map_err(RespFrame::from)?,
+        };
+        streams.push((key, id));
+      }
+      match read(backend, &streams, self.count) {
+        Ok(ret) if ret.is_empty() => Ok(streams),
+        ret => Err(ret.map_or_else(Into::into, encode_streams)),
+      }
+    });
+    let streams = match ret {
+      Ok(streams) => streams,
+      Err(frame) => return frame,
+    };
+
+    let count = self.count;
+    let keys = streams.iter().map(|(key, _)| key.clone()).collect();
+    let ids = streams.into_iter().collect::<HashMap<_, _>>();
+    let serve = move |backend: &Backend, key: &str| match backend.xread(key, ids[key], count) {
+      Ok(entries) if entries.is_empty() => None,
+      Ok(entries) => Some(encode_streams(vec![(key.to_string(), entries)])),
+      Err(e) => Some(e.into()),
+    };
+    backend.block_on(keys, self.timeout, true, serve).await.unwrap_or(RESP_NULL.clone())
+  }
+}
+
+impl CommandExecutor for XRead {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    // without BLOCK there is nothing to wait for, so `$` reads nothing
+    let streams =
+      self.streams.into_iter().filter_map(|(key, id)| id.map(|id| (key, id))).collect::<Vec<_>>();
+    read(backend, &streams, self.count).map_or_else(Into::into, encode_streams)
+  }
+}
+
+impl TryFrom<RespArray> for XRead {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (mut count, mut blocking, mut timeout) = (None, false, None);
+    loop {
+      let opt = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+      match opt.to_ascii_lowercase().as_str() {
+        "count" => {
+          let n = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+          // 0 and below mean no limit
+          count = (n > 0).then_some(n as usize);
+        }
+        "block" => {
+          blocking = true;
+          timeout = parse_block(args.next().ok_or(CommandError::SyntaxError)?)?;
+        }
+        "streams" => break,
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+
+    let rest = args.collect::<Vec<_>>();
+    if rest.is_empty() || !rest.len().is_multiple_of(2) {
+      return Err(CommandError::InvalidArgument(
+        "Unbalanced 'xread' list of streams: for each stream key an ID or '$' must be specified."
+          .to_string(),
+      ));
+    }
+    let mut keys = rest;
+    let ids = keys.split_off(keys.len() / 2);
+    let keys = keys.into_iter().map(parse_string).collect::<Result<Vec<_>, _>>()?;
+    let ids = ids
+      .into_iter()
+      .map(|id| match parse_string(id)?.as_str() {
+        "$" => Ok(None),
+        id => StreamId::parse(id, 0).map(Some),
+      })
+      .collect::<Result<Vec<_>, CommandError>>()?;
+    let streams = keys.into_iter().zip(ids).collect();
+    Ok(XRead { count, blocking, timeout, streams })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xread_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*8\r\n$5\r\nXREAD\r\n$5\r\nCOUNT\r\n$1\r\n2\r\n$7\r\nSTREAMS\r\n$1\r\na\r\n$1\r\nb\r\n$3\r\n1-1\r\n$1\r\n$\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XRead = frame.try_into()?;
+    assert_eq!(ret.count, Some(2));
+    assert!(!ret.blocking);
+    let streams = vec![("a".to_string(), Some(StreamId::new(1, 1))), ("b".to_string(), None)];
+    assert_eq!(ret.streams, streams);
+
+    buf.extend_from_slice(
+      b"*6\r\n$5\r\nxread\r\n$5\r\nblock\r\n$1\r\n0\r\n$7\r\nstreams\r\n$1\r\na\r\n$1\r\n$\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XRead = frame.try_into()?;
+    assert!(ret.blocking);
+    assert_eq!(ret.timeout, None);
+
+    buf.extend_from_slice(
+      b"*5\r\n$5\r\nxread\r\n$7\r\nSTREAMS\r\n$1\r\na\r\n$1\r\nb\r\n$1\r\n0\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(XRead::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xread_execute() -> Result<()> {
+    let backend = Backend::new();
+    let fields = vec![BulkString::from("message").into(), BulkString::from("apple").into()];
+    let id = StreamIdSpec::Explicit(StreamId::new(1, 0));
+    backend.xadd("mystream", id, fields.clone(), false, None)?;
+    let xread = |id| XRead {
+      count: None,
+      blocking: false,
+      timeout: None,
+      streams: vec![("mystream".to_string(), id), ("other".to_string(), Some(StreamId::MIN))],
+    };
+
+    let entry = RespArray::new([BulkString::from("1-0").into(), RespArray::new(fields).into()]);
+    let expected = RespArray::new([RespArray::new([
+      BulkString::from("mystream").into(),
+      RespArray::new([entry.into()]).into(),
+    ])
+    .into()]);
+    assert_eq!(xread(Some(StreamId::MIN)).execute(&backend), expected.into());
+    assert_eq!(xread(Some(StreamId::new(1, 0))).execute(&backend), RESP_NULL.clone());
+    assert_eq!(xread(None).execute(&backend), RESP_NULL.clone());
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_xread_run_blocking() -> Result<()> {
+    let backend = Backend::new();
+    let id = StreamIdSpec::Explicit(StreamId::new(1, 0));
+    backend.xadd("mystream", id, vec![], false, None)?;
+    let xread = |timeout| XRead {
+      count: None,
+      blocking: true,
+      timeout,
+      streams: vec![("mystream".to_string(), None)],
+    };
+    assert_eq!(xread(Some(Duration::from_millis(10))).run(&backend).await, RESP_NULL.clone());
+
+    let handle = tokio::spawn({
+      let backend = backend.clone();
+      async move { xread(None).run(&backend).await }
+    });
+    let mut ids = vec![];
+    while !handle.is_finished() {
+      ids.extend(backend.xadd("mystream", StreamIdSpec::Auto, vec![], false, None)?);
+      tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    // `$` skips 1-0, the reader is woken by the first entry added after it parked
+    let ret = handle.await?;
+    let read = |id| encode_streams(vec![("mystream".to_string(), vec![(id, Vec::new())])]);
+    assert!(ids.into_iter().any(|id| ret == read(id)));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,224 @@
+use super::{
+  extract_args, parse_integer, parse_string,
+  xread::{encode_streams, parse_block},
+  CommandError, CommandExecutor, RESP_NULL,
+};
+use crate::{Backend, GroupRead, RespArray, RespFrame, StreamId};
+use std::time::Duration;
+
+/// XREADGROUP GROUP group consumer [COUNT count] [BLOCK milliseconds] [NOACK] STREAMS key
+/// [key ...] id [id ...]
+///  XGROUP CREATE mystream mygroup 0
+/// OK
+///  XADD mystream 1-0 message apple
+/// "1-0"
+///  XREADGROUP GROUP mygroup alice STREAMS mystream >
+/// 1) 1) "mystream"
+///    2) 1) 1) "1-0"
+///          2) 1) "message"
+///             2) "apple"
+#[derive(Debug)]
+pub struct XReadGroup {
+  pub(crate) group: String,
+  pub(crate) consumer: String,
+  pub(crate) count: Option<usize>,
+  pub(crate) noack: bool,
+  // only set with BLOCK, `timeout` is meaningless otherwise
+  pub(crate) blocking: bool,
+  pub(crate) timeout: Option<Duration>,
+  pub(crate) streams: Vec<(String, GroupRead)>,
+}
+
+fn parse_group_read(frame: RespFrame) -> Result<GroupRead, CommandError> {
+  match parse_string(frame)?.as_str() {
+    ">" => Ok(GroupRead::New),
+    "$" => Err(CommandError::InvalidArgument(
+      "The $ ID is meaningless in the context of XREADGROUP".to_string(),
+    )),
+    id => StreamId::parse(id, 0).map(GroupRead::Pending),
+  }
+}
+
+impl XReadGroup {
+  /// Read for the consumer, with BLOCK waiting for a new entry when none is left to deliver.
+  /// Reading history never waits, there is nothing to wait for.
+  pub async fn run(self, backend: &Backend) -> RespFrame {
+    let history = self.streams.iter().any(|(_, read)| *read != GroupRead::New);
+    if !self.blocking || history {
+      return backend.locked(true, || self.execute(backend));
+    }
+    let ret = backend.locked(true, || {
+      backend.xreadgroup(&self.group, &self.consumer, &self.streams, self.count, self.noack)
+    });
+    match ret {
+      Ok(streams) if streams.is_empty() => {}
+      ret => return ret.map_or_else(Into::into, encode_streams),
+    }
+
+    let (group, consumer, count, noack) = (self.group, self.consumer, self.count, self.noack);
+    let keys = self.streams.into_iter().map(|(key, _)| key).collect();
+    let serve = move |backend: &Backend, key: &str| {
+      let streams = [(key.to_string(), GroupRead::New)];
+      match backend.xreadgroup(&group, &consumer, &streams, count, noack) {
+        Ok(streams) if streams.is_empty() => None,
+        ret => Some(ret.map_or_else(Into::into, encode_streams)),
+      }
+    };
+    backend.block_on(keys, self.timeout, true, serve).await.unwrap_or(RESP_NULL.clone())
+  }
+}
+
+impl CommandExecutor for XReadGroup {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ret =
+      backend.xreadgroup(&self.group, &self.consumer, &self.streams, self.count, self.noack);
+    ret.map_or_else(Into::into, encode_streams)
+  }
+}
+
+impl TryFrom<RespArray> for XReadGroup {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (mut group, mut count, mut noack) = (None, None, false);
+    let (mut blocking, mut timeout) = (false, None);
+    loop {
+      let opt = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+      match opt.to_ascii_lowercase().as_str() {
+        "group" => match (args.next(), args.next()) {
+          (Some(name), Some(consumer)) => {
+            group = Some((parse_string(name)?, parse_string(consumer)?))
+          }
+          _ => return Err(CommandError::SyntaxError),
+        },
+        "count" => {
+          let n = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+          // 0 and below mean no limit
+          count = (n > 0).then_some(n as usize);
+        }
+        "block" => {
+          blocking = true;
+          timeout = parse_block(args.next().ok_or(CommandError::SyntaxError)?)?;
+        }
+        "noack" => noack = true,
+        "streams" => break,
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+    let Some((group, consumer)) = group else {
+      return Err(CommandError::InvalidArgument("Missing GROUP option for XREADGROUP".to_string()));
+    };
+
+    let rest = args.collect::<Vec<_>>();
+    if rest.is_empty() || !rest.len().is_multiple_of(2) {
+      return Err(CommandError::InvalidArgument(
+        "Unbalanced 'xreadgroup' list of streams: for each stream key an ID or '>' must be \
+         specified."
+          .to_string(),
+      ));
+    }
+    let mut keys = rest;
+    let reads = keys.split_off(keys.len() / 2);
+    let keys = keys.into_iter().map(parse_string).collect::<Result<Vec<_>, _>>()?;
+    let reads = reads.into_iter().map(parse_group_read).collect::<Result<Vec<_>, _>>()?;
+    let streams = keys.into_iter().zip(reads).collect();
+    Ok(XReadGroup { group, consumer, count, noack, blocking, timeout, streams })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xreadgroup_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*11\r\n$10\r\nXREADGROUP\r\n$5\r\nGROUP\r\n$7\r\nmygroup\r\n$5\r\nalice\r\n$5\r\nCOUNT\r\n$1\r\n1\r\n$7\r\nSTREAMS\r\n$1\r\na\r\n$1\r\nb\r\n$1\r\n>\r\n$1\r\n0\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XReadGroup = frame.try_into()?;
+    assert_eq!((ret.group.as_str(), ret.consumer.as_str()), ("mygroup", "alice"));
+    assert_eq!(ret.count, Some(1));
+    assert!(!ret.noack);
+    let streams =
+      vec![("a".to_string(), GroupRead::New), ("b".to_string(), GroupRead::Pending(StreamId::MIN))];
+    assert_eq!(ret.streams, streams);
+
+    buf.extend_from_slice(
+      b"*6\r\n$10\r\nxreadgroup\r\n$5\r\nGROUP\r\n$1\r\ng\r\n$1\r\nc\r\n$7\r\nSTREAMS\r\n$1\r\na\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(XReadGroup::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xreadgroup_execute() -> Result<()> {
+    let backend = Backend::new();
+    backend.xgroup_create("mystream", "mygroup", None, true, None)?;
+    let fields = vec![BulkString::from("message").into(), BulkString::from("apple").into()];
+    let id = StreamIdSpec::Explicit(StreamId::new(1, 0));
+    backend.xadd("mystream", id, fields.clone(), false, None)?;
+    let xreadgroup = |read| XReadGroup {
+      group: "mygroup".to_string(),
+      consumer: "alice".to_string(),
+      count: None,
+      noack: false,
+      blocking: false,
+      timeout: None,
+      streams: vec![("mystream".to_string(), read)],
+    };
+
+    let entry = RespArray::new([BulkString::from("1-0").into(), RespArray::new(fields).into()]);
+    let expected = RespArray::new([RespArray::new([
+      BulkString::from("mystream").into(),
+      RespArray::new([entry.into()]).into(),
+    ])
+    .into()]);
+    assert_eq!(xreadgroup(GroupRead::New).execute(&backend), expected.clone().into());
+    assert_eq!(xreadgroup(GroupRead::New).execute(&backend), RESP_NULL.clone());
+    assert_eq!(xreadgroup(GroupRead::Pending(StreamId::MIN)).execute(&backend), expected.into());
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_xreadgroup_run_blocking() -> Result<()> {
+    let backend = Backend::new();
+    backend.xgroup_create("mystream", "mygroup", None, true, None)?;
+    let xreadgroup = |timeout| XReadGroup {
+      group: "mygroup".to_string(),
+      consumer: "alice".to_string(),
+      count: None,
+      noack: false,
+      blocking: true,
+      timeout,
+      streams: vec![("mystream".to_string(), GroupRead::New)],
+    };
+    let ret = xreadgroup(Some(Duration::from_millis(10))).run(&backend).await;
+    assert_eq!(ret, RESP_NULL.clone());
+
+    let handle = tokio::spawn({
+      let backend = backend.clone();
+      async move { xreadgroup(None).run(&backend).await }
+    });
+    let mut ids = vec![];
+    while !handle.is_finished() {
+      ids.extend(backend.xadd("mystream", StreamIdSpec::Auto, vec![], false, None)?);
+      tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    // the first entry is delivered right away, it was added after the group was created
+    let id = ids[0];
+    let expected = encode_streams(vec![("mystream".to_string(), vec![(id, Some(vec![]))])]);
+    assert_eq!(handle.await?, expected);
+    assert_eq!(backend.xpending_summary("mystream", "mygroup")?.count, 1);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,144 @@
+use super::{
+  extract_args, parse_integer, parse_stream_id, parse_string, CommandError, CommandExecutor,
+};
+use crate::{Backend, RespArray, RespFrame, StreamTrim, TrimStrategy};
+use std::iter::Peekable;
+
+/// XTRIM key <MAXLEN | MINID> [= | ~] threshold [LIMIT count]
+///  XADD mystream * field1 A field2 B field3 C field4 D
+/// "1609459200000-0"
+///  XTRIM mystream MAXLEN 2
+/// (integer) 0
+#[derive(Debug)]
+pub struct XTrim {
+  pub(crate) key: String,
+  pub(crate) trim: StreamTrim,
+}
+
+/// Parse what follows `MAXLEN | MINID`, `strategy` is the keyword itself.
+pub(crate) fn parse_trim(
+  strategy: &str,
+  args: &mut Peekable<impl Iterator<Item = RespFrame>>,
+) -> Result<StreamTrim, CommandError> {
+  let mut approx = false;
+  if let Some(RespFrame::BulkString(op)) = args.peek() {
+    match op.as_ref() {
+      b"~" => approx = true,
+      b"=" => {}
+      _ => return parse_threshold(strategy, args, approx),
+    }
+    args.next();
+  }
+  parse_threshold(strategy, args, approx)
+}
+
+fn parse_threshold(
+  strategy: &str,
+  args: &mut Peekable<impl Iterator<Item = RespFrame>>,
+  approx: bool,
+) -> Result<StreamTrim, CommandError> {
+  let threshold = args.next().ok_or(CommandError::SyntaxError)?;
+  let strategy = match strategy.to_ascii_lowercase().as_str() {
+    "maxlen" => match parse_integer(threshold)? {
+      len if len < 0 => {
+        return Err(CommandError::InvalidArgument("The MAXLEN argument must be >= 0.".to_string()))
+      }
+      len => TrimStrategy::MaxLen(len as usize),
+    },
+    "minid" => TrimStrategy::MinId(parse_stream_id(threshold)?),
+    _ => return Err(CommandError::SyntaxError),
+  };
+
+  let mut limit = None;
+  if let Some(RespFrame::BulkString(opt)) = args.peek() {
+    if opt.as_ref().eq_ignore_ascii_case(b"limit") {
+      args.next();
+      if !approx {
+        return Err(CommandError::InvalidArgument(
+          "syntax error, LIMIT cannot be used without the special ~ option".to_string(),
+        ));
+      }
+      match parse_integer(args.next().ok_or(CommandError::SyntaxError)?)? {
+        n if n < 0 => {
+          return Err(CommandError::InvalidArgument("The LIMIT argument must be >= 0.".to_string()))
+        }
+        n => limit = Some(n as usize),
+      }
+    }
+  }
+  Ok(StreamTrim { strategy, approx, limit })
+}
+
+impl CommandExecutor for XTrim {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.xtrim(&self.key, self.trim) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for XTrim {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter().peekable();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let strategy = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let trim = parse_trim(&strategy, &mut args)?;
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(XTrim { key, trim })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, StreamId, StreamIdSpec};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_xtrim_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*7\r\n$5\r\nXTRIM\r\n$8\r\nmystream\r\n$5\r\nMINID\r\n$1\r\n~\r\n$3\r\n5-1\r\n$5\r\nLIMIT\r\n$2\r\n10\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: XTrim = frame.try_into()?;
+    assert_eq!(ret.key, "mystream");
+    assert_eq!(
+      ret.trim,
+      StreamTrim {
+        strategy: TrimStrategy::MinId(StreamId::new(5, 1)),
+        approx: true,
+        limit: Some(10)
+      }
+    );
+
+    buf.extend_from_slice(
+      b"*6\r\n$5\r\nxtrim\r\n$8\r\nmystream\r\n$6\r\nMAXLEN\r\n$1\r\n2\r\n$5\r\nLIMIT\r\n$2\r\n10\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(XTrim::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_xtrim_execute() -> Result<()> {
+    let backend = Backend::new();
+    for _ in 0..4 {
+      backend.xadd("mystream", StreamIdSpec::Auto, vec![], false, None)?;
+    }
+
+    let trim = StreamTrim { strategy: TrimStrategy::MaxLen(2), approx: false, limit: None };
+    let ret = XTrim { key: "mystream".to_string(), trim }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+    assert_eq!(backend.xlen("mystream")?, 2);
+
+    Ok(())
+  }
+}
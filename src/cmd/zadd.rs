@@ -0,0 +1,135 @@
+use super::{extract_args, parse_float, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, RespArray, RespFrame, ZAddOptions};
+
+/// ZADD key [NX | XX] [GT | LT] [CH] [INCR] score member [score member ...]
+///  ZADD myzset 1 "one"
+/// (integer) 1
+///  ZADD myzset 1 "uno" 2 "two" 3 "three"
+/// (integer) 3
+///  ZADD myzset XX CH 5 "one" 6 "four"
+/// (integer) 1
+///  ZADD myzset INCR 2 "two"
+/// (double) 4
+#[derive(Debug)]
+pub struct ZAdd {
+  pub(crate) key: String,
+  pub(crate) pairs: Vec<(f64, String)>,
+  pub(crate) opts: ZAddOptions,
+}
+
+impl CommandExecutor for ZAdd {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zadd(&self.key, self.pairs, self.opts) {
+      Ok((_, Some(score))) if self.opts.incr => RespFrame::Double(score),
+      Ok(_) if self.opts.incr => RESP_NULL.clone(),
+      Ok((n, _)) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZAdd {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter().peekable();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+
+    let mut opts = ZAddOptions::default();
+    while let Some(RespFrame::BulkString(opt)) = args.peek() {
+      match opt.as_ref().to_ascii_lowercase().as_slice() {
+        b"nx" => opts.nx = true,
+        b"xx" => opts.xx = true,
+        b"gt" => opts.gt = true,
+        b"lt" => opts.lt = true,
+        b"ch" => opts.ch = true,
+        b"incr" => opts.incr = true,
+        _ => break,
+      }
+      args.next();
+    }
+
+    let rest: Vec<RespFrame> = args.collect();
+    if rest.is_empty() || !rest.len().is_multiple_of(2) {
+      return Err(CommandError::SyntaxError);
+    }
+    if opts.nx && opts.xx {
+      return Err(CommandError::InvalidArgument(
+        "XX and NX options at the same time are not compatible".to_string(),
+      ));
+    }
+    if (opts.gt && opts.lt) || ((opts.gt || opts.lt) && opts.nx) {
+      return Err(CommandError::InvalidArgument(
+        "GT, LT, and/or NX options at the same time are not compatible".to_string(),
+      ));
+    }
+    if opts.incr && rest.len() > 2 {
+      return Err(CommandError::InvalidArgument(
+        "INCR option supports a single increment-element pair".to_string(),
+      ));
+    }
+
+    let mut rest = rest.into_iter();
+    let mut pairs = vec![];
+    while let (Some(score), Some(member)) = (rest.next(), rest.next()) {
+      pairs.push((parse_float(score)?, parse_string(member)?));
+    }
+    Ok(ZAdd { key, pairs, opts })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zadd_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*8\r\n$4\r\nZADD\r\n$6\r\nmyzset\r\n$2\r\nXX\r\n$2\r\nch\r\n$1\r\n5\r\n$3\r\none\r\n$4\r\n-inf\r\n$4\r\nfour\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZAdd = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert_eq!(ret.pairs, vec![(5.0, "one".to_string()), (f64::NEG_INFINITY, "four".to_string())]);
+    assert_eq!(ret.opts, ZAddOptions { xx: true, ch: true, ..Default::default() });
+
+    buf.extend_from_slice(b"*5\r\n$4\r\nzadd\r\n$1\r\nz\r\n$2\r\nnx\r\n$2\r\ngt\r\n$1\r\n1\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZAdd::try_from(frame), Err(CommandError::SyntaxError)));
+
+    buf.extend_from_slice(
+      b"*6\r\n$4\r\nzadd\r\n$1\r\nz\r\n$2\r\nnx\r\n$2\r\ngt\r\n$1\r\n1\r\n$1\r\na\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZAdd::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    buf.extend_from_slice(b"*4\r\n$4\r\nzadd\r\n$1\r\nz\r\n$3\r\nnan\r\n$1\r\na\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZAdd::try_from(frame), Err(CommandError::NotFloat)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zadd_execute() {
+    let backend = Backend::new();
+    let zadd = |pairs: &[(f64, &str)], opts| ZAdd {
+      key: "myzset".to_string(),
+      pairs: pairs.iter().map(|(s, m)| (*s, m.to_string())).collect(),
+      opts,
+    };
+
+    let ret = zadd(&[(1.0, "one"), (2.0, "two")], ZAddOptions::default()).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+
+    let incr = ZAddOptions { incr: true, ..Default::default() };
+    assert_eq!(zadd(&[(2.0, "two")], incr).execute(&backend), RespFrame::Double(4.0));
+
+    let incr_nx = ZAddOptions { incr: true, nx: true, ..Default::default() };
+    assert_eq!(zadd(&[(2.0, "two")], incr_nx).execute(&backend), RESP_NULL.clone());
+  }
+}
@@ -0,0 +1,62 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// ZCARD key
+///  ZADD myzset 1 "one" 2 "two"
+/// (integer) 2
+///  ZCARD myzset
+/// (integer) 2
+#[derive(Debug)]
+pub struct ZCard {
+  pub(crate) key: String,
+}
+
+impl CommandExecutor for ZCard {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zcard(&self.key) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZCard {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    Ok(ZCard { key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zcard_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*2\r\n$5\r\nZCARD\r\n$6\r\nmyzset\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZCard = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zcard_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs = vec![(1.0, "one".to_string()), (2.0, "two".to_string())];
+    backend.zadd("myzset", pairs, ZAddOptions::default())?;
+
+    assert_eq!(ZCard { key: "myzset".to_string() }.execute(&backend), RespFrame::Integer(2));
+    assert_eq!(ZCard { key: "missing".to_string() }.execute(&backend), RespFrame::Integer(0));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,83 @@
+use super::{extract_args, parse_string, zrange::parse_score_bound, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+use std::ops::Bound;
+
+/// ZCOUNT key min max
+///  ZADD myzset 1 "one" 2 "two" 3 "three"
+/// (integer) 3
+///  ZCOUNT myzset -inf +inf
+/// (integer) 3
+///  ZCOUNT myzset (1 3
+/// (integer) 2
+#[derive(Debug)]
+pub struct ZCount {
+  pub(crate) key: String,
+  pub(crate) min: Bound<f64>,
+  pub(crate) max: Bound<f64>,
+}
+
+impl CommandExecutor for ZCount {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zcount(&self.key, self.min, self.max) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZCount {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(min), Some(max)) => Ok(ZCount {
+        key: parse_string(key)?,
+        min: parse_score_bound(min)?,
+        max: parse_score_bound(max)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid key, min or max".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zcount_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$6\r\nZCOUNT\r\n$6\r\nmyzset\r\n$2\r\n(1\r\n$4\r\n+inf\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZCount = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert_eq!(ret.min, Bound::Excluded(1.0));
+    assert_eq!(ret.max, Bound::Included(f64::INFINITY));
+
+    buf.extend_from_slice(b"*4\r\n$6\r\nZCOUNT\r\n$6\r\nmyzset\r\n$1\r\na\r\n$1\r\n1\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZCount::try_from(frame), Err(CommandError::InvalidScoreRange)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zcount_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs =
+      vec![(1.0, "one".to_string()), (2.0, "two".to_string()), (3.0, "three".to_string())];
+    backend.zadd("myzset", pairs, ZAddOptions::default())?;
+
+    let zcount = |min, max| ZCount { key: "myzset".to_string(), min, max };
+    let ret = zcount(Bound::Excluded(1.0), Bound::Included(3.0)).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+    let ret = zcount(Bound::Included(f64::NEG_INFINITY), Bound::Excluded(1.0)).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(0));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,74 @@
+use super::{extract_args, parse_float, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame, ZAddOptions};
+
+/// ZINCRBY key increment member
+///  ZADD myzset 1 "one"
+/// (integer) 1
+///  ZINCRBY myzset 2 "one"
+/// (double) 3
+#[derive(Debug)]
+pub struct ZIncrBy {
+  pub(crate) key: String,
+  pub(crate) increment: f64,
+  pub(crate) member: String,
+}
+
+impl CommandExecutor for ZIncrBy {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let opts = ZAddOptions { incr: true, ..Default::default() };
+    match backend.zadd(&self.key, vec![(self.increment, self.member)], opts) {
+      Ok((_, Some(score))) => RespFrame::Double(score),
+      Ok((_, None)) => unreachable!("an unconditional increment always writes"),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZIncrBy {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(increment), Some(member)) => Ok(ZIncrBy {
+        key: parse_string(key)?,
+        increment: parse_float(increment)?,
+        member: parse_string(member)?,
+      }),
+      _ => Err(CommandError::InvalidArgument("Invalid key, increment or member".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::RespDecode;
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zincrby_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$7\r\nZINCRBY\r\n$6\r\nmyzset\r\n$3\r\n2.5\r\n$3\r\none\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZIncrBy = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert_eq!(ret.increment, 2.5);
+    assert_eq!(ret.member, "one");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zincrby_execute() {
+    let backend = Backend::new();
+    let zincrby =
+      |increment| ZIncrBy { key: "myzset".to_string(), increment, member: "one".to_string() };
+
+    assert_eq!(zincrby(1.0).execute(&backend), RespFrame::Double(1.0));
+    assert_eq!(zincrby(2.0).execute(&backend), RespFrame::Double(3.0));
+    assert_eq!(zincrby(f64::INFINITY).execute(&backend), RespFrame::Double(f64::INFINITY));
+    assert_eq!(zincrby(f64::NEG_INFINITY).execute(&backend), CommandError::NotANumber.into());
+  }
+}
@@ -0,0 +1,93 @@
+use super::{
+  extract_args, parse_integer, parse_string, zunion::parse_keys, CommandError, CommandExecutor,
+};
+use crate::{Aggregate, Backend, RespArray, RespFrame, ZSetOp};
+
+/// ZINTERCARD numkeys key [key ...] [LIMIT limit]
+///  ZADD zset1 1 "one" 2 "two"
+/// (integer) 2
+///  ZADD zset2 1 "one" 2 "two" 3 "three"
+/// (integer) 3
+///  ZINTERCARD 2 zset1 zset2
+/// (integer) 2
+///  ZINTERCARD 2 zset1 zset2 LIMIT 1
+/// (integer) 1
+#[derive(Debug)]
+pub struct ZInterCard {
+  pub(crate) keys: Vec<String>,
+  // 0 means no limit
+  pub(crate) limit: usize,
+}
+
+impl CommandExecutor for ZInterCard {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zcombine(ZSetOp::Inter, &self.keys, &[], Aggregate::Sum) {
+      Ok(items) if self.limit > 0 => RespFrame::Integer(items.len().min(self.limit) as i64),
+      Ok(items) => RespFrame::Integer(items.len() as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZInterCard {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let keys = parse_keys(&mut args, "zintercard")?;
+    let limit = match (args.next(), args.next(), args.next()) {
+      (None, _, _) => 0,
+      (Some(opt), Some(limit), None) => {
+        if !parse_string(opt)?.eq_ignore_ascii_case("limit") {
+          return Err(CommandError::SyntaxError);
+        }
+        match parse_integer(limit)? {
+          limit if limit < 0 => {
+            return Err(CommandError::InvalidArgument("LIMIT can't be negative".to_string()))
+          }
+          limit => limit as usize,
+        }
+      }
+      _ => return Err(CommandError::SyntaxError),
+    };
+    Ok(ZInterCard { keys, limit })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zintercard_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*6\r\n$10\r\nZINTERCARD\r\n$1\r\n2\r\n$5\r\nzset1\r\n$5\r\nzset2\r\n$5\r\nLIMIT\r\n$1\r\n1\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZInterCard = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["zset1", "zset2"]);
+    assert_eq!(ret.limit, 1);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zintercard_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs = |items: &[&str]| items.iter().map(|m| (1.0, m.to_string())).collect();
+    backend.zadd("zset1", pairs(&["one", "two"]), ZAddOptions::default())?;
+    backend.zadd("zset2", pairs(&["one", "two", "three"]), ZAddOptions::default())?;
+    let keys = vec!["zset1".to_string(), "zset2".to_string()];
+
+    let ret = ZInterCard { keys: keys.clone(), limit: 0 }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+    let ret = ZInterCard { keys, limit: 1 }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,155 @@
+use super::{
+  extract_args, extract_command_name, parse_integer, parse_string, parse_timeout,
+  zpopmin::member_score, zunion::parse_keys, CommandError, CommandExecutor, RESP_NULL,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+use std::time::Duration;
+
+/// ZMPOP numkeys key [key ...] <MIN | MAX> [COUNT count]
+/// BZMPOP timeout numkeys key [key ...] <MIN | MAX> [COUNT count]
+///  ZADD myzset 1 "one" 2 "two" 3 "three"
+/// (integer) 3
+///  ZMPOP 2 notsuchkey myzset MIN COUNT 2
+/// 1) "myzset"
+/// 2) 1) 1) "one"
+///       2) (double) 1
+///    2) 1) "two"
+///       2) (double) 2
+#[derive(Debug)]
+pub struct ZMPop {
+  pub(crate) keys: Vec<String>,
+  pub(crate) max: bool,
+  pub(crate) count: usize,
+  // only BZMPOP waits for a write, `timeout` is meaningless otherwise
+  pub(crate) blocking: bool,
+  pub(crate) timeout: Option<Duration>,
+}
+
+impl ZMPop {
+  /// Pop from the first non-empty key, for BZMPOP waiting for a write if they are all empty.
+  pub async fn run(self, backend: &Backend) -> RespFrame {
+    if !self.blocking {
+      return backend.locked(false, || self.execute(backend));
+    }
+    let (max, count) = (self.max, self.count);
+    let serve = move |backend: &Backend, key: &str| pop(backend, key, max, count);
+    backend.block_on(self.keys, self.timeout, false, serve).await.unwrap_or(RESP_NULL.clone())
+  }
+}
+
+fn pop(backend: &Backend, key: &str, max: bool, count: usize) -> Option<RespFrame> {
+  match backend.zpop(key, max, count) {
+    Ok(Some(items)) => {
+      let items: Vec<_> =
+        items.into_iter().map(|(member, score)| member_score(member, score)).collect();
+      Some(RespArray::new([BulkString::from(key).into(), RespArray::new(items).into()]).into())
+    }
+    Ok(None) => None,
+    Err(e) => Some(e.into()),
+  }
+}
+
+impl CommandExecutor for ZMPop {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ret = self.keys.iter().find_map(|key| pop(backend, key, self.max, self.count));
+    ret.unwrap_or(RESP_NULL.clone())
+  }
+}
+
+impl TryFrom<RespArray> for ZMPop {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let name = extract_command_name(&value)?;
+    let blocking = name == "bzmpop";
+    let mut args = extract_args(value, 1)?.into_iter();
+    let timeout = match blocking {
+      true => parse_timeout(args.next().ok_or(CommandError::SyntaxError)?)?,
+      false => None,
+    };
+
+    let keys = parse_keys(&mut args, &name)?;
+    let max = match parse_string(args.next().ok_or(CommandError::SyntaxError)?)?
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      "min" => false,
+      "max" => true,
+      _ => return Err(CommandError::SyntaxError),
+    };
+
+    let count = match (args.next(), args.next(), args.next()) {
+      (None, _, _) => 1,
+      (Some(opt), Some(count), None) => {
+        if !parse_string(opt)?.eq_ignore_ascii_case("count") {
+          return Err(CommandError::SyntaxError);
+        }
+        match parse_integer(count)? {
+          count if count > 0 => count as usize,
+          _ => {
+            return Err(CommandError::InvalidArgument("count should be greater than 0".to_string()))
+          }
+        }
+      }
+      _ => return Err(CommandError::SyntaxError),
+    };
+
+    Ok(ZMPop { keys, max, count, blocking, timeout })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zmpop_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*8\r\n$6\r\nBZMPOP\r\n$1\r\n1\r\n$1\r\n2\r\n$10\r\nnotsuchkey\r\n$6\r\nmyzset\r\n$3\r\nMAX\r\n$5\r\nCOUNT\r\n$1\r\n2\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZMPop = frame.try_into()?;
+    assert_eq!(ret.keys, vec!["notsuchkey", "myzset"]);
+    assert!(ret.max);
+    assert_eq!(ret.count, 2);
+    assert!(ret.blocking);
+    assert_eq!(ret.timeout, Some(Duration::from_secs(1)));
+
+    buf.extend_from_slice(b"*4\r\n$5\r\nzmpop\r\n$1\r\n1\r\n$6\r\nmyzset\r\n$6\r\nMIDDLE\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZMPop::try_from(frame), Err(CommandError::SyntaxError)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zmpop_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs =
+      vec![(1.0, "one".to_string()), (2.0, "two".to_string()), (3.0, "three".to_string())];
+    backend.zadd("myzset", pairs, ZAddOptions::default())?;
+    let zmpop = || ZMPop {
+      keys: vec!["notsuchkey".to_string(), "myzset".to_string()],
+      max: false,
+      count: 2,
+      blocking: false,
+      timeout: None,
+    };
+
+    let ret = zmpop().execute(&backend);
+    let items = [member_score("one".to_string(), 1.0), member_score("two".to_string(), 2.0)];
+    assert_eq!(
+      ret,
+      RespArray::new([BulkString::from("myzset").into(), RespArray::new(items).into()]).into()
+    );
+
+    zmpop().execute(&backend);
+    assert_eq!(zmpop().execute(&backend), RESP_NULL.clone());
+
+    Ok(())
+  }
+}
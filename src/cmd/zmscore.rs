@@ -0,0 +1,76 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, RespArray, RespFrame};
+
+/// ZMSCORE key member [member ...]
+///  ZADD myzset 1 "one" 2 "two"
+/// (integer) 2
+///  ZMSCORE myzset "one" "two" "nofield"
+/// 1) (double) 1
+/// 2) (double) 2
+/// 3) (nil)
+#[derive(Debug)]
+pub struct ZMScore {
+  pub(crate) key: String,
+  pub(crate) members: Vec<String>,
+}
+
+impl CommandExecutor for ZMScore {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zmscore(&self.key, &self.members) {
+      Ok(scores) => {
+        let scores = scores.into_iter().map(|score| match score {
+          Some(score) => RespFrame::Double(score),
+          None => RESP_NULL.clone(),
+        });
+        RespArray::new(scores.collect::<Vec<_>>()).into()
+      }
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZMScore {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let members = args.map(parse_string).collect::<Result<Vec<_>, _>>()?;
+    if members.is_empty() {
+      return Err(CommandError::InvalidArgument("Invalid member".to_string()));
+    }
+    Ok(ZMScore { key, members })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zmscore_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$7\r\nZMSCORE\r\n$6\r\nmyzset\r\n$3\r\none\r\n$3\r\ntwo\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZMScore = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert_eq!(ret.members, vec!["one", "two"]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zmscore_execute() -> Result<()> {
+    let backend = Backend::new();
+    backend.zadd("myzset", vec![(1.0, "one".to_string())], ZAddOptions::default())?;
+
+    let members = vec!["one".to_string(), "nofield".to_string()];
+    let ret = ZMScore { key: "myzset".to_string(), members }.execute(&backend);
+    assert_eq!(ret, RespArray::new([RespFrame::Double(1.0), RESP_NULL.clone()]).into());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,99 @@
+use super::{
+  extract_args, extract_command_name, parse_integer, parse_string, CommandError, CommandExecutor,
+};
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+/// ZPOPMIN key [count]
+/// ZPOPMAX key [count]
+///  ZADD myzset 1 "one" 2 "two" 3 "three"
+/// (integer) 3
+///  ZPOPMIN myzset
+/// 1) "one"
+/// 2) (double) 1
+#[derive(Debug)]
+pub struct ZPopMin {
+  pub(crate) key: String,
+  pub(crate) max: bool,
+  pub(crate) count: Option<usize>,
+}
+
+/// A `member score` pair as a two element array.
+pub(crate) fn member_score(member: String, score: f64) -> RespFrame {
+  RespArray::new([BulkString::from(member).into(), RespFrame::Double(score)]).into()
+}
+
+impl CommandExecutor for ZPopMin {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let items = match backend.zpop(&self.key, self.max, self.count.unwrap_or(1)) {
+      Ok(items) => items.unwrap_or_default(),
+      Err(e) => return e.into(),
+    };
+    if self.count.is_some() {
+      let items = items.into_iter().map(|(member, score)| member_score(member, score));
+      return RespArray::new(items.collect::<Vec<_>>()).into();
+    }
+    match items.into_iter().next() {
+      Some((member, score)) => member_score(member, score),
+      None => RespArray::new([]).into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZPopMin {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let max = extract_command_name(&value)? == "zpopmax";
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let count = match args.next().map(parse_integer).transpose()? {
+      Some(count) if count < 0 => return Err(CommandError::NotPositive),
+      count => count.map(|count| count as usize),
+    };
+    if args.next().is_some() {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(ZPopMin { key, max, count })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zpopmin_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$7\r\nZPOPMAX\r\n$6\r\nmyzset\r\n$1\r\n2\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZPopMin = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert!(ret.max);
+    assert_eq!(ret.count, Some(2));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zpopmin_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs =
+      vec![(1.0, "one".to_string()), (2.0, "two".to_string()), (3.0, "three".to_string())];
+    backend.zadd("myzset", pairs, ZAddOptions::default())?;
+
+    let ret = ZPopMin { key: "myzset".to_string(), max: false, count: None }.execute(&backend);
+    assert_eq!(ret, member_score("one".to_string(), 1.0));
+
+    let ret = ZPopMin { key: "myzset".to_string(), max: true, count: Some(5) }.execute(&backend);
+    let expected = [member_score("three".to_string(), 3.0), member_score("two".to_string(), 2.0)];
+    assert_eq!(ret, RespArray::new(expected).into());
+
+    let ret = ZPopMin { key: "myzset".to_string(), max: true, count: None }.execute(&backend);
+    assert_eq!(ret, RespArray::new([]).into());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,209 @@
+use super::{extract_args, parse_integer, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, BulkString, RespArray, RespFrame, ZRangeBy};
+use std::ops::Bound;
+
+/// ZRANGE key start stop [BYSCORE | BYLEX] [REV] [LIMIT offset count] [WITHSCORES]
+///  ZADD myzset 1 "one" 2 "two" 3 "three"
+/// (integer) 3
+///  ZRANGE myzset (3 +inf BYSCORE LIMIT 0 1
+/// (empty array)
+///  ZRANGE myzset 0 1 REV WITHSCORES
+/// 1) 1) "three"
+///    2) (double) 3
+/// 2) 1) "two"
+///    2) (double) 2
+#[derive(Debug)]
+pub struct ZRange {
+  pub(crate) key: String,
+  pub(crate) by: ZRangeBy,
+  pub(crate) rev: bool,
+  pub(crate) limit: Option<(i64, i64)>,
+  pub(crate) with_scores: bool,
+}
+
+/// Parse a `ZRANGE ... BYSCORE` style bound: a float, `-inf`/`+inf`, or `(` for exclusive.
+pub(crate) fn parse_score_bound(frame: RespFrame) -> Result<Bound<f64>, CommandError> {
+  let s = parse_string(frame).map_err(|_| CommandError::InvalidScoreRange)?;
+  let (s, exclusive) = match s.strip_prefix('(') {
+    Some(s) => (s, true),
+    None => (s.as_str(), false),
+  };
+  let score: f64 = s.parse().map_err(|_| CommandError::InvalidScoreRange)?;
+  if score.is_nan() {
+    return Err(CommandError::InvalidScoreRange);
+  }
+  Ok(if exclusive { Bound::Excluded(score) } else { Bound::Included(score) })
+}
+
+/// Parse a `ZRANGE ... BYLEX` style bound: `[member`, `(member`, or `-`/`+` for unbounded.
+/// `is_max` tells which end an unbounded `-`/`+` stands for.
+pub(crate) fn parse_lex_bound(
+  frame: RespFrame,
+  is_max: bool,
+) -> Result<Bound<String>, CommandError> {
+  let s = parse_string(frame).map_err(|_| CommandError::InvalidLexRange)?;
+  let mut chars = s.chars();
+  match (chars.next(), is_max) {
+    (Some('['), _) => Ok(Bound::Included(chars.collect())),
+    (Some('('), _) => Ok(Bound::Excluded(chars.collect())),
+    (Some('-'), false) | (Some('+'), true) if s.len() == 1 => Ok(Bound::Unbounded),
+    // `+` as a minimum or `-` as a maximum selects nothing
+    (Some('-'), true) if s.len() == 1 => Ok(Bound::Excluded(String::new())),
+    (Some('+'), false) if s.len() == 1 => Ok(Bound::Excluded(char::MAX.to_string())),
+    _ => Err(CommandError::InvalidLexRange),
+  }
+}
+
+/// Members, each paired with its score if `with_scores` is set.
+pub(crate) fn scored_reply(items: Vec<(String, f64)>, with_scores: bool) -> RespFrame {
+  let frames: Vec<RespFrame> = items
+    .into_iter()
+    .map(|(member, score)| match with_scores {
+      true => RespArray::new([BulkString::from(member).into(), RespFrame::Double(score)]).into(),
+      false => BulkString::from(member).into(),
+    })
+    .collect();
+  RespArray::new(frames).into()
+}
+
+impl ZRange {
+  /// Parse `key start stop [options]`, the arguments shared by `ZRANGE` and `ZRANGESTORE`.
+  pub(crate) fn parse_args(args: Vec<RespFrame>) -> Result<Self, CommandError> {
+    let mut args = args.into_iter();
+    let (key, start, stop) = match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(start), Some(stop)) => (parse_string(key)?, start, stop),
+      _ => return Err(CommandError::SyntaxError),
+    };
+
+    let (mut by_score, mut by_lex, mut rev, mut limit, mut with_scores) =
+      (false, false, false, None, false);
+    while let Some(arg) = args.next() {
+      match parse_string(arg)?.to_ascii_lowercase().as_str() {
+        "byscore" => by_score = true,
+        "bylex" => by_lex = true,
+        "rev" => rev = true,
+        "withscores" => with_scores = true,
+        "limit" => match (args.next(), args.next()) {
+          (Some(offset), Some(count)) => {
+            limit = Some((parse_integer(offset)?, parse_integer(count)?))
+          }
+          _ => return Err(CommandError::SyntaxError),
+        },
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+    if by_score && by_lex {
+      return Err(CommandError::SyntaxError);
+    }
+    if limit.is_some() && !by_score && !by_lex {
+      return Err(CommandError::InvalidArgument(
+        "syntax error, LIMIT is only supported in combination with either BYSCORE or BYLEX"
+          .to_string(),
+      ));
+    }
+    if with_scores && by_lex {
+      return Err(CommandError::InvalidArgument(
+        "syntax error, WITHSCORES not supported in combination with BYLEX".to_string(),
+      ));
+    }
+
+    let by = if by_score || by_lex {
+      // with REV the interval of BYSCORE and BYLEX is given from the highest end
+      let (min, max) = if rev { (stop, start) } else { (start, stop) };
+      if by_score {
+        ZRangeBy::Score(parse_score_bound(min)?, parse_score_bound(max)?)
+      } else {
+        ZRangeBy::Lex(parse_lex_bound(min, false)?, parse_lex_bound(max, true)?)
+      }
+    } else {
+      ZRangeBy::Rank(parse_integer(start)?, parse_integer(stop)?)
+    };
+    Ok(ZRange { key, by, rev, limit, with_scores })
+  }
+}
+
+impl CommandExecutor for ZRange {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zrange(&self.key, &self.by, self.rev, self.limit) {
+      Ok(items) => scored_reply(items, self.with_scores),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZRange {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    ZRange::parse_args(extract_args(value, 1)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zrange_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*9\r\n$6\r\nZRANGE\r\n$6\r\nmyzset\r\n$4\r\n+inf\r\n$2\r\n(1\r\n$7\r\nBYSCORE\r\n$3\r\nREV\r\n$5\r\nLIMIT\r\n$1\r\n0\r\n$1\r\n1\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZRange = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert_eq!(ret.by, ZRangeBy::Score(Bound::Excluded(1.0), Bound::Included(f64::INFINITY)));
+    assert!(ret.rev);
+    assert_eq!(ret.limit, Some((0, 1)));
+    assert!(!ret.with_scores);
+
+    buf.extend_from_slice(
+      b"*6\r\n$6\r\nZRANGE\r\n$6\r\nmyzset\r\n$1\r\n-\r\n$2\r\n[c\r\n$5\r\nBYLEX\r\n$10\r\nWITHSCORES\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZRange::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    buf.extend_from_slice(
+      b"*5\r\n$6\r\nZRANGE\r\n$6\r\nmyzset\r\n$1\r\n-\r\n$1\r\nc\r\n$5\r\nBYLEX\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZRange::try_from(frame), Err(CommandError::InvalidLexRange)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zrange_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs =
+      vec![(1.0, "one".to_string()), (2.0, "two".to_string()), (3.0, "three".to_string())];
+    backend.zadd("myzset", pairs, ZAddOptions::default())?;
+
+    let ret = ZRange {
+      key: "myzset".to_string(),
+      by: ZRangeBy::Rank(0, 1),
+      rev: true,
+      limit: None,
+      with_scores: true,
+    }
+    .execute(&backend);
+    let pair =
+      |m: &str, s| RespArray::new([BulkString::from(m).into(), RespFrame::Double(s)]).into();
+    assert_eq!(ret, RespArray::new([pair("three", 3.0), pair("two", 2.0)]).into());
+
+    let ret = ZRange {
+      key: "myzset".to_string(),
+      by: ZRangeBy::Lex(Bound::Unbounded, Bound::Excluded(String::new())),
+      rev: false,
+      limit: None,
+      with_scores: false,
+    }
+    .execute(&backend);
+    assert_eq!(ret, RespArray::new([]).into());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,100 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor, ZRange};
+use crate::{Backend, RespArray, RespFrame};
+
+/// ZRANGESTORE dst src min max [BYSCORE | BYLEX] [REV] [LIMIT offset count]
+///  ZADD srczset 1 "one" 2 "two" 3 "three" 4 "four"
+/// (integer) 4
+///  ZRANGESTORE dstzset srczset 2 -1
+/// (integer) 2
+///  ZRANGE dstzset 0 -1
+/// 1) "three"
+/// 2) "four"
+#[derive(Debug)]
+pub struct ZRangeStore {
+  pub(crate) destination: String,
+  pub(crate) range: ZRange,
+}
+
+impl CommandExecutor for ZRangeStore {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let ZRange { key, by, rev, limit, .. } = self.range;
+    match backend.zrange(&key, &by, rev, limit) {
+      Ok(items) => RespFrame::Integer(backend.zstore(&self.destination, items) as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZRangeStore {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?;
+    if args.is_empty() {
+      return Err(CommandError::SyntaxError);
+    }
+    let destination = parse_string(args.remove(0))?;
+    let range = ZRange::parse_args(args)?;
+    if range.with_scores {
+      return Err(CommandError::SyntaxError);
+    }
+    Ok(ZRangeStore { destination, range })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions, ZRangeBy};
+  use anyhow::Result;
+  use bytes::BytesMut;
+  use std::ops::Bound;
+
+  #[test]
+  fn test_zrangestore_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*6\r\n$11\r\nZRANGESTORE\r\n$7\r\ndstzset\r\n$7\r\nsrczset\r\n$1\r\n2\r\n$4\r\n+inf\r\n$7\r\nBYSCORE\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZRangeStore = frame.try_into()?;
+    assert_eq!(ret.destination, "dstzset");
+    assert_eq!(ret.range.key, "srczset");
+    assert_eq!(ret.range.by, ZRangeBy::Score(Bound::Included(2.0), Bound::Included(f64::INFINITY)));
+
+    buf.extend_from_slice(
+      b"*6\r\n$11\r\nZRANGESTORE\r\n$3\r\ndst\r\n$3\r\nsrc\r\n$1\r\n0\r\n$2\r\n-1\r\n$10\r\nWITHSCORES\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZRangeStore::try_from(frame), Err(CommandError::SyntaxError)));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zrangestore_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs = ["one", "two", "three", "four"]
+      .iter()
+      .enumerate()
+      .map(|(i, m)| (i as f64 + 1.0, m.to_string()))
+      .collect();
+    backend.zadd("srczset", pairs, ZAddOptions::default())?;
+
+    let range = ZRange {
+      key: "srczset".to_string(),
+      by: ZRangeBy::Rank(2, -1),
+      rev: false,
+      limit: None,
+      with_scores: false,
+    };
+    let ret = ZRangeStore { destination: "dstzset".to_string(), range }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(2));
+    assert_eq!(
+      backend.zrange("dstzset", &ZRangeBy::Rank(0, -1), false, None)?,
+      vec![("three".to_string(), 3.0), ("four".to_string(), 4.0)]
+    );
+
+    Ok(())
+  }
+}
@@ -0,0 +1,102 @@
+use super::{
+  extract_args, extract_command_name, parse_string, CommandError, CommandExecutor, RESP_NULL,
+};
+use crate::{Backend, RespArray, RespFrame};
+
+/// ZRANK key member [WITHSCORE]
+/// ZREVRANK key member [WITHSCORE]
+///  ZADD myzset 1 "one" 2 "two" 3 "three"
+/// (integer) 3
+///  ZRANK myzset "four"
+/// (nil)
+///  ZREVRANK myzset "one"
+/// (integer) 2
+///  ZRANK myzset "three" WITHSCORE
+/// 1) (integer) 2
+/// 2) (double) 3
+#[derive(Debug)]
+pub struct ZRank {
+  pub(crate) key: String,
+  pub(crate) member: String,
+  pub(crate) rev: bool,
+  pub(crate) with_score: bool,
+}
+
+impl CommandExecutor for ZRank {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zrank(&self.key, &self.member, self.rev) {
+      Ok(Some((rank, score))) if self.with_score => {
+        RespArray::new([RespFrame::Integer(rank as i64), RespFrame::Double(score)]).into()
+      }
+      Ok(Some((rank, _))) => RespFrame::Integer(rank as i64),
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZRank {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let rev = extract_command_name(&value)? == "zrevrank";
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, member) = match (args.next(), args.next()) {
+      (Some(key), Some(member)) => (parse_string(key)?, parse_string(member)?),
+      _ => return Err(CommandError::InvalidArgument("Invalid key or member".to_string())),
+    };
+    let with_score = match args.next().map(parse_string).transpose()? {
+      Some(opt) if opt.eq_ignore_ascii_case("withscore") => true,
+      Some(_) => return Err(CommandError::SyntaxError),
+      None => false,
+    };
+    Ok(ZRank { key, member, rev, with_score })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zrank_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*4\r\n$8\r\nZREVRANK\r\n$6\r\nmyzset\r\n$3\r\none\r\n$9\r\nWITHSCORE\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZRank = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert_eq!(ret.member, "one");
+    assert!(ret.rev);
+    assert!(ret.with_score);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zrank_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs =
+      vec![(1.0, "one".to_string()), (2.0, "two".to_string()), (3.0, "three".to_string())];
+    backend.zadd("myzset", pairs, ZAddOptions::default())?;
+    let zrank = |member: &str, rev, with_score| ZRank {
+      key: "myzset".to_string(),
+      member: member.to_string(),
+      rev,
+      with_score,
+    };
+
+    assert_eq!(zrank("four", false, false).execute(&backend), RESP_NULL.clone());
+    assert_eq!(zrank("one", true, false).execute(&backend), RespFrame::Integer(2));
+    assert_eq!(
+      zrank("three", false, true).execute(&backend),
+      RespArray::new([RespFrame::Integer(2), RespFrame::Double(3.0)]).into()
+    );
+
+    Ok(())
+  }
+}
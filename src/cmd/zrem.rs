@@ -0,0 +1,75 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor};
+use crate::{Backend, RespArray, RespFrame};
+
+/// ZREM key member [member ...]
+///  ZADD myzset 1 "one" 2 "two"
+/// (integer) 2
+///  ZREM myzset "two" "four"
+/// (integer) 1
+#[derive(Debug)]
+pub struct ZRem {
+  pub(crate) key: String,
+  pub(crate) members: Vec<String>,
+}
+
+impl CommandExecutor for ZRem {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zrem(&self.key, &self.members) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZRem {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let key = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+    let members = args.map(parse_string).collect::<Result<Vec<_>, _>>()?;
+    if members.is_empty() {
+      return Err(CommandError::InvalidArgument("Invalid member".to_string()));
+    }
+    Ok(ZRem { key, members })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zrem_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*4\r\n$4\r\nZREM\r\n$6\r\nmyzset\r\n$3\r\ntwo\r\n$4\r\nfour\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZRem = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert_eq!(ret.members, vec!["two", "four"]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zrem_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs = vec![(1.0, "one".to_string()), (2.0, "two".to_string())];
+    backend.zadd("myzset", pairs, ZAddOptions::default())?;
+
+    let members = vec!["two".to_string(), "four".to_string()];
+    let ret = ZRem { key: "myzset".to_string(), members }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+
+    // removing the last member removes the key
+    let ret =
+      ZRem { key: "myzset".to_string(), members: vec!["one".to_string()] }.execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+    assert_eq!(backend.type_of("myzset"), None);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,95 @@
+use super::{
+  extract_args, extract_command_name, parse_integer, parse_string,
+  zrange::{parse_lex_bound, parse_score_bound},
+  CommandError, CommandExecutor,
+};
+use crate::{Backend, RespArray, RespFrame, ZRangeBy};
+
+/// ZREMRANGEBYSCORE key min max
+/// ZREMRANGEBYRANK key start stop
+/// ZREMRANGEBYLEX key min max
+///  ZADD myzset 1 "one" 2 "two" 3 "three"
+/// (integer) 3
+///  ZREMRANGEBYSCORE myzset -inf (2
+/// (integer) 1
+///  ZREMRANGEBYRANK myzset 0 0
+/// (integer) 1
+#[derive(Debug)]
+pub struct ZRemRange {
+  pub(crate) key: String,
+  pub(crate) by: ZRangeBy,
+}
+
+impl CommandExecutor for ZRemRange {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zremrange(&self.key, &self.by) {
+      Ok(n) => RespFrame::Integer(n as i64),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZRemRange {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let name = extract_command_name(&value)?;
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (key, min, max) = match (args.next(), args.next(), args.next()) {
+      (Some(key), Some(min), Some(max)) => (parse_string(key)?, min, max),
+      _ => return Err(CommandError::InvalidArgument("Invalid key, min or max".to_string())),
+    };
+    let by = match name.as_str() {
+      "zremrangebyscore" => ZRangeBy::Score(parse_score_bound(min)?, parse_score_bound(max)?),
+      "zremrangebylex" => ZRangeBy::Lex(parse_lex_bound(min, false)?, parse_lex_bound(max, true)?),
+      _ => ZRangeBy::Rank(parse_integer(min)?, parse_integer(max)?),
+    };
+    Ok(ZRemRange { key, by })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+  use std::ops::Bound;
+
+  #[test]
+  fn test_zremrange_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf
+      .extend_from_slice(b"*4\r\n$14\r\nZREMRANGEBYLEX\r\n$6\r\nmyzset\r\n$2\r\n[a\r\n$1\r\n+\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZRemRange = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert_eq!(ret.by, ZRangeBy::Lex(Bound::Included("a".to_string()), Bound::Unbounded));
+
+    buf.extend_from_slice(
+      b"*4\r\n$15\r\nZREMRANGEBYRANK\r\n$6\r\nmyzset\r\n$1\r\n0\r\n$2\r\n-2\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZRemRange = frame.try_into()?;
+    assert_eq!(ret.by, ZRangeBy::Rank(0, -2));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zremrange_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs =
+      vec![(1.0, "one".to_string()), (2.0, "two".to_string()), (3.0, "three".to_string())];
+    backend.zadd("myzset", pairs, ZAddOptions::default())?;
+    let zremrange = |by| ZRemRange { key: "myzset".to_string(), by };
+
+    let ret = zremrange(ZRangeBy::Score(Bound::Unbounded, Bound::Excluded(2.0))).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+    let ret = zremrange(ZRangeBy::Rank(0, 0)).execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(1));
+    assert_eq!(backend.zcard("myzset")?, 1);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,70 @@
+use super::{extract_args, parse_string, CommandError, CommandExecutor, RESP_NULL};
+use crate::{Backend, RespArray, RespFrame};
+
+/// ZSCORE key member
+///  ZADD myzset 1 "one"
+/// (integer) 1
+///  ZSCORE myzset "one"
+/// (double) 1
+#[derive(Debug)]
+pub struct ZScore {
+  pub(crate) key: String,
+  pub(crate) member: String,
+}
+
+impl CommandExecutor for ZScore {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    match backend.zscore(&self.key, &self.member) {
+      Ok(Some(score)) => RespFrame::Double(score),
+      Ok(None) => RESP_NULL.clone(),
+      Err(e) => e.into(),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZScore {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+      (Some(key), Some(member)) => {
+        Ok(ZScore { key: parse_string(key)?, member: parse_string(member)? })
+      }
+      _ => Err(CommandError::InvalidArgument("Invalid key or member".to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zscore_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(b"*3\r\n$6\r\nZSCORE\r\n$6\r\nmyzset\r\n$3\r\none\r\n");
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZScore = frame.try_into()?;
+    assert_eq!(ret.key, "myzset");
+    assert_eq!(ret.member, "one");
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zscore_execute() -> Result<()> {
+    let backend = Backend::new();
+    backend.zadd("myzset", vec![(1.5, "one".to_string())], ZAddOptions::default())?;
+
+    let ret = ZScore { key: "myzset".to_string(), member: "one".to_string() }.execute(&backend);
+    assert_eq!(ret, RespFrame::Double(1.5));
+    let ret = ZScore { key: "myzset".to_string(), member: "two".to_string() }.execute(&backend);
+    assert_eq!(ret, RESP_NULL.clone());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,208 @@
+use super::{
+  extract_args, extract_command_name, parse_float, parse_integer, parse_string,
+  zrange::scored_reply, CommandError, CommandExecutor,
+};
+use crate::{Aggregate, Backend, RespArray, RespFrame, ZSetOp};
+
+/// ZUNION numkeys key [key ...] [WEIGHTS weight [weight ...]] [AGGREGATE <SUM | MIN | MAX>]
+///   [WITHSCORES]
+/// ZINTER numkeys key [key ...] [WEIGHTS weight [weight ...]] [AGGREGATE <SUM | MIN | MAX>]
+///   [WITHSCORES]
+/// ZDIFF numkeys key [key ...] [WITHSCORES]
+/// ZUNIONSTORE destination numkeys key [key ...] [WEIGHTS weight [weight ...]]
+///   [AGGREGATE <SUM | MIN | MAX>]
+/// ZINTERSTORE destination numkeys key [key ...] [WEIGHTS weight [weight ...]]
+///   [AGGREGATE <SUM | MIN | MAX>]
+/// ZDIFFSTORE destination numkeys key [key ...]
+///  ZADD zset1 1 "one" 2 "two"
+/// (integer) 2
+///  ZADD zset2 1 "one" 2 "two" 3 "three"
+/// (integer) 3
+///  ZUNIONSTORE out 2 zset1 zset2 WEIGHTS 2 3
+/// (integer) 3
+///  ZINTER 2 zset1 zset2 AGGREGATE MAX WITHSCORES
+/// 1) 1) "one"
+///    2) (double) 1
+/// 2) 1) "two"
+///    2) (double) 2
+#[derive(Debug)]
+pub struct ZUnion {
+  pub(crate) op: ZSetOp,
+  pub(crate) destination: Option<String>,
+  pub(crate) keys: Vec<String>,
+  pub(crate) weights: Vec<f64>,
+  pub(crate) aggregate: Aggregate,
+  pub(crate) with_scores: bool,
+}
+
+/// Parse the `numkeys key [key ...]` arguments of a multi-key sorted set command.
+pub(crate) fn parse_keys(
+  args: &mut impl Iterator<Item = RespFrame>,
+  name: &str,
+) -> Result<Vec<String>, CommandError> {
+  let numkeys = parse_integer(args.next().ok_or(CommandError::SyntaxError)?)?;
+  if numkeys <= 0 {
+    return Err(CommandError::InvalidArgument(format!(
+      "at least 1 input key is needed for '{}' command",
+      name
+    )));
+  }
+  let keys = args.take(numkeys as usize).map(parse_string).collect::<Result<Vec<_>, _>>()?;
+  if keys.len() < numkeys as usize {
+    return Err(CommandError::SyntaxError);
+  }
+  Ok(keys)
+}
+
+impl CommandExecutor for ZUnion {
+  fn execute(self, backend: &Backend) -> RespFrame {
+    let items = match backend.zcombine(self.op, &self.keys, &self.weights, self.aggregate) {
+      Ok(items) => items,
+      Err(e) => return e.into(),
+    };
+    match self.destination {
+      Some(destination) => RespFrame::Integer(backend.zstore(&destination, items) as i64),
+      None => scored_reply(items, self.with_scores),
+    }
+  }
+}
+
+impl TryFrom<RespArray> for ZUnion {
+  type Error = CommandError;
+  fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+    let name = extract_command_name(&value)?;
+    let op = match name.trim_end_matches("store") {
+      "zunion" => ZSetOp::Union,
+      "zinter" => ZSetOp::Inter,
+      "zdiff" => ZSetOp::Diff,
+      _ => return Err(CommandError::InvalidCommand(format!("unexpected command {}", name))),
+    };
+
+    let mut args = extract_args(value, 1)?.into_iter();
+    let destination = match name.ends_with("store") {
+      true => Some(parse_string(args.next().ok_or(CommandError::SyntaxError)?)?),
+      false => None,
+    };
+    let keys = parse_keys(&mut args, &name)?;
+
+    let mut zunion = ZUnion {
+      op,
+      destination,
+      keys,
+      weights: vec![],
+      aggregate: Aggregate::Sum,
+      with_scores: false,
+    };
+    let is_diff = op == ZSetOp::Diff;
+    let is_store = zunion.destination.is_some();
+    while let Some(arg) = args.next() {
+      match parse_string(arg)?.to_ascii_lowercase().as_str() {
+        "weights" if !is_diff => {
+          // a repeated WEIGHTS replaces the previous one
+          zunion.weights.clear();
+          for _ in 0..zunion.keys.len() {
+            let weight = args.next().ok_or(CommandError::SyntaxError)?;
+            let weight = parse_float(weight).map_err(|_| {
+              CommandError::InvalidArgument("weight value is not a float".to_string())
+            })?;
+            zunion.weights.push(weight);
+          }
+        }
+        "aggregate" if !is_diff => {
+          let aggregate = parse_string(args.next().ok_or(CommandError::SyntaxError)?)?;
+          zunion.aggregate = match aggregate.to_ascii_lowercase().as_str() {
+            "sum" => Aggregate::Sum,
+            "min" => Aggregate::Min,
+            "max" => Aggregate::Max,
+            _ => return Err(CommandError::SyntaxError),
+          };
+        }
+        "withscores" if !is_store => zunion.with_scores = true,
+        _ => return Err(CommandError::SyntaxError),
+      }
+    }
+
+    Ok(zunion)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespDecode, ZAddOptions};
+  use anyhow::Result;
+  use bytes::BytesMut;
+
+  #[test]
+  fn test_zunion_from_resp_array() -> Result<()> {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(
+      b"*10\r\n$11\r\nZUNIONSTORE\r\n$3\r\nout\r\n$1\r\n2\r\n$5\r\nzset1\r\n$5\r\nzset2\r\n$7\r\nWEIGHTS\r\n$1\r\n2\r\n$1\r\n3\r\n$9\r\nAGGREGATE\r\n$3\r\nmin\r\n",
+    );
+
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZUnion = frame.try_into()?;
+    assert_eq!(ret.op, ZSetOp::Union);
+    assert_eq!(ret.destination.as_deref(), Some("out"));
+    assert_eq!(ret.keys, vec!["zset1", "zset2"]);
+    assert_eq!(ret.weights, vec![2.0, 3.0]);
+    assert_eq!(ret.aggregate, Aggregate::Min);
+
+    buf.extend_from_slice(
+      b"*9\r\n$6\r\nzunion\r\n$1\r\n1\r\n$1\r\na\r\n$7\r\nweights\r\n$1\r\n2\r\n$7\r\nweights\r\n$1\r\n5\r\n$7\r\nweights\r\n$1\r\n7\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    let ret: ZUnion = frame.try_into()?;
+    assert_eq!(ret.weights, vec![7.0]);
+
+    buf.extend_from_slice(
+      b"*5\r\n$5\r\nzdiff\r\n$1\r\n1\r\n$1\r\na\r\n$7\r\nweights\r\n$1\r\n1\r\n",
+    );
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZUnion::try_from(frame), Err(CommandError::SyntaxError)));
+
+    buf.extend_from_slice(b"*3\r\n$6\r\nzinter\r\n$1\r\n0\r\n$1\r\na\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert!(matches!(ZUnion::try_from(frame), Err(CommandError::InvalidArgument(_))));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_zunion_execute() -> Result<()> {
+    let backend = Backend::new();
+    let pairs = |items: &[(f64, &str)]| items.iter().map(|(s, m)| (*s, m.to_string())).collect();
+    backend.zadd("zset1", pairs(&[(1.0, "one"), (2.0, "two")]), ZAddOptions::default())?;
+    backend.zadd(
+      "zset2",
+      pairs(&[(1.0, "one"), (2.0, "two"), (3.0, "three")]),
+      ZAddOptions::default(),
+    )?;
+    let keys = vec!["zset1".to_string(), "zset2".to_string()];
+
+    let ret = ZUnion {
+      op: ZSetOp::Union,
+      destination: Some("out".to_string()),
+      keys: keys.clone(),
+      weights: vec![2.0, 3.0],
+      aggregate: Aggregate::Sum,
+      with_scores: false,
+    }
+    .execute(&backend);
+    assert_eq!(ret, RespFrame::Integer(3));
+    assert_eq!(backend.zscore("out", "two")?, Some(10.0));
+
+    let ret = ZUnion {
+      op: ZSetOp::Diff,
+      destination: None,
+      keys: keys.into_iter().rev().collect(),
+      weights: vec![],
+      aggregate: Aggregate::Sum,
+      with_scores: false,
+    }
+    .execute(&backend);
+    assert_eq!(ret, RespArray::new([BulkString::from("three").into()]).into());
+
+    Ok(())
+  }
+}
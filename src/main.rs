@@ -12,6 +12,7 @@ async fn main() -> Result<()> {
   let listener = TcpListener::bind(addr).await?;
 
   let backend = Backend::new();
+  backend.start_active_expire();
   loop {
     let (stream, raddr) = listener.accept().await?;
     info!("Accepted connection from: {}", raddr);
@@ -1,13 +1,14 @@
-use crate::{
-  cmd::{Command, CommandExecutor},
-  Backend, RespDecode, RespEncode, RespError, RespFrame,
-};
-use anyhow::Result;
+use crate::{cmd::Command, Backend, RespDecode, RespEncode, RespError, RespFrame};
+use anyhow::{bail, Result};
 use futures::SinkExt;
+use std::collections::VecDeque;
 use tokio::net::TcpStream;
 use tokio_stream::StreamExt;
 use tokio_util::codec::{Decoder, Encoder, Framed};
-use tracing::info;
+use tracing::{info, warn};
+
+/// How many frames a client may pipeline behind a blocking command before it is disconnected.
+const MAX_PENDING_FRAMES: usize = 1024;
 
 #[derive(Debug)]
 struct RespFrameCodec;
@@ -25,27 +26,54 @@ struct RedisResponse {
 
 pub async fn stream_handler(stream: TcpStream, backend: Backend) -> Result<()> {
   let mut framed = Framed::new(stream, RespFrameCodec);
+  // frames pipelined by the client while a blocking command waits
+  let mut pending = VecDeque::new();
   loop {
-    match framed.next().await {
-      Some(Ok(frame)) => {
-        info!("Received frame: {:?}", frame);
-        let request = RedisRequest { frame, backend: backend.clone() };
-        let response = request_handler(request).await?;
-        info!("Sending response frame: {:?}", response.frame);
-        framed.send(response.frame).await?;
+    let frame = match pending.pop_front() {
+      Some(frame) => frame,
+      None => match framed.next().await {
+        Some(Ok(frame)) => frame,
+        Some(Err(e)) => return Err(e),
+        None => return Ok(()),
+      },
+    };
+    info!("Received frame: {:?}", frame);
+    let request = RedisRequest { frame, backend: backend.clone() };
+    let handler = request_handler(request);
+    tokio::pin!(handler);
+    // keep reading while the command runs, so a client leaving mid-wait unparks it
+    let response = loop {
+      tokio::select! {
+        biased;
+        response = &mut handler => break response,
+        frame = framed.next() => match frame {
+          Some(Ok(_)) if pending.len() >= MAX_PENDING_FRAMES => {
+            bail!("more than {} frames pipelined behind a blocking command", MAX_PENDING_FRAMES)
+          }
+          Some(Ok(frame)) => pending.push_back(frame),
+          Some(Err(e)) => return Err(e),
+          None => return Ok(()),
+        },
       }
-      Some(Err(e)) => return Err(e),
-      None => return Ok(()),
-    }
+    };
+    info!("Sending response frame: {:?}", response.frame);
+    framed.send(response.frame).await?;
   }
 }
 
-async fn request_handler(request: RedisRequest) -> Result<RedisResponse> {
+async fn request_handler(request: RedisRequest) -> RedisResponse {
   let (frame, backend) = (request.frame, request.backend);
-  let cmd = Command::try_from(frame)?;
-  info!("Executing command: {:?}", cmd);
-  let frame: RespFrame = cmd.execute(&backend);
-  Ok(RedisResponse { frame })
+  let frame: RespFrame = match Command::try_from(frame) {
+    Ok(cmd) => {
+      info!("Executing command: {:?}", cmd);
+      cmd.run(&backend).await
+    }
+    Err(e) => {
+      warn!("Invalid command: {:?}", e);
+      e.into()
+    }
+  };
+  RedisResponse { frame }
 }
 
 impl Encoder<RespFrame> for RespFrameCodec {
@@ -70,3 +98,45 @@ impl Decoder for RespFrameCodec {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{BulkString, RespArray, SimpleError};
+
+  #[tokio::test]
+  async fn test_request_handler_replies_error_on_bad_command() {
+    let frame = RespArray::new([BulkString::from("get").into()]).into();
+    let request = RedisRequest { frame, backend: Backend::new() };
+    let response = request_handler(request).await;
+
+    assert!(
+      matches!(response.frame, RespFrame::Error(SimpleError(ref msg)) if msg.starts_with("ERR "))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_stream_handler_caps_frames_pipelined_while_blocked() -> Result<()> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let mut client = Framed::new(TcpStream::connect(listener.local_addr()?).await?, RespFrameCodec);
+    let (stream, _) = listener.accept().await?;
+    let backend = Backend::new();
+    let handler = tokio::spawn(stream_handler(stream, backend.clone()));
+
+    let command = |args: &[&str]| -> RespFrame {
+      RespArray::new(args.iter().map(|arg| BulkString::from(*arg).into()).collect::<Vec<_>>())
+        .into()
+    };
+    client.feed(command(&["BLPOP", "list", "0"])).await?;
+    for _ in 0..=MAX_PENDING_FRAMES {
+      client.feed(command(&["GET", "key"])).await?;
+    }
+    client.flush().await?;
+
+    assert!(handler.await?.is_err());
+    // the blocked command went away with the connection
+    assert!(backend.blocked.lock().unwrap().is_empty());
+    assert!(client.next().await.is_none());
+    Ok(())
+  }
+}
@@ -109,6 +109,21 @@ mod tests {
     Ok(())
   }
 
+  #[test]
+  fn test_array_decode_partial_element() -> Result<()> {
+    let mut buf = BytesMut::new();
+    // the length of the last element arrived, but not all of its bytes
+    buf.extend_from_slice(b"*2\r\n$3\r\nget\r\n$5\r\nhel");
+    let ret = RespArray::decode(&mut buf);
+    assert_eq!(ret.unwrap_err(), RespError::NotComplete);
+
+    buf.extend_from_slice(b"lo\r\n");
+    let frame = RespArray::decode(&mut buf)?;
+    assert_eq!(frame, RespArray::new([b"get".into(), b"hello".into()]));
+
+    Ok(())
+  }
+
   #[test]
   fn test_null_array_encode() {
     let frame: RespFrame = RespArray::default().into();
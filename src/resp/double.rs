@@ -6,7 +6,10 @@ use bytes::BytesMut;
 impl RespEncode for f64 {
   fn encode(self) -> Vec<u8> {
     let mut buf = Vec::with_capacity(32);
-    let ret = if self.abs() > 1e+8 || self.abs() < 1e-8 {
+    let ret = if self.is_infinite() || self == 0.0 {
+      // `inf`, `-inf` and `0` have no exponent form in RESP3
+      format!(",{}\r\n", self)
+    } else if self.abs() > 1e+8 || self.abs() < 1e-8 {
       format!(",{:+e}\r\n", self)
     } else {
       format!(",{}\r\n", self)
@@ -51,6 +54,12 @@ mod tests {
 
     let frame: RespFrame = (-1.23456e-9).into();
     assert_eq!(frame.encode(), b",-1.23456e-9\r\n");
+
+    let frame: RespFrame = f64::NEG_INFINITY.into();
+    assert_eq!(frame.encode(), b",-inf\r\n");
+
+    let frame: RespFrame = 0.0.into();
+    assert_eq!(frame.encode(), b",0\r\n");
   }
 
   #[test]
@@ -112,7 +112,8 @@ fn calc_total_length(buf: &[u8], end: usize, len: usize, prefix: &str) -> Result
       // find nth CRLF in the buffer, for array and set, we need to find 1 CRLF for each element
       for _ in 0..len {
         let frame_len = RespFrame::expect_length(data)?;
-        data = &data[frame_len..];
+        // the element's length is known before all of it arrived
+        data = data.get(frame_len..).ok_or(RespError::NotComplete)?;
         total += frame_len;
       }
       Ok(total)